@@ -0,0 +1,645 @@
+//! Comparisons of [Rope<M>]s and [RopeSlice<M>]s that skip over the parts of
+//! their trees that they share.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::config::RopeConfig;
+use crate::rope::{Measurable, Rope};
+use crate::slice::{RSEnum, RopeSlice};
+use crate::tree::Node;
+
+/// The nodes from the root of a tree down to the leaf holding some index,
+/// each with the index at which it starts, and its length.
+type NodePath<'a, M, C> = Vec<(&'a Arc<Node<M, C>>, usize, usize)>;
+
+/// Nodes of a tree at the same height, each with the index at which it
+/// starts, and its length.
+type NodeLevel<'a, M, C> = Vec<(&'a Arc<Node<M, C>>, usize, usize)>;
+
+impl<M, C> Rope<M, C>
+where
+    M: Measurable + PartialEq,
+    C: RopeConfig,
+{
+    /// Returns the width of the longest run of elements that both
+    /// [Rope<M>]s start with.
+    ///
+    /// Subtrees that both [Rope<M>]s share, such as after cloning one and
+    /// editing the clone, are skipped without looking at their elements. This
+    /// makes it cheap to find where two versions of a [Rope<M>] diverge.
+    ///
+    /// Runs in O(log N) time per shared subtree, plus O(M) time for the
+    /// elements that had to be compared.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let rope = Rope::from_slice(&[Lorem, Ipsum].repeat(1000));
+    /// let mut edited = rope.clone();
+    /// edited.insert(1500, Dolor(5));
+    ///
+    /// assert_eq!(rope.common_prefix_width(&edited), 1500);
+    /// assert_eq!(rope.common_suffix_width(&edited), rope.width() - 1500);
+    /// ```
+    #[inline]
+    pub fn common_prefix_width(&self, other: &Self) -> usize {
+        self.width_slice(..)
+            .common_prefix_width(&other.width_slice(..))
+    }
+
+    /// Returns the width of the longest run of elements that both
+    /// [Rope<M>]s end with.
+    ///
+    /// Subtrees that both [Rope<M>]s share are skipped, the same as in
+    /// [common_prefix_width()][Rope::common_prefix_width].
+    ///
+    /// Runs in O(log N) time per shared subtree, plus O(M) time for the
+    /// elements that had to be compared.
+    #[inline]
+    pub fn common_suffix_width(&self, other: &Self) -> usize {
+        self.width_slice(..)
+            .common_suffix_width(&other.width_slice(..))
+    }
+
+    /// Finds the regions that differ between an `old` version of the
+    /// [Rope<M>] and this one, returning the width range of each region in
+    /// `old`, and in `self`, in order.
+    ///
+    /// Both trees are walked down from their roots together, and subtrees
+    /// that they share are matched up without looking at their elements, so
+    /// this is cheap when `self` was made by editing a clone of `old`. The
+    /// regions are then trimmed down to the elements that actually differ.
+    ///
+    /// Regions that only differ in elements with a width of 0 have empty
+    /// width ranges.
+    ///
+    /// Runs in O(K log N) time, where K is the amount of leaves that differ.
+    /// If the [Rope<M>]s share nothing, that is O(N).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let old = Rope::from_slice(&[Lorem, Ipsum].repeat(1000));
+    /// let mut new = old.clone();
+    /// new.insert(300, Dolor(5));
+    /// new.insert(1500, Dolor(7));
+    ///
+    /// assert_eq!(
+    ///     new.changed_ranges(&old),
+    ///     vec![(300..300, 300..305), (1495..1495, 1500..1507)]
+    /// );
+    /// ```
+    pub fn changed_ranges(&self, old: &Self) -> Vec<(Range<usize>, Range<usize>)> {
+        let mut old_level = vec![(&old.root, 0, old.len())];
+        let mut new_level = vec![(&self.root, 0, self.len())];
+        let mut old_height = old.root.depth();
+        let mut new_height = self.root.depth();
+
+        // The `(old_start, new_start, len)` of the subtrees found in both.
+        let mut shared = Vec::new();
+        while !old_level.is_empty() && !new_level.is_empty() {
+            if old_height == new_height {
+                match_level(&mut old_level, &mut new_level, &mut shared);
+                if old_height == 0 {
+                    break;
+                }
+            }
+
+            if old_height >= new_height {
+                old_level = expand_level(&old_level);
+                old_height -= 1;
+            }
+            if new_height > old_height {
+                new_level = expand_level(&new_level);
+                new_height -= 1;
+            }
+        }
+
+        // Nodes can show up more than once in a tree, so only the shared
+        // subtrees that are in the same order in both trees are kept.
+        shared.sort_unstable();
+        let mut gaps = Vec::new();
+        let (mut old_pos, mut new_pos) = (0, 0);
+        for (old_start, new_start, len) in shared {
+            if old_start < old_pos || new_start < new_pos {
+                continue;
+            }
+            gaps.push((old_pos..old_start, new_pos..new_start));
+            (old_pos, new_pos) = (old_start + len, new_start + len);
+        }
+        gaps.push((old_pos..old.len(), new_pos..self.len()));
+
+        gaps.into_iter()
+            .filter_map(|(old_range, new_range)| {
+                let old_slice = old.index_slice(old_range.clone());
+                let new_slice = self.index_slice(new_range.clone());
+
+                let prefix = old_slice.common_prefix_len(&new_slice);
+                let old_slice = old_slice.index_slice(prefix..);
+                let new_slice = new_slice.index_slice(prefix..);
+                let suffix = old_slice.common_suffix_len(&new_slice);
+
+                let old_range = old_range.start + prefix..old_range.end - suffix;
+                let new_range = new_range.start + prefix..new_range.end - suffix;
+                if old_range.is_empty() && new_range.is_empty() {
+                    return None;
+                }
+
+                Some((
+                    old.index_to_width(old_range.start)..old.index_to_width(old_range.end),
+                    self.index_to_width(new_range.start)..self.index_to_width(new_range.end),
+                ))
+            })
+            .collect()
+    }
+}
+
+impl<'a, M, C> RopeSlice<'a, M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    /// Returns the width of the longest run of elements that both
+    /// [RopeSlice<M>]s start with.
+    ///
+    /// Same as [Rope::common_prefix_width()], but for [RopeSlice<M>]s.
+    ///
+    /// Runs in O(log N) time per shared subtree, plus O(M) time for the
+    /// elements that had to be compared.
+    #[inline]
+    pub fn common_prefix_width(&self, other: &RopeSlice<M, C>) -> usize
+    where
+        M: PartialEq,
+    {
+        self.index_to_width(self.common_prefix_len(other))
+    }
+
+    /// Returns the width of the longest run of elements that both
+    /// [RopeSlice<M>]s end with.
+    ///
+    /// Same as [Rope::common_suffix_width()], but for [RopeSlice<M>]s.
+    ///
+    /// Runs in O(log N) time per shared subtree, plus O(M) time for the
+    /// elements that had to be compared.
+    #[inline]
+    pub fn common_suffix_width(&self, other: &RopeSlice<M, C>) -> usize
+    where
+        M: PartialEq,
+    {
+        let len = self.common_suffix_len(other);
+        self.width() - self.index_to_width(self.len() - len)
+    }
+
+    //-----------------------------------------------------------------------
+    // Internal utilities
+
+    /// The amount of elements that both [RopeSlice<M>]s start with.
+    pub(crate) fn common_prefix_len(&self, other: &RopeSlice<M, C>) -> usize
+    where
+        M: PartialEq,
+    {
+        let max = self.len().min(other.len());
+        let (mut cursor1, mut cursor2) = (Cursor::new(self), Cursor::new(other));
+        let mut index = 0;
+
+        while index < max {
+            let (leaf1, offset1) = cursor1.leaf_at(index);
+            let (leaf2, offset2) = cursor2.leaf_at(index);
+
+            // Everything from here to the end of a shared node is the same.
+            if let Some((offset, len)) = cursor1.shared_node(&cursor2) {
+                index += len - offset;
+                continue;
+            }
+
+            let len = (leaf1.len() - offset1)
+                .min(leaf2.len() - offset2)
+                .min(max - index);
+            let chunk1 = &leaf1[offset1..offset1 + len];
+            let chunk2 = &leaf2[offset2..offset2 + len];
+            match chunk1.iter().zip(chunk2).position(|(m1, m2)| m1 != m2) {
+                Some(pos) => return index + pos,
+                None => index += len,
+            }
+        }
+
+        max
+    }
+
+    /// The amount of elements that both [RopeSlice<M>]s end with.
+    pub(crate) fn common_suffix_len(&self, other: &RopeSlice<M, C>) -> usize
+    where
+        M: PartialEq,
+    {
+        let max = self.len().min(other.len());
+        let (mut cursor1, mut cursor2) = (Cursor::new(self), Cursor::new(other));
+        let mut count = 0;
+
+        while count < max {
+            let (leaf1, offset1) = cursor1.leaf_at(self.len() - 1 - count);
+            let (leaf2, offset2) = cursor2.leaf_at(other.len() - 1 - count);
+
+            // Everything from the start of a shared node to here is the same.
+            if let Some((offset, _)) = cursor1.shared_node(&cursor2) {
+                count += offset + 1;
+                continue;
+            }
+
+            let len = (offset1 + 1).min(offset2 + 1).min(max - count);
+            let chunk1 = &leaf1[offset1 + 1 - len..=offset1];
+            let chunk2 = &leaf2[offset2 + 1 - len..=offset2];
+            match chunk1.iter().zip(chunk2).rposition(|(m1, m2)| m1 != m2) {
+                Some(pos) => return count + (len - 1 - pos),
+                None => count += len,
+            }
+        }
+
+        max
+    }
+
+    /// Compares the elements of both [RopeSlice<M>]s, skipping the subtrees
+    /// that they share.
+    pub(crate) fn shared_cmp(&self, other: &RopeSlice<M, C>) -> Ordering
+    where
+        M: Ord,
+    {
+        let index = self.common_prefix_len(other);
+        if index == self.len() || index == other.len() {
+            return self.len().cmp(&other.len());
+        }
+
+        let (leaf1, offset1) = Cursor::new(self).leaf_at(index);
+        let (leaf2, offset2) = Cursor::new(other).leaf_at(index);
+        leaf1[offset1].cmp(&leaf2[offset2])
+    }
+}
+
+/// A position in a [RopeSlice<M>], along with the path from the root down to
+/// the leaf holding it.
+///
+/// Moving to a new position only walks back up the path as far as needed,
+/// so going through the leaves in order takes amortized O(1) time per leaf.
+struct Cursor<'a, M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    root: Option<&'a Arc<Node<M, C>>>,
+    /// The elements of a [RopeSlice<M>] that isn't backed by a tree.
+    light: &'a [M],
+    /// The index in `root` at which the [RopeSlice<M>] starts.
+    start: usize,
+    /// The position in `root` that `path` leads to.
+    pos: usize,
+    path: NodePath<'a, M, C>,
+}
+
+impl<'a, M, C> Cursor<'a, M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    fn new(slice: &RopeSlice<'a, M, C>) -> Self {
+        let (root, light, start) = match slice.0 {
+            RSEnum::Full {
+                node, start_info, ..
+            } => (Some(node), &[][..], start_info.len as usize),
+            RSEnum::Light { slice } => (None, slice, 0),
+        };
+        Cursor {
+            root,
+            light,
+            start,
+            pos: start,
+            path: Vec::new(),
+        }
+    }
+
+    /// Moves to the element at `index`, returning the leaf holding it, and
+    /// the offset of `index` into that leaf.
+    fn leaf_at(&mut self, index: usize) -> (&'a [M], usize) {
+        let Some(root) = self.root else {
+            return (self.light, index);
+        };
+
+        self.pos = self.start + index;
+        while let Some(&(_, start, len)) = self.path.last() {
+            if (start..start + len).contains(&self.pos) {
+                break;
+            }
+            self.path.pop();
+        }
+        if self.path.is_empty() {
+            self.path.push((root, 0, root.len()));
+        }
+
+        loop {
+            let (node, start, _) = *self.path.last().unwrap();
+            match **node {
+                Node::Leaf(ref slice) => return (slice, self.pos - start),
+                Node::Branch(ref children) => {
+                    let (child_i, acc_info) = children.search_index(self.pos - start);
+                    self.path.push((
+                        &children.nodes()[child_i],
+                        start + acc_info.len as usize,
+                        children.info()[child_i].0.len as usize,
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Finds the highest node that both cursors are in, at the same offset
+    /// into it, returning that offset, and the length of the node.
+    ///
+    /// Only looks through the paths when both cursors are in the same leaf,
+    /// since no bigger node can be shared otherwise.
+    fn shared_node(&self, other: &Self) -> Option<(usize, usize)> {
+        let (&(leaf1, start1, _), &(leaf2, start2, _)) = (self.path.last()?, other.path.last()?);
+        if !Arc::ptr_eq(leaf1, leaf2) || self.pos - start1 != other.pos - start2 {
+            return None;
+        }
+
+        self.path.iter().find_map(|&(node1, start1, len)| {
+            let offset = self.pos - start1;
+            other
+                .path
+                .iter()
+                .any(|&(node2, start2, _)| {
+                    other.pos - start2 == offset && Arc::ptr_eq(node1, node2)
+                })
+                .then_some((offset, len))
+        })
+    }
+}
+
+/// Removes the nodes that are in both levels, adding their positions to
+/// `shared`.
+fn match_level<M, C>(
+    old_level: &mut NodeLevel<M, C>,
+    new_level: &mut NodeLevel<M, C>,
+    shared: &mut Vec<(usize, usize, usize)>,
+) where
+    M: Measurable,
+    C: RopeConfig,
+{
+    let mut old_nodes: HashMap<*const Node<M, C>, Vec<usize>> = HashMap::new();
+    for (i, (node, ..)) in old_level.iter().enumerate().rev() {
+        old_nodes.entry(Arc::as_ptr(node)).or_default().push(i);
+    }
+
+    let mut matched = vec![false; old_level.len()];
+    new_level.retain(|(node, new_start, len)| {
+        let old_i = old_nodes
+            .get_mut(&Arc::as_ptr(node))
+            .and_then(|indices| indices.pop());
+        match old_i {
+            Some(old_i) => {
+                matched[old_i] = true;
+                shared.push((old_level[old_i].1, *new_start, *len));
+                false
+            }
+            None => true,
+        }
+    });
+
+    let mut matched = matched.into_iter();
+    old_level.retain(|_| !matched.next().unwrap());
+}
+
+/// Replaces every branch in a level with its children.
+fn expand_level<'a, M, C>(level: &NodeLevel<'a, M, C>) -> NodeLevel<'a, M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    let mut children_level = Vec::new();
+    for (node, start, _) in level {
+        let mut child_start = *start;
+        for ((info, _), child) in node.children().iter() {
+            children_level.push((child, child_start, info.len as usize));
+            child_start += info.len as usize;
+        }
+    }
+    children_level
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use crate::Lipsum::{self, *};
+    use crate::{Rope, RopeSlice};
+
+    fn lorem_ipsum() -> Vec<Lipsum> {
+        (0..500)
+            .map(|i| match i % 6 {
+                0 => Lorem,
+                1 => Ipsum,
+                2 => Dolor(i % 5),
+                3 => Sit,
+                4 => Amet,
+                _ => Consectur("adipiscing"),
+            })
+            .collect()
+    }
+
+    fn prefix_len(vec1: &[Lipsum], vec2: &[Lipsum]) -> usize {
+        vec1.iter()
+            .zip(vec2)
+            .take_while(|(m1, m2)| m1 == m2)
+            .count()
+    }
+
+    fn suffix_len(vec1: &[Lipsum], vec2: &[Lipsum]) -> usize {
+        let iter = vec1.iter().rev().zip(vec2.iter().rev());
+        iter.take_while(|(m1, m2)| m1 == m2).count()
+    }
+
+    #[test]
+    fn common_prefix_01() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let clone = rope.clone();
+        assert_eq!(
+            rope.width_slice(..)
+                .common_prefix_len(&clone.width_slice(..)),
+            rope.len()
+        );
+        assert_eq!(rope.common_prefix_width(&clone), rope.width());
+        assert_eq!(rope.common_suffix_width(&clone), rope.width());
+
+        for index in [0, 1, 100, 250, 499, 500] {
+            let mut edited = rope.clone();
+            edited.insert_rope_at_index(index, Rope::from_slice(&[Dolor(9)]));
+            let (vec1, vec2) = (Vec::from(&rope), Vec::from(&edited));
+
+            let (slice1, slice2) = (rope.width_slice(..), edited.width_slice(..));
+            assert_eq!(slice1.common_prefix_len(&slice2), prefix_len(&vec1, &vec2));
+            assert_eq!(slice1.common_suffix_len(&slice2), suffix_len(&vec1, &vec2));
+            assert_eq!(slice2.common_prefix_len(&slice1), prefix_len(&vec1, &vec2));
+            assert_eq!(slice2.common_suffix_len(&slice1), suffix_len(&vec1, &vec2));
+
+            // The same, but without any shared nodes.
+            let light1 = RopeSlice::from(vec1.as_slice());
+            assert_eq!(light1.common_prefix_len(&slice2), prefix_len(&vec1, &vec2));
+            assert_eq!(light1.common_suffix_len(&slice2), suffix_len(&vec1, &vec2));
+        }
+    }
+
+    #[test]
+    fn common_prefix_02() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let mut edited = rope.clone();
+        edited.remove(300..320);
+        edited.insert_slice(10, &[Lorem, Lorem]);
+
+        let (vec1, vec2) = (Vec::from(&rope), Vec::from(&edited));
+        for (start, end) in [(0, 200), (5, 400), (50, 60), (300, 300)] {
+            let slice1 = rope.width_slice(start..end);
+            let slice2 = edited.width_slice(start..end);
+            let (vec1, vec2) = (Vec::from(slice1), Vec::from(slice2));
+
+            assert_eq!(slice1.common_prefix_len(&slice2), prefix_len(&vec1, &vec2));
+            assert_eq!(slice1.common_suffix_len(&slice2), suffix_len(&vec1, &vec2));
+        }
+
+        let prefix = prefix_len(&vec1, &vec2);
+        let suffix = suffix_len(&vec1, &vec2);
+        assert_eq!(
+            rope.common_prefix_width(&edited),
+            rope.index_to_width(prefix)
+        );
+        assert_eq!(
+            rope.common_suffix_width(&edited),
+            rope.width() - rope.index_to_width(rope.len() - suffix)
+        );
+    }
+
+    #[test]
+    fn common_prefix_03() {
+        // Ropes that share no nodes, and have their leaves split differently.
+        let vec = lorem_ipsum();
+        let rope = Rope::from_slice(vec.as_slice());
+        let mut other = Rope::from_slice(&vec[..7]);
+        for chunk in vec[7..].chunks(13) {
+            other.append(Rope::from_slice(chunk));
+        }
+
+        let (slice1, slice2) = (rope.width_slice(..), other.width_slice(..));
+        assert_eq!(slice1.common_prefix_len(&slice2), vec.len());
+        assert_eq!(slice1.common_suffix_len(&slice2), vec.len());
+        assert_eq!(rope, other);
+
+        for index in [0, 1, 100, 250, 499] {
+            let mut edited_vec = vec.clone();
+            edited_vec[index] = Dolor(9);
+            let edited = Rope::from_slice(edited_vec.as_slice());
+
+            let (slice1, slice2) = (other.width_slice(..), edited.width_slice(..));
+            assert_eq!(slice1.common_prefix_len(&slice2), index);
+            assert_eq!(slice1.common_suffix_len(&slice2), vec.len() - 1 - index);
+            assert_eq!(other.cmp(&edited), vec.cmp(&edited_vec));
+            assert_ne!(other, edited);
+        }
+    }
+
+    #[test]
+    fn shared_cmp_01() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let vec = Vec::from(&rope);
+
+        for index in [0, 1, 100, 250, 499] {
+            for measurable in [Lorem, Dolor(2), Consectur("z")] {
+                let mut edited = rope.clone();
+                edited.remove_index_range(index, index + 1);
+                edited.insert_rope_at_index(index, Rope::from_slice(&[measurable]));
+                let edited_vec = Vec::from(&edited);
+
+                assert_eq!(rope.cmp(&edited), vec.cmp(&edited_vec));
+                assert_eq!(edited.cmp(&rope), edited_vec.cmp(&vec));
+                assert_eq!(rope == edited, vec == edited_vec);
+            }
+        }
+
+        let mut shorter = rope.clone();
+        shorter.remove_index_range(400, 500);
+        assert_eq!(rope.cmp(&shorter), Ordering::Greater);
+        assert_eq!(shorter.cmp(&rope), Ordering::Less);
+        assert_ne!(rope, shorter);
+        assert_eq!(rope.cmp(&rope.clone()), Ordering::Equal);
+    }
+
+    /// The changed ranges between two versions, found by comparing all of
+    /// their elements, as `(old_index_range, new_index_range)`.
+    fn changed_index_ranges(old: &[Lipsum], new: &[Lipsum]) -> (usize, usize, usize) {
+        let prefix = prefix_len(old, new);
+        let suffix = suffix_len(&old[prefix..], &new[prefix..]);
+        (prefix, old.len() - suffix, new.len() - suffix)
+    }
+
+    #[test]
+    fn changed_ranges_01() {
+        let old = Rope::from_slice(lorem_ipsum().as_slice());
+        assert_eq!(old.changed_ranges(&old.clone()), vec![]);
+        assert_eq!(old.changed_ranges(&Rope::from(Vec::from(&old))), vec![]);
+
+        for (start, end) in [(0, 0), (0, 10), (100, 100), (200, 230), (490, 500)] {
+            let mut new = old.clone();
+            new.remove_index_range(start, end);
+            new.insert_rope_at_index(start, Rope::from_slice(&[Dolor(9), Lorem]));
+
+            let (prefix, old_end, new_end) =
+                changed_index_ranges(&Vec::from(&old), &Vec::from(&new));
+            let expected = (
+                old.index_to_width(prefix)..old.index_to_width(old_end),
+                new.index_to_width(prefix)..new.index_to_width(new_end),
+            );
+            assert_eq!(new.changed_ranges(&old), vec![expected.clone()]);
+
+            // The other way around, the ranges are swapped.
+            assert_eq!(old.changed_ranges(&new), vec![(expected.1, expected.0)]);
+        }
+    }
+
+    #[test]
+    fn changed_ranges_02() {
+        let old = Rope::from_slice(lorem_ipsum().as_slice());
+        let mut new = old.clone();
+        new.remove_index_range(400, 420);
+        new.insert_rope_at_index(250, Rope::from_slice(&[Ipsum; 30]));
+        new.remove_index_range(20, 21);
+
+        // The removal at index 20 is of a `Dolor(0)`, so its ranges are empty.
+        let expected = [(20, 21, 20, 20), (250, 250, 249, 279), (400, 420, 429, 429)].map(
+            |(old_start, old_end, new_start, new_end)| {
+                (
+                    old.index_to_width(old_start)..old.index_to_width(old_end),
+                    new.index_to_width(new_start)..new.index_to_width(new_end),
+                )
+            },
+        );
+        assert_eq!(new.changed_ranges(&old), expected);
+    }
+
+    #[test]
+    fn changed_ranges_03() {
+        // Nothing is shared between these.
+        let old = Rope::from_slice(lorem_ipsum().as_slice());
+        let new = Rope::from_slice(&[Lorem, Ipsum]);
+        let ranges = new.changed_ranges(&old);
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(ranges[0].1.end, new.width());
+        assert_eq!(ranges[0].0.end, old.width());
+
+        let empty: Rope<Lipsum> = Rope::new();
+        assert_eq!(empty.changed_ranges(&old), vec![(0..old.width(), 0..0)]);
+        assert_eq!(empty.changed_ranges(&empty), vec![]);
+    }
+}
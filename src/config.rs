@@ -0,0 +1,173 @@
+use std::fmt::Debug;
+use std::mem::size_of;
+
+use crate::tree::{AccumCache, CacheStorage, NoAccumCache, MAX_CHILDREN, MAX_LEAF_BYTES};
+
+/// Compile time parameters for the tree behind a [Rope<M, C>][crate::Rope].
+///
+/// Configurations are meant to be marker types, with no values of their own.
+///
+/// Every [Rope<M>][crate::Rope] uses [DefaultConfig] unless told otherwise,
+/// which aims for nodes of around 1024 bytes. Smaller leaves make edits
+/// cheaper, while bigger ones make reading and iterating faster, and use less
+/// memory on the tree's structure.
+///
+/// # Examples
+///
+/// ```rust
+/// # use any_rope::{CacheWidths, Rope, RopeConfig};
+/// # use any_rope::Lipsum::{self, *};
+/// // A configuration for read-mostly ropes.
+/// #[derive(Debug, Clone, Copy)]
+/// struct BigLeaves;
+///
+/// impl RopeConfig for BigLeaves {
+///     const MAX_CHILDREN: usize = 8;
+///     const LEAF_BYTES: usize = 16 * 1024;
+///     type Cache = CacheWidths;
+/// }
+///
+/// let rope = Rope::<Lipsum, BigLeaves>::from_slice_with_config(&[Lorem, Ipsum, Dolor(3)]);
+/// assert_eq!(rope, [Lorem, Ipsum, Dolor(3)].as_slice());
+/// ```
+pub trait RopeConfig: Debug + Clone + Copy + 'static {
+    /// The maximum number of children of each branch node.
+    ///
+    /// Must be at least 4, and no greater than
+    /// [DefaultConfig::MAX_CHILDREN], which is the capacity of branch nodes.
+    /// Breaking these bounds is a compile time error.
+    const MAX_CHILDREN: usize;
+
+    /// The amount of bytes that each leaf node aims to store.
+    ///
+    /// Leaves bigger than [DefaultConfig::LEAF_BYTES] store their elements in
+    /// a separate heap allocation.
+    const LEAF_BYTES: usize;
+
+    /// Whether nodes should cache the accumulated widths of their contents,
+    /// either [NoCache] or [CacheWidths].
+    ///
+    /// With [CacheWidths], finding a width or index within a leaf, or within
+    /// a branch with many children, is done through a binary search, instead
+    /// of a linear scan that calls [width()][crate::Measurable::width] on
+    /// every element along the way. This pays off when
+    /// [width()][crate::Measurable::width] is expensive, or when leaves are
+    /// big.
+    ///
+    /// The caches are built by the first search through a node after it
+    /// changes, which takes an allocation and as much time as a linear scan,
+    /// so ropes that are edited far more than they are queried should use
+    /// [NoCache]. With [NoCache], nodes don't store a cache at all.
+    type Cache: CacheMode;
+
+    /// The maximum number of elements of type `M` in each leaf node.
+    ///
+    /// By default, this is as many elements as fit in
+    /// [LEAF_BYTES][RopeConfig::LEAF_BYTES]. Values smaller than 8 are
+    /// rounded up to 8, which also covers elements too big to fit in a leaf.
+    fn max_len<M>() -> usize {
+        match size_of::<M>() {
+            // Zero sized elements take no space at all, so just use the same
+            // count as for bytes.
+            0 => Self::LEAF_BYTES,
+            size => Self::LEAF_BYTES / size,
+        }
+    }
+}
+
+/// Whether the nodes of a [Rope<M, C>][crate::Rope] cache the accumulated
+/// widths of their contents, set through [RopeConfig::Cache].
+///
+/// This is implemented only by [NoCache] and [CacheWidths].
+pub trait CacheMode: sealed::Sealed + Debug + Clone + Copy + 'static {
+    /// Whether caching is enabled.
+    const ENABLED: bool;
+
+    /// What nodes store for their caches.
+    #[doc(hidden)]
+    type Storage<T: Send + Sync>: CacheStorage<T>;
+}
+
+/// A [CacheMode] where nodes don't cache anything, and take no space for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoCache;
+
+impl CacheMode for NoCache {
+    const ENABLED: bool = false;
+    type Storage<T: Send + Sync> = NoAccumCache;
+}
+
+/// A [CacheMode] where nodes cache the accumulated widths of their contents,
+/// for binary searches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheWidths;
+
+impl CacheMode for CacheWidths {
+    const ENABLED: bool = true;
+    type Storage<T: Send + Sync> = AccumCache<T>;
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for super::NoCache {}
+    impl Sealed for super::CacheWidths {}
+}
+
+/// The [RopeConfig] used by default, with nodes of around 1024 bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DefaultConfig;
+
+impl RopeConfig for DefaultConfig {
+    const MAX_CHILDREN: usize = MAX_CHILDREN;
+    const LEAF_BYTES: usize = MAX_LEAF_BYTES;
+    type Cache = NoCache;
+
+    // Smaller leaves in tests, in order to trigger deeper trees without having
+    // to use huge slice data.
+    #[cfg(test)]
+    fn max_len<M>() -> usize {
+        9
+    }
+}
+
+/// A [RopeConfig] with the smallest nodes possible.
+///
+/// This makes for very deep trees, even with few elements, which is not
+/// efficient, but is useful for stressing the tree in tests.
+///
+/// # Examples
+///
+/// ```rust
+/// # use any_rope::{Rope, SmallConfig};
+/// # use any_rope::Lipsum::{self, *};
+/// let mut rope = Rope::<Lipsum, SmallConfig>::from_slice_with_config(&[Lorem; 1000]);
+/// rope.remove(10..990);
+///
+/// assert_eq!(rope, [Lorem; 20].as_slice());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SmallConfig;
+
+impl RopeConfig for SmallConfig {
+    const MAX_CHILDREN: usize = 5;
+    const LEAF_BYTES: usize = 0;
+    type Cache = NoCache;
+}
+
+/// The [DefaultConfig], with cached widths, for testing the searches that use
+/// them.
+#[cfg(test)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CachedConfig;
+
+#[cfg(test)]
+impl RopeConfig for CachedConfig {
+    const MAX_CHILDREN: usize = DefaultConfig::MAX_CHILDREN;
+    const LEAF_BYTES: usize = DefaultConfig::LEAF_BYTES;
+    type Cache = CacheWidths;
+
+    fn max_len<M>() -> usize {
+        DefaultConfig::max_len::<M>()
+    }
+}
@@ -0,0 +1,524 @@
+//! Dumps of the tree behind a [Rope<M>], for debugging.
+
+use std::collections::HashSet;
+use std::fmt::{self, Debug, Write};
+use std::sync::Arc;
+
+use crate::config::{DefaultConfig, RopeConfig};
+use crate::iter::{Chunks, Iter};
+use crate::rope::{Measurable, Rope};
+use crate::slice::RopeSlice;
+use crate::tree::{Node, SliceInfo};
+
+/// The chunks of a [Rope<M>] or [RopeSlice<M>], printed by [Debug] as a list
+/// of lists, returned by [debug_chunks()][Rope::debug_chunks].
+///
+/// Unlike the elements themselves, the way that they are split into chunks
+/// depends on how the [Rope<M>] was built and edited.
+pub struct DebugChunks<'a, M, C = DefaultConfig>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    chunks: Chunks<'a, M, C>,
+}
+
+impl<M, C> Debug for DebugChunks<'_, M, C>
+where
+    M: Measurable + Debug,
+    C: RopeConfig,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.chunks.clone()).finish()
+    }
+}
+
+/// The elements of a [Rope<M>] or [RopeSlice<M>], printed by [Debug] as a map
+/// from the width at which each element starts to the element, returned by
+/// [debug_widths()][Rope::debug_widths].
+pub struct DebugWidths<'a, M, C = DefaultConfig>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    iter: Iter<'a, M, C>,
+}
+
+impl<M, C> Debug for DebugWidths<'_, M, C>
+where
+    M: Measurable + Debug,
+    C: RopeConfig,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter.clone()).finish()
+    }
+}
+
+/// An indented dump of the tree behind a [Rope<M>], returned by
+/// [debug_tree()][Rope::debug_tree].
+///
+/// Each line is a node, indented by its depth. Branches and leaves show their
+/// length, width and `zero_width_end` flag, and leaves also show their
+/// elements. Nodes that are shared with other [Rope<M>]s through their
+/// [Arc] are marked as `shared`, and nodes whose cached information in their
+/// parent doesn't match their contents are marked as `stale`, alongside that
+/// cached information.
+pub struct DebugTree<'a, M, C = DefaultConfig>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    rope: &'a Rope<M, C>,
+}
+
+impl<M, C> Debug for DebugTree<'_, M, C>
+where
+    M: Measurable + Debug,
+    C: RopeConfig,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_node(f, &self.rope.root, None, 0)
+    }
+}
+
+fn fmt_node<M, C>(
+    f: &mut fmt::Formatter,
+    node: &Arc<Node<M, C>>,
+    cached: Option<(SliceInfo, bool)>,
+    depth: usize,
+) -> fmt::Result
+where
+    M: Measurable + Debug,
+    C: RopeConfig,
+{
+    let info = node.slice_info();
+    let zero_width_end = node.zero_width_end();
+
+    let kind = if node.is_leaf() { "Leaf" } else { "Branch" };
+    write!(
+        f,
+        "{:indent$}{kind} {{ len: {}, width: {}, zero_width_end: {zero_width_end} }}",
+        "",
+        info.len,
+        info.width,
+        indent = depth * 4
+    )?;
+    if Arc::strong_count(node) > 1 {
+        f.write_str(" shared")?;
+    }
+    if let Some((cached_info, cached_zero_width_end)) = cached {
+        if cached_info != info || cached_zero_width_end != zero_width_end {
+            write!(
+                f,
+                " stale {{ len: {}, width: {}, zero_width_end: {cached_zero_width_end} }}",
+                cached_info.len, cached_info.width
+            )?;
+        }
+    }
+
+    match **node {
+        Node::Leaf(ref slice) => writeln!(f, " {:?}", &slice[..]),
+        Node::Branch(ref children) => {
+            writeln!(f)?;
+            for (info, child) in children.iter() {
+                fmt_node(f, child, Some(*info), depth + 1)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+impl<M, C> Rope<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    /// Returns the chunks of the [Rope<M>], to be printed with [Debug]. See
+    /// [DebugChunks<M>].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let rope = Rope::from_slice(&[Lorem, Ipsum, Sit]);
+    ///
+    /// assert_eq!(format!("{:?}", rope), "[Lorem, Ipsum, Sit]");
+    /// assert_eq!(format!("{:?}", rope.debug_chunks()), "[[Lorem, Ipsum, Sit]]");
+    /// ```
+    #[inline]
+    pub fn debug_chunks(&self) -> DebugChunks<'_, M, C> {
+        DebugChunks {
+            chunks: self.chunks(),
+        }
+    }
+
+    /// Returns the elements of the [Rope<M>] alongside their widths, to be
+    /// printed with [Debug]. See [DebugWidths<M>].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let rope = Rope::from_slice(&[Lorem, Ipsum, Sit, Dolor(3)]);
+    ///
+    /// assert_eq!(
+    ///     format!("{:?}", rope.debug_widths()),
+    ///     "{0: Lorem, 1: Ipsum, 3: Sit, 3: Dolor(3)}"
+    /// );
+    /// ```
+    #[inline]
+    pub fn debug_widths(&self) -> DebugWidths<'_, M, C> {
+        DebugWidths { iter: self.iter() }
+    }
+
+    /// Returns an indented dump of the tree behind the [Rope<M>], for
+    /// debugging. See [DebugTree<M>] for what it prints.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let rope = Rope::from_slice(&[Lorem, Ipsum, Sit]);
+    ///
+    /// assert_eq!(
+    ///     format!("{:?}", rope.debug_tree()),
+    ///     "Leaf { len: 3, width: 3, zero_width_end: true } [Lorem, Ipsum, Sit]\n"
+    /// );
+    /// ```
+    #[inline]
+    pub fn debug_tree(&self) -> DebugTree<'_, M, C> {
+        DebugTree { rope: self }
+    }
+
+    /// Renders the tree behind the [Rope<M>] in the Graphviz DOT language,
+    /// for debugging.
+    ///
+    /// Each node is labeled with its length, width and `zero_width_end` flag,
+    /// and each edge with the information about the child that its parent
+    /// has cached. Edges whose cached information doesn't match the child are
+    /// drawn in red, and nodes that are shared with other [Rope<M>]s through
+    /// their [Arc] are filled in. Nodes that show up more than once in the
+    /// tree are only drawn once, with an edge from every parent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let rope = Rope::from_slice(&[Lorem, Ipsum, Sit].repeat(100));
+    /// let dot = rope.to_dot();
+    ///
+    /// assert!(dot.starts_with("digraph rope {"));
+    /// assert!(dot.contains("->"));
+    /// ```
+    #[inline]
+    pub fn to_dot(&self) -> String {
+        self.to_dot_marking(|node| Arc::strong_count(node) > 1)
+    }
+
+    /// Renders the tree behind the [Rope<M>] in the Graphviz DOT language,
+    /// filling in the nodes that are also in the tree of `other`.
+    ///
+    /// Other than which nodes are filled in, this is the same as
+    /// [to_dot()][Rope::to_dot]. It shows what two versions of a [Rope<M>],
+    /// such as one and an edited clone of it, still share, no matter what
+    /// else holds on to their nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let rope = Rope::from_slice(&[Lorem, Ipsum, Sit].repeat(100));
+    /// let mut edited = rope.clone();
+    /// edited.insert(150, Dolor(3));
+    ///
+    /// assert!(edited.to_dot_with(&rope).contains("fillcolor"));
+    /// assert!(!edited.to_dot_with(&Rope::from_slice(&[Lorem])).contains("fillcolor"));
+    /// ```
+    pub fn to_dot_with(&self, other: &Self) -> String {
+        let mut other_nodes = HashSet::new();
+        let mut stack = vec![&other.root];
+        while let Some(node) = stack.pop() {
+            if !other_nodes.insert(Arc::as_ptr(node)) {
+                continue;
+            }
+            if let Node::Branch(ref children) = **node {
+                stack.extend(children.nodes());
+            }
+        }
+
+        self.to_dot_marking(|node| other_nodes.contains(&Arc::as_ptr(node)))
+    }
+
+    //-----------------------------------------------------------------------
+    // Internal utilities
+
+    /// Renders the tree in the DOT language, filling in the nodes for which
+    /// `is_shared` returns `true`.
+    fn to_dot_marking(&self, is_shared: impl Fn(&Arc<Node<M, C>>) -> bool) -> String {
+        let mut dot = String::from("digraph rope {\n    node [shape=record];\n");
+        let mut visited = HashSet::new();
+        let mut stack = vec![&self.root];
+
+        while let Some(node) = stack.pop() {
+            if !visited.insert(Arc::as_ptr(node)) {
+                continue;
+            }
+
+            let info = node.slice_info();
+            let kind = if node.is_leaf() { "leaf" } else { "branch" };
+            let fill = if is_shared(node) {
+                ", style=filled, fillcolor=lightblue"
+            } else {
+                ""
+            };
+            writeln!(
+                dot,
+                "    {} [label=\"{{{kind}|len: {}|width: {}|zero_width_end: {}}}\"{fill}];",
+                dot_id(node),
+                info.len,
+                info.width,
+                node.zero_width_end(),
+            )
+            .unwrap();
+
+            if let Node::Branch(ref children) = **node {
+                for ((info, zero_width_end), child) in children.iter() {
+                    let stale =
+                        *info != child.slice_info() || *zero_width_end != child.zero_width_end();
+                    writeln!(
+                        dot,
+                        "    {} -> {} [label=\"{}, {}{}\"{}];",
+                        dot_id(node),
+                        dot_id(child),
+                        info.len,
+                        info.width,
+                        if *zero_width_end { ", zwe" } else { "" },
+                        if stale { ", color=red" } else { "" },
+                    )
+                    .unwrap();
+                }
+                stack.extend(children.nodes().iter().rev());
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl<'a, M, C> RopeSlice<'a, M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    /// Returns the chunks of the [RopeSlice<M>], to be printed with [Debug].
+    /// See [DebugChunks<M>].
+    #[inline]
+    pub fn debug_chunks(&self) -> DebugChunks<'a, M, C> {
+        DebugChunks {
+            chunks: self.chunks(),
+        }
+    }
+
+    /// Returns the elements of the [RopeSlice<M>] alongside their widths, to
+    /// be printed with [Debug]. See [DebugWidths<M>].
+    #[inline]
+    pub fn debug_widths(&self) -> DebugWidths<'a, M, C> {
+        DebugWidths { iter: self.iter() }
+    }
+}
+
+/// The name of a node in the DOT output, unique to its allocation.
+fn dot_id<M, C>(node: &Arc<Node<M, C>>) -> String
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    format!("n{:p}", Arc::as_ptr(node))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Lipsum::{self, *};
+    use crate::{Measurable, Rope};
+
+    fn lorem_ipsum() -> Vec<Lipsum> {
+        [Lorem, Ipsum, Dolor(3), Sit, Consectur("amet")].repeat(20)
+    }
+
+    /// A word, measured by its length in bytes.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Word(&'static str);
+
+    impl Measurable for Word {
+        fn width(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    impl std::fmt::Display for Word {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    #[test]
+    fn debug_tree_01() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let dump = format!("{:?}", rope.debug_tree());
+        let lines: Vec<_> = dump.lines().collect();
+
+        assert!(lines[0].starts_with("Branch { len: 100, width: 200, zero_width_end: false }"));
+        let leaves: Vec<_> = lines.iter().filter(|line| line.contains("Leaf")).collect();
+        assert_eq!(leaves.len(), rope.chunks().count());
+        // All leaves are at the same depth.
+        let indent = rope.root.depth() * 4;
+        for leaf in leaves {
+            assert!(leaf[indent..].starts_with("Leaf"));
+        }
+        assert!(!dump.contains("shared"));
+        assert!(!dump.contains("stale"));
+
+        let clone = rope.clone();
+        let dump = format!("{:?}", clone.debug_tree());
+        assert!(lines[0].len() < dump.lines().next().unwrap().len());
+        assert!(dump.lines().next().unwrap().ends_with("shared"));
+    }
+
+    #[test]
+    fn debug_01() {
+        let vec = lorem_ipsum();
+        let rope = Rope::from_slice(vec.as_slice());
+        let mut edited = Rope::new();
+        for (i, measurable) in vec.iter().enumerate() {
+            edited.insert_slice(edited.width(), &[*measurable]);
+            if i % 7 == 0 {
+                edited.compact_range(..);
+            }
+        }
+
+        // Equal ropes print equally, no matter how they are chunked.
+        assert_eq!(rope, edited);
+        assert_eq!(format!("{:?}", rope), format!("{:?}", vec));
+        assert_eq!(format!("{:?}", edited), format!("{:?}", vec));
+        assert_eq!(format!("{:#?}", rope), format!("{:#?}", vec));
+
+        let chunks: Vec<_> = rope.chunks().collect();
+        assert_eq!(
+            format!("{:?}", rope.debug_chunks()),
+            format!("{:?}", chunks)
+        );
+
+        let slice = rope.width_slice(10..50);
+        let vec = Vec::from(slice);
+        assert_eq!(format!("{:?}", slice), format!("{:?}", vec));
+        let chunks: Vec<_> = slice.chunks().collect();
+        assert_eq!(
+            format!("{:?}", slice.debug_chunks()),
+            format!("{:?}", chunks)
+        );
+    }
+
+    #[test]
+    fn debug_02() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let printed = format!("{:?}", rope.debug_widths());
+        assert!(printed.starts_with(
+            "{0: Lorem, 1: Ipsum, 3: Dolor(3), 6: Sit, 6: Consectur(\"amet\"), 10: Lorem, "
+        ));
+        assert_eq!(printed.matches(": ").count(), rope.len());
+
+        let slice = rope.width_slice(..0);
+        assert_eq!(format!("{:?}", slice.debug_widths()), "{}");
+    }
+
+    #[test]
+    fn display_01() {
+        let words = ["lorem", " ", "ipsum", "", " dolor"];
+        let rope = Rope::from_slice(
+            &words
+                .repeat(10)
+                .iter()
+                .map(|word| Word(word))
+                .collect::<Vec<_>>(),
+        );
+
+        assert_eq!(rope.to_string(), words.concat().repeat(10));
+        assert_eq!(rope.width_slice(5..11).to_string(), " ipsum");
+    }
+
+    #[test]
+    fn to_dot_01() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let dot = rope.to_dot();
+
+        assert!(dot.starts_with("digraph rope {\n"));
+        assert!(dot.ends_with("}\n"));
+        let nodes = dot
+            .lines()
+            .filter(|line| line.contains("[label=\"{"))
+            .count();
+        let edges = dot.lines().filter(|line| line.contains("->")).count();
+        assert_eq!(nodes, edges + 1);
+        assert!(!dot.contains("fillcolor"));
+        assert!(!dot.contains("color=red"));
+
+        let mut edited = rope.clone();
+        edited.insert(50, Amet);
+        let dot = edited.to_dot();
+        assert!(dot.contains("fillcolor"));
+        assert!(dot.contains("{leaf|len: "));
+    }
+
+    #[test]
+    fn to_dot_02() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let repeated = rope.repeat(4);
+        let dot = repeated.to_dot();
+
+        // Shared nodes are only drawn once.
+        let nodes = dot
+            .lines()
+            .filter(|line| line.contains("[label=\"{"))
+            .count();
+        let edges = dot.lines().filter(|line| line.contains("->")).count();
+        assert!(nodes < edges + 1);
+    }
+
+    #[test]
+    fn to_dot_03() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let filled = |dot: &str| {
+            dot.lines()
+                .filter(|line| line.contains("fillcolor"))
+                .count()
+        };
+        let unfilled = |dot: &str| dot.replace(", style=filled, fillcolor=lightblue", "");
+
+        // Every node is in its own tree, and none are in an unrelated one,
+        // even while shared with a clone.
+        let stats = rope.stats();
+        assert_eq!(
+            filled(&rope.to_dot_with(&rope)),
+            stats.branches + stats.leaves
+        );
+        let _clone = rope.clone();
+        let unrelated = Rope::from_slice(lorem_ipsum().as_slice());
+        assert_eq!(filled(&rope.to_dot_with(&unrelated)), 0);
+
+        // After an edit, only the nodes on the path to it are new.
+        let mut edited = rope.clone();
+        edited.insert(50, Amet);
+        let dot = edited.to_dot_with(&rope);
+        let stats = edited.stats();
+        let nodes = stats.branches + stats.leaves;
+        assert!(filled(&dot) > 0);
+        assert!(nodes - filled(&dot) <= stats.depth + 2);
+        assert_eq!(unfilled(&dot), unfilled(&edited.to_dot()));
+    }
+}
@@ -0,0 +1,146 @@
+use std::ops::Range;
+
+use crate::rope::Measurable;
+
+/// An edit to a [Rope<M>][crate::Rope], to be applied alongside others by
+/// [apply_edits()][crate::Rope::apply_edits].
+///
+/// All widths are relative to the [Rope<M>][crate::Rope] before any of the
+/// edits are applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Edit<M>
+where
+    M: Measurable,
+{
+    /// Inserts the elements at a width, the same way that
+    /// [insert_slice()][crate::Rope::insert_slice] would.
+    Insert(usize, Vec<M>),
+
+    /// Removes the elements in a width range, the same way that
+    /// [remove()][crate::Rope::remove] would.
+    Remove(Range<usize>),
+
+    /// Removes the elements in a width range, the same way that
+    /// [remove()][crate::Rope::remove] would, and puts the new elements in
+    /// their place.
+    Replace(Range<usize>, Vec<M>),
+}
+
+/// A mapping from positions before a set of [Edit<M>]s to positions after
+/// them, returned by [apply_edits()][crate::Rope::apply_edits].
+///
+/// Positions before an edit are left as is, and positions after it are
+/// shifted by the change in length. Positions inside of a removed range are
+/// moved to the end of what was put in its place. Positions at an insertion
+/// point are moved to the end of the inserted elements, just like the
+/// elements that were there.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct EditMap {
+    indices: Vec<MappedRange>,
+    widths: Vec<MappedRange>,
+}
+
+/// The bounds of an edited range before the edits, and its end after them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct MappedRange {
+    start: usize,
+    end: usize,
+    new_end: usize,
+}
+
+impl EditMap {
+    /// Creates a new, empty [EditMap].
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an edited range, which must come after all previously added ones.
+    ///
+    /// Takes the `(index, width)` bounds of the removed range, and the
+    /// `(len, width)` of the elements that were put in its place.
+    pub(crate) fn push(&mut self, start: (usize, usize), end: (usize, usize), new: (usize, usize)) {
+        let (index_delta, width_delta) = self.deltas();
+        self.indices.push(MappedRange {
+            start: start.0,
+            end: end.0,
+            new_end: (start.0 as isize + index_delta) as usize + new.0,
+        });
+        self.widths.push(MappedRange {
+            start: start.1,
+            end: end.1,
+            new_end: (start.1 as isize + width_delta) as usize + new.1,
+        });
+    }
+
+    /// Maps an index from before the edits to an index after them.
+    #[inline]
+    pub fn map_index(&self, index: usize) -> usize {
+        map_position(&self.indices, index)
+    }
+
+    /// Maps a width from before the edits to a width after them.
+    #[inline]
+    pub fn map_width(&self, width: usize) -> usize {
+        map_position(&self.widths, width)
+    }
+
+    /// How much the lengths and widths after the last edit have shifted.
+    #[inline]
+    fn deltas(&self) -> (isize, isize) {
+        let delta = |ranges: &[MappedRange]| {
+            ranges
+                .last()
+                .map_or(0, |range| range.new_end as isize - range.end as isize)
+        };
+        (delta(&self.indices), delta(&self.widths))
+    }
+}
+
+fn map_position(ranges: &[MappedRange], pos: usize) -> usize {
+    let passed = ranges.partition_point(|range| range.end <= pos);
+
+    match ranges.get(passed) {
+        Some(range) if range.start <= pos => range.new_end,
+        _ => match passed.checked_sub(1).map(|i| ranges[i]) {
+            Some(range) => pos - range.end + range.new_end,
+            None => pos,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_01() {
+        let mut map = EditMap::new();
+        // Replaces [2, 5) with 1 element, of width 4.
+        map.push((2, 3), (5, 9), (1, 4));
+        // Inserts 3 elements, of width 3.
+        map.push((7, 12), (7, 12), (3, 3));
+
+        assert_eq!(map.map_index(0), 0);
+        assert_eq!(map.map_index(2), 3);
+        assert_eq!(map.map_index(4), 3);
+        assert_eq!(map.map_index(5), 3);
+        assert_eq!(map.map_index(6), 4);
+        assert_eq!(map.map_index(7), 8);
+        assert_eq!(map.map_index(10), 11);
+
+        assert_eq!(map.map_width(2), 2);
+        assert_eq!(map.map_width(3), 7);
+        assert_eq!(map.map_width(9), 7);
+        assert_eq!(map.map_width(11), 9);
+        assert_eq!(map.map_width(12), 13);
+        assert_eq!(map.map_width(20), 21);
+    }
+
+    #[test]
+    fn map_02() {
+        let map = EditMap::new();
+        assert_eq!(map.map_index(10), 10);
+        assert_eq!(map.map_width(10), 10);
+    }
+}
@@ -0,0 +1,401 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+use std::sync::{Arc, Weak};
+
+use crate::config::{DefaultConfig, RopeConfig};
+use crate::rope::{Measurable, Rope};
+use crate::slice::{RSEnum, RopeSlice};
+use crate::tree::Node;
+
+/// The modulus of the polynomial hashes, the Mersenne prime 2^61 - 1.
+const MODULUS: u64 = (1 << 61) - 1;
+
+/// The base of the polynomial hashes.
+const BASE: u64 = 0x0123_4567_89ab_cdef % MODULUS;
+
+/// Below this many entries, the [HashCache] is never pruned.
+const MIN_PRUNE_LEN: usize = 64;
+
+/// A cache of the hashes of the nodes of [Rope<M>]s, for hashing the same
+/// [Rope<M>] many times between small edits.
+///
+/// The [Hash] implementation of [Rope<M>] goes through every element, every
+/// time. A [HashCache<M>] instead hashes every node of the tree once, and
+/// remembers its hash for as long as the node is alive. Since edits only
+/// replace the nodes on the path from the root to the edited leaves, hashing
+/// a [Rope<M>] after a small edit only takes O(log N) time.
+///
+/// The hashes are computed such that equal [Rope<M>]s and [RopeSlice<M>]s
+/// always hash the same, no matter how they are split into chunks. They don't
+/// match the ones from the [Hash] implementation, and they are only
+/// comparable between hashes from the same [HashCache<M>], or from
+/// [HashCache<M>]s with equal [BuildHasher]s.
+///
+/// The cache holds [Weak] references to the nodes it has seen, so their
+/// memory is only freed once they are pruned from the cache. While a node is
+/// in the cache, editing it makes a copy of it, the same as if it were
+/// shared with a clone of the [Rope<M>].
+///
+/// # Examples
+///
+/// ```rust
+/// # use any_rope::{HashCache, Rope};
+/// # use any_rope::Lipsum::*;
+/// let mut rope = Rope::from_slice(&[Lorem, Ipsum, Dolor(3), Sit].repeat(1000));
+/// let mut cache = HashCache::new();
+///
+/// let before = cache.hash_rope(&rope);
+/// rope.insert(100, Amet);
+/// // Only the nodes that changed are hashed again.
+/// let after = cache.hash_rope(&rope);
+/// assert_ne!(before, after);
+///
+/// // Equal ropes hash the same, no matter how they were built.
+/// let other = Rope::from_slice(&Vec::from(&rope));
+/// assert_eq!(after, cache.hash_rope(&other));
+/// ```
+pub struct HashCache<M, C = DefaultConfig, S = RandomState>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    build_hasher: S,
+    /// The hashes of nodes, keyed by their address, which can't be reused by
+    /// another node while the [Weak] is alive.
+    nodes: HashMap<usize, (Weak<Node<M, C>>, PolyHash)>,
+    /// The amount of entries right after the last pruning.
+    pruned_len: usize,
+}
+
+/// A polynomial hash of a list of elements, which can be combined with the
+/// hash of another list to get the hash of both of them, one after the other.
+#[derive(Debug, Clone, Copy)]
+struct PolyHash {
+    hash: u64,
+    /// `BASE` to the power of the length of the list.
+    pow: u64,
+}
+
+impl PolyHash {
+    const EMPTY: Self = Self { hash: 0, pow: 1 };
+
+    /// The hash of this list, followed by `other`.
+    #[inline]
+    fn then(self, other: Self) -> Self {
+        Self {
+            hash: add_mod(mul_mod(self.hash, other.pow), other.hash),
+            pow: mul_mod(self.pow, other.pow),
+        }
+    }
+
+    /// The hash of this list, followed by an element with the hash `hash`.
+    #[inline]
+    fn push(self, hash: u64) -> Self {
+        // Adding 1 makes sure that no element leaves the hash unchanged.
+        Self {
+            hash: add_mod(mul_mod(self.hash, BASE), hash % MODULUS + 1),
+            pow: mul_mod(self.pow, BASE),
+        }
+    }
+}
+
+#[inline]
+fn mul_mod(a: u64, b: u64) -> u64 {
+    ((a as u128 * b as u128) % MODULUS as u128) as u64
+}
+
+#[inline]
+fn add_mod(a: u64, b: u64) -> u64 {
+    (a + b) % MODULUS
+}
+
+impl<M, C> HashCache<M, C>
+where
+    M: Measurable + Hash,
+    C: RopeConfig,
+{
+    /// Creates a new, empty [HashCache<M>].
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<M, C> Default for HashCache<M, C>
+where
+    M: Measurable + Hash,
+    C: RopeConfig,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M, C, S> HashCache<M, C, S>
+where
+    M: Measurable + Hash,
+    C: RopeConfig,
+    S: BuildHasher,
+{
+    /// Creates a new, empty [HashCache<M>], which hashes elements with
+    /// hashers built by `build_hasher`.
+    #[inline]
+    pub fn with_hasher(build_hasher: S) -> Self {
+        Self {
+            build_hasher,
+            nodes: HashMap::new(),
+            pruned_len: 0,
+        }
+    }
+
+    /// Hashes a [Rope<M>], reusing the hashes of the nodes that were hashed
+    /// before.
+    ///
+    /// Runs in O(K log N) time, where K is the amount of leaves that changed
+    /// since the last time that the [Rope<M>] was hashed.
+    #[inline]
+    pub fn hash_rope(&mut self, rope: &Rope<M, C>) -> u64 {
+        self.hash_slice(rope.width_slice(..))
+    }
+
+    /// Hashes a [RopeSlice<M>], reusing the hashes of the nodes that were
+    /// hashed before.
+    ///
+    /// Only the nodes that are fully inside of the [RopeSlice<M>] are
+    /// cached, so the leaves at its edges are always hashed again.
+    pub fn hash_slice(&mut self, slice: RopeSlice<M, C>) -> u64 {
+        let poly = match slice.0 {
+            RSEnum::Full {
+                node,
+                start_info,
+                end_info,
+            } => {
+                let range = (start_info.len as usize, end_info.len as usize);
+                let poly = self.hash_node_range(node, range, node.len());
+                self.prune();
+                poly
+            }
+            RSEnum::Light { slice } => self.hash_elements(slice),
+        };
+
+        self.build_hasher.hash_one((poly.hash, slice.len()))
+    }
+
+    /// The amount of nodes whose hashes are cached.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if no hashes are cached.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Throws away all of the cached hashes, freeing the nodes that only the
+    /// cache was holding on to.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.pruned_len = 0;
+    }
+
+    //-----------------------------------------------------------------------
+    // Internal utilities
+
+    /// Hashes the elements in the `start..end` index range of a node with
+    /// `len` elements.
+    fn hash_node_range(
+        &mut self,
+        node: &Arc<Node<M, C>>,
+        (start, end): (usize, usize),
+        len: usize,
+    ) -> PolyHash {
+        if start == 0 && end == len {
+            return self.hash_node(node);
+        }
+
+        match **node {
+            Node::Leaf(ref slice) => self.hash_elements(&slice[start..end]),
+            Node::Branch(ref children) => {
+                let mut poly = PolyHash::EMPTY;
+                let mut child_start = 0;
+                for ((info, _), child) in children.iter() {
+                    let child_len = info.len as usize;
+                    let child_end = child_start + child_len;
+                    if child_start >= end {
+                        break;
+                    }
+                    if child_end > start {
+                        let range = (
+                            start.max(child_start) - child_start,
+                            end.min(child_end) - child_start,
+                        );
+                        poly = poly.then(self.hash_node_range(child, range, child_len));
+                    }
+                    child_start = child_end;
+                }
+                poly
+            }
+        }
+    }
+
+    /// Hashes a whole node, or fetches its hash from the cache.
+    fn hash_node(&mut self, node: &Arc<Node<M, C>>) -> PolyHash {
+        let key = Arc::as_ptr(node) as usize;
+        if let Some((_, poly)) = self.nodes.get(&key) {
+            return *poly;
+        }
+
+        let poly = match **node {
+            Node::Leaf(ref slice) => self.hash_elements(slice),
+            Node::Branch(ref children) => children
+                .nodes()
+                .iter()
+                .fold(PolyHash::EMPTY, |poly, child| {
+                    poly.then(self.hash_node(child))
+                }),
+        };
+        self.nodes.insert(key, (Arc::downgrade(node), poly));
+        poly
+    }
+
+    fn hash_elements(&self, slice: &[M]) -> PolyHash {
+        slice.iter().fold(PolyHash::EMPTY, |poly, measurable| {
+            poly.push(self.build_hasher.hash_one(measurable))
+        })
+    }
+
+    /// Removes the entries of nodes that were dropped everywhere else, once
+    /// the cache has doubled in size since the last time that it was pruned.
+    fn prune(&mut self) {
+        if self.nodes.len() < (self.pruned_len * 2).max(MIN_PRUNE_LEN) {
+            return;
+        }
+
+        self.nodes.retain(|_, (node, _)| node.strong_count() > 0);
+        self.pruned_len = self.nodes.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::Hasher;
+
+    use super::*;
+    use crate::Lipsum::{self, *};
+
+    fn lorem_ipsum() -> Vec<Lipsum> {
+        (0..300)
+            .map(|i| match i % 6 {
+                0 => Lorem,
+                1 => Ipsum,
+                2 => Dolor(i % 5),
+                3 => Sit,
+                4 => Amet,
+                _ => Consectur("adipiscing"),
+            })
+            .collect()
+    }
+
+    fn std_hash<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// The same elements as [lorem_ipsum()], but chunked differently.
+    fn rechunked(vec: &[Lipsum]) -> Rope<Lipsum> {
+        Rope::concat(vec.chunks(7).map(Rope::from_slice))
+    }
+
+    #[test]
+    fn hash_01() {
+        let vec = lorem_ipsum();
+        let rope = Rope::from_slice(vec.as_slice());
+        let other = rechunked(&vec);
+        assert_eq!(rope, other);
+        assert!(rope.chunks().ne(other.chunks()));
+
+        assert_eq!(std_hash(&rope), std_hash(&other));
+        assert_eq!(std_hash(&rope), std_hash(&rope.width_slice(..)));
+        assert_eq!(
+            std_hash(&rope),
+            std_hash(&RopeSlice::<_>::from(vec.as_slice()))
+        );
+
+        let slice = rope.width_slice(20..100);
+        let other_slice = other.width_slice(20..100);
+        assert_eq!(std_hash(&slice), std_hash(&other_slice));
+        assert_eq!(std_hash(&slice), std_hash(&Rope::from(slice)));
+        assert_eq!(std_hash(&slice), std_hash(&slice.to_owned()));
+        assert_ne!(std_hash(&slice), std_hash(&rope));
+    }
+
+    #[test]
+    fn hash_cache_01() {
+        let vec = lorem_ipsum();
+        let rope = Rope::from_slice(vec.as_slice());
+        let other = rechunked(&vec);
+        let mut cache = HashCache::new();
+
+        let hash = cache.hash_rope(&rope);
+        assert_eq!(hash, cache.hash_rope(&other));
+        assert_eq!(hash, cache.hash_slice(RopeSlice::from(vec.as_slice())));
+
+        let slice = rope.width_slice(20..100);
+        let vec_slice = Vec::from(slice);
+        let hash = cache.hash_slice(slice);
+        assert_eq!(hash, cache.hash_slice(other.width_slice(20..100)));
+        assert_eq!(
+            hash,
+            cache.hash_slice(RopeSlice::from(vec_slice.as_slice()))
+        );
+        assert_ne!(hash, cache.hash_rope(&rope));
+
+        let empty: Rope<Lipsum> = Rope::new();
+        assert_eq!(
+            cache.hash_rope(&empty),
+            cache.hash_slice(rope.width_slice(0..0))
+        );
+    }
+
+    #[test]
+    fn hash_cache_02() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let mut cache = HashCache::new();
+
+        cache.hash_rope(&rope);
+        let len = cache.len();
+        assert_eq!(len, rope.stats().branches + rope.stats().leaves);
+
+        // Only the path to the edited leaf is hashed again.
+        rope.insert(100, Amet);
+        let hash = cache.hash_rope(&rope);
+        assert!(cache.len() - len <= rope.root.depth() + 2);
+        assert_eq!(hash, cache.hash_rope(&Rope::from(Vec::from(&rope))));
+    }
+
+    #[test]
+    fn hash_cache_03() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let mut cache = HashCache::new();
+
+        for i in 0..200 {
+            rope.insert(i, Lorem);
+            cache.hash_rope(&rope);
+        }
+
+        // Dropped nodes are eventually pruned.
+        let live = rope.stats().branches + rope.stats().leaves;
+        assert!(cache.len() < 2 * live.max(MIN_PRUNE_LEN) + rope.root.depth() + 2);
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(rope.unique_bytes(), rope.stats().heap_bytes);
+    }
+}
@@ -0,0 +1,623 @@
+use std::collections::{BTreeMap, HashSet};
+use std::ops::Range;
+
+use crate::config::{DefaultConfig, RopeConfig};
+use crate::rope::{Measurable, Rope};
+
+/// The width ranges that changed between two versions of a [Rope<M>], as
+/// `(before, after)` pairs, in order.
+pub type Changes = Vec<(Range<usize>, Range<usize>)>;
+
+/// A [Rope<M>] with an undo tree.
+///
+/// The [Rope<M>] is edited through [rope_mut()][History::rope_mut], and
+/// edits are grouped into revisions by [commit()][History::commit]. Since
+/// cloning a [Rope<M>] is O(1), and clones share all unedited parts of their
+/// trees, every revision keeps a full snapshot, for little more than the
+/// memory taken by the edited parts.
+///
+/// Undoing and then committing new edits starts a new branch in the tree,
+/// without losing the old one, which can still be reached with
+/// [goto()][History::goto].
+///
+/// # Examples
+///
+/// ```rust
+/// # use any_rope::{History, Rope};
+/// # use any_rope::Lipsum::*;
+/// let mut history = History::new(Rope::from_slice(&[Lorem, Ipsum, Dolor(3)]));
+///
+/// history.rope_mut().insert(3, Sit);
+/// history.rope_mut().insert(3, Dolor(4));
+/// history.commit();
+/// history.rope_mut().remove(0..1);
+/// history.commit();
+///
+/// // Undoing puts back the `Lorem`, which spans the width range 0..1.
+/// assert_eq!(history.undo(), Some(vec![(0..0, 0..1)]));
+/// assert_eq!(history.undo(), Some(vec![(3..7, 3..3)]));
+/// assert_eq!(history.rope(), &[Lorem, Ipsum, Dolor(3)].as_slice());
+/// assert_eq!(history.undo(), None);
+///
+/// assert_eq!(history.redo(), Some(vec![(3..3, 3..7)]));
+/// assert_eq!(history.rope(), &[Lorem, Ipsum, Sit, Dolor(4), Dolor(3)].as_slice());
+/// ```
+#[derive(Clone)]
+pub struct History<M, C = DefaultConfig>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    rope: Rope<M, C>,
+    revisions: BTreeMap<usize, Revision<M, C>>,
+    current: usize,
+    next_id: usize,
+    max_revisions: Option<usize>,
+    max_bytes: Option<usize>,
+}
+
+/// A revision in a [History<M>].
+///
+/// Revisions are identified by ids, which are never reused, and which are
+/// larger for newer revisions.
+#[derive(Clone)]
+pub struct Revision<M, C = DefaultConfig>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    rope: Rope<M, C>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    last_child: Option<usize>,
+    changes: Changes,
+}
+
+impl<M, C> Revision<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    /// The [Rope<M>] as of this revision.
+    #[inline]
+    pub fn rope(&self) -> &Rope<M, C> {
+        &self.rope
+    }
+
+    /// The id of the revision that this one was made from, or [None] for
+    /// the oldest one.
+    #[inline]
+    pub fn parent(&self) -> Option<usize> {
+        self.parent
+    }
+
+    /// The ids of the revisions made from this one, from oldest to newest.
+    #[inline]
+    pub fn children(&self) -> &[usize] {
+        &self.children
+    }
+
+    /// The width ranges changed by this revision, from its parent to itself.
+    #[inline]
+    pub fn changes(&self) -> &[(Range<usize>, Range<usize>)] {
+        &self.changes
+    }
+}
+
+impl<M, C> History<M, C>
+where
+    M: Measurable + PartialEq,
+    C: RopeConfig,
+{
+    /// Creates a [History<M>] with a single revision, holding `rope`.
+    pub fn new(rope: Rope<M, C>) -> Self {
+        let revision = Revision {
+            rope: rope.clone(),
+            parent: None,
+            children: Vec::new(),
+            last_child: None,
+            changes: Vec::new(),
+        };
+
+        Self {
+            rope,
+            revisions: BTreeMap::from([(0, revision)]),
+            current: 0,
+            next_id: 1,
+            max_revisions: None,
+            max_bytes: None,
+        }
+    }
+
+    /// The [Rope<M>] being edited, including uncommitted edits.
+    #[inline]
+    pub fn rope(&self) -> &Rope<M, C> {
+        &self.rope
+    }
+
+    /// Gives mutable access to the [Rope<M>], for editing.
+    ///
+    /// Edits are not recorded until [commit()][History::commit] is called.
+    #[inline]
+    pub fn rope_mut(&mut self) -> &mut Rope<M, C> {
+        &mut self.rope
+    }
+
+    /// Returns `true` if the [Rope<M>] was edited since the last commit.
+    ///
+    /// Edits that leave the [Rope<M>] as it was may still count.
+    ///
+    /// Runs in O(1) time.
+    #[inline]
+    pub fn has_uncommitted(&self) -> bool {
+        !self.rope.is_instance(&self.revisions[&self.current].rope)
+    }
+
+    /// Records the edits made since the last commit as a new revision, a
+    /// child of the current one, and returns its id.
+    ///
+    /// Returns [None] if nothing changed.
+    ///
+    /// Runs in O(K log N) time, where K is the amount of edited leaves.
+    pub fn commit(&mut self) -> Option<usize> {
+        if !self.has_uncommitted() {
+            return None;
+        }
+
+        let parent = self.revisions.get_mut(&self.current).unwrap();
+        let changes = self.rope.changed_ranges(&parent.rope);
+        if changes.is_empty() {
+            self.rope = parent.rope.clone();
+            return None;
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        parent.children.push(id);
+        parent.last_child = Some(id);
+
+        let revision = Revision {
+            rope: self.rope.clone(),
+            parent: Some(self.current),
+            children: Vec::new(),
+            last_child: None,
+            changes,
+        };
+        self.revisions.insert(id, revision);
+        self.current = id;
+
+        self.prune();
+        Some(id)
+    }
+
+    /// Goes back to the parent of the current revision, returning the
+    /// changed width ranges, as `(before, after)` pairs.
+    ///
+    /// Uncommitted edits are committed first. Returns [None] if there is
+    /// nothing to undo.
+    pub fn undo(&mut self) -> Option<Changes> {
+        self.commit();
+
+        let current = &self.revisions[&self.current];
+        let parent_id = current.parent?;
+        let changes = current
+            .changes
+            .iter()
+            .map(|(before, after)| (after.clone(), before.clone()))
+            .collect();
+
+        let parent = self.revisions.get_mut(&parent_id).unwrap();
+        parent.last_child = Some(self.current);
+        self.rope = parent.rope.clone();
+        self.current = parent_id;
+
+        Some(changes)
+    }
+
+    /// Goes forward to the child of the current revision that was last
+    /// undone or committed, returning the changed width ranges, as
+    /// `(before, after)` pairs.
+    ///
+    /// Uncommitted edits are committed first, which leaves nothing to redo.
+    /// Returns [None] if there is nothing to redo.
+    pub fn redo(&mut self) -> Option<Changes> {
+        self.commit();
+
+        let child_id = self.revisions[&self.current].last_child?;
+        let child = &self.revisions[&child_id];
+        self.rope = child.rope.clone();
+        self.current = child_id;
+
+        Some(child.changes.clone())
+    }
+
+    /// Goes to any revision in the tree, returning the changed width
+    /// ranges, as `(before, after)` pairs.
+    ///
+    /// Uncommitted edits are committed first. Returns [None] if there is no
+    /// revision with the id `id`.
+    ///
+    /// Runs in O(K log N) time, where K is the amount of leaves that differ
+    /// between the revisions.
+    pub fn goto(&mut self, id: usize) -> Option<Changes> {
+        self.commit();
+
+        let target = self.revisions.get(&id)?;
+        let changes = target.rope.changed_ranges(&self.rope);
+        self.rope = target.rope.clone();
+        self.current = id;
+
+        Some(changes)
+    }
+
+    /// The id of the current revision, which uncommitted edits are based on.
+    #[inline]
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// Returns the revision with the id `id`, if it hasn't been pruned.
+    #[inline]
+    pub fn revision(&self, id: usize) -> Option<&Revision<M, C>> {
+        self.revisions.get(&id)
+    }
+
+    /// Iterates over the ids and revisions in the tree, from oldest to
+    /// newest.
+    #[inline]
+    pub fn revisions(&self) -> impl Iterator<Item = (usize, &Revision<M, C>)> {
+        self.revisions.iter().map(|(id, revision)| (*id, revision))
+    }
+
+    /// The amount of revisions in the tree.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.revisions.len()
+    }
+
+    /// Always returns `false`, since there is always a current revision.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// The maximum amount of revisions kept, or [None] if there is no limit.
+    #[inline]
+    pub fn max_revisions(&self) -> Option<usize> {
+        self.max_revisions
+    }
+
+    /// Sets the maximum amount of revisions kept, pruning revisions right
+    /// away if there are too many.
+    ///
+    /// The current revision is never pruned, so at least one is always kept.
+    /// Revisions that aren't needed to undo back from the current one go
+    /// first, starting with the oldest branches that the current revision
+    /// isn't on, then the revisions that could be redone. After that, the
+    /// oldest revisions are pruned. The remaining revisions keep their
+    /// parents, and still hold the same [Rope<M>]s.
+    pub fn set_max_revisions(&mut self, max_revisions: Option<usize>) {
+        self.max_revisions = max_revisions;
+        self.prune();
+    }
+
+    /// The maximum amount of bytes taken up by the revisions, or [None] if
+    /// there is no limit.
+    #[inline]
+    pub fn max_bytes(&self) -> Option<usize> {
+        self.max_bytes
+    }
+
+    /// Sets the maximum amount of bytes taken up by the revisions, as
+    /// counted by [heap_bytes()][History::heap_bytes], pruning revisions
+    /// right away if they take up more.
+    ///
+    /// Revisions are pruned in the same order as with
+    /// [set_max_revisions()][History::set_max_revisions], and the current
+    /// one is always kept, even if it alone takes up more.
+    ///
+    /// Pruning a revision is counted as freeing the nodes that only its
+    /// [Rope<M>] holds, so while clones of the [Rope<M>]s are held outside of
+    /// the [History<M>], more revisions may be pruned than needed.
+    pub fn set_max_bytes(&mut self, max_bytes: Option<usize>) {
+        self.max_bytes = max_bytes;
+        self.prune();
+    }
+
+    /// The amount of bytes allocated for the [Rope<M>]s of all revisions,
+    /// including uncommitted edits.
+    ///
+    /// Nodes shared between revisions are only counted once, so this is
+    /// usually not much more than the size of a single [Rope<M>].
+    ///
+    /// Runs in O(K) time, where K is the amount of distinct nodes.
+    pub fn heap_bytes(&self) -> usize {
+        let mut visited = HashSet::new();
+        let revisions = self.revisions.values().map(|revision| &revision.rope);
+        std::iter::once(&self.rope)
+            .chain(revisions)
+            .map(|rope| rope.heap_bytes_unvisited(&mut visited))
+            .sum()
+    }
+
+    /// Removes revisions, other than the current one, until there are no
+    /// more than [max_revisions()][History::max_revisions], and they take up
+    /// no more than [max_bytes()][History::max_bytes].
+    fn prune(&mut self) {
+        let max = self.max_revisions.unwrap_or(usize::MAX).max(1);
+        let max_bytes = self.max_bytes.unwrap_or(usize::MAX);
+        let mut bytes = match self.max_bytes {
+            Some(_) => self.heap_bytes(),
+            None => 0,
+        };
+
+        while self.revisions.len() > max || bytes > max_bytes {
+            let Some(id) = self.prune_candidate() else {
+                break;
+            };
+            // The nodes only held by the pruned revision are freed with it.
+            bytes = bytes.saturating_sub(self.revisions[&id].rope.unique_bytes());
+            self.remove_revision(id);
+        }
+    }
+
+    /// The revision to prune next, which is either a leaf of the tree, or
+    /// the root when the tree is a single branch leading up to the current
+    /// revision.
+    ///
+    /// Leaves on branches that the current revision isn't on go first,
+    /// oldest first, then leaves that come after the current revision.
+    fn prune_candidate(&self) -> Option<usize> {
+        let leaves = self
+            .revisions
+            .iter()
+            .filter(|(id, revision)| revision.children.is_empty() && **id != self.current)
+            .map(|(id, _)| *id);
+
+        let mut first_leaf = None;
+        for id in leaves {
+            if !self.is_descendant_of_current(id) {
+                return Some(id);
+            }
+            first_leaf = first_leaf.or(Some(id));
+        }
+        if first_leaf.is_some() {
+            return first_leaf;
+        }
+
+        let mut root = self.current;
+        while let Some(parent) = self.revisions[&root].parent {
+            root = parent;
+        }
+        (root != self.current).then_some(root)
+    }
+
+    /// Returns `true` if the current revision is among the ancestors of
+    /// `id`.
+    fn is_descendant_of_current(&self, mut id: usize) -> bool {
+        while let Some(parent) = self.revisions[&id].parent {
+            if parent == self.current {
+                return true;
+            }
+            id = parent;
+        }
+        false
+    }
+
+    /// Removes a revision other than the current one, which must be either
+    /// a leaf, or a root with a single child, which then becomes the root.
+    fn remove_revision(&mut self, id: usize) {
+        let removed = self.revisions.remove(&id).unwrap();
+
+        match removed.parent {
+            Some(parent_id) => {
+                debug_assert!(removed.children.is_empty());
+                let parent = self.revisions.get_mut(&parent_id).unwrap();
+                parent.children.retain(|child| *child != id);
+                if parent.last_child == Some(id) {
+                    parent.last_child = parent.children.last().copied();
+                }
+            }
+            None => {
+                debug_assert!(removed.children.len() <= 1);
+                if let Some(&root_id) = removed.children.first() {
+                    let root = self.revisions.get_mut(&root_id).unwrap();
+                    root.parent = None;
+                    root.changes.clear();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Lipsum::{self, *};
+
+    fn lorem_ipsum() -> Vec<Lipsum> {
+        [Lorem, Ipsum, Dolor(3), Sit, Consectur("amet")].repeat(20)
+    }
+
+    #[test]
+    fn undo_redo_01() {
+        let mut history = History::new(Rope::from_slice(lorem_ipsum().as_slice()));
+        assert_eq!(history.undo(), None);
+        assert_eq!(history.redo(), None);
+
+        let mut versions = vec![history.rope().clone()];
+        for i in 0..10 {
+            history.rope_mut().insert(i * 10, Dolor(i));
+            history.rope_mut().remove(i * 5..i * 5 + 2);
+            assert!(history.has_uncommitted());
+            history.commit().unwrap();
+            versions.push(history.rope().clone());
+        }
+        assert_eq!(history.len(), 11);
+
+        for version in versions.iter().rev().skip(1) {
+            let before = history.rope().clone();
+            let changes = history.undo().unwrap();
+            assert_eq!(history.rope(), version);
+            assert_eq!(changes, version.changed_ranges(&before));
+        }
+        assert_eq!(history.undo(), None);
+
+        for version in versions.iter().skip(1) {
+            history.redo().unwrap();
+            assert_eq!(history.rope(), version);
+        }
+        assert_eq!(history.redo(), None);
+    }
+
+    #[test]
+    fn commit_01() {
+        let mut history = History::new(Rope::from_slice(lorem_ipsum().as_slice()));
+        assert_eq!(history.commit(), None);
+
+        // Edits that change nothing don't make a revision.
+        history.rope_mut().insert(0, Lorem);
+        history.rope_mut().remove(0..1);
+        assert_eq!(history.commit(), None);
+        assert!(!history.has_uncommitted());
+        assert_eq!(history.len(), 1);
+
+        // Uncommitted edits are committed before undoing.
+        history.rope_mut().insert(0, Dolor(2));
+        assert_eq!(history.undo(), Some(vec![(0..2, 0..0)]));
+        assert_eq!(history.redo(), Some(vec![(0..0, 0..2)]));
+        assert_eq!(history.len(), 2);
+    }
+
+    #[test]
+    fn branches_01() {
+        let mut history = History::new(Rope::from_slice(lorem_ipsum().as_slice()));
+        history.rope_mut().insert(0, Dolor(1));
+        let first = history.commit().unwrap();
+        let first_rope = history.rope().clone();
+
+        history.undo();
+        history.rope_mut().insert(0, Dolor(2));
+        let second = history.commit().unwrap();
+        assert_eq!(history.revision(0).unwrap().children(), &[first, second]);
+
+        // Redo follows the branch that was last undone.
+        history.undo();
+        assert_eq!(history.redo(), Some(vec![(0..0, 0..2)]));
+        assert_eq!(history.current(), second);
+
+        assert_eq!(history.goto(first), Some(vec![(0..2, 0..1)]));
+        assert_eq!(history.rope(), &first_rope);
+        history.undo();
+        history.redo();
+        assert_eq!(history.current(), first);
+        assert_eq!(history.goto(100), None);
+    }
+
+    /// Checks that the revisions still form a tree, with the correct
+    /// changes, and with each one holding the [Rope] in `versions`.
+    fn assert_tree(history: &History<Lipsum>, versions: &[(usize, Rope<Lipsum>)]) {
+        for (id, revision) in history.revisions() {
+            match revision.parent() {
+                Some(parent) => {
+                    let parent = history.revision(parent).unwrap();
+                    assert!(parent.children().contains(&id));
+                    let changes = revision.rope().changed_ranges(parent.rope());
+                    assert_eq!(revision.changes(), changes.as_slice());
+                }
+                None => assert!(revision.changes().is_empty()),
+            }
+        }
+        for (id, rope) in versions {
+            if let Some(revision) = history.revision(*id) {
+                assert_eq!(revision.rope(), rope);
+            }
+        }
+    }
+
+    #[test]
+    fn prune_01() {
+        let mut history = History::new(Rope::from_slice(lorem_ipsum().as_slice()));
+        let mut versions = vec![(0, history.rope().clone())];
+        for i in 0..10 {
+            history.rope_mut().insert(i * 3, Dolor(i));
+            let id = history.commit().unwrap();
+            versions.push((id, history.rope().clone()));
+        }
+
+        // Make a branch off of revision 5.
+        history.goto(5);
+        history.rope_mut().remove(0..4);
+        let branch = history.commit().unwrap();
+        versions.push((branch, history.rope().clone()));
+        history.goto(8);
+
+        // The dead branch goes first, then what could be redone.
+        history.set_max_revisions(Some(10));
+        let ids: Vec<usize> = history.revisions().map(|(id, _)| id).collect();
+        assert_eq!(ids, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(history.revision(5).unwrap().children(), &[6]);
+
+        // Then the oldest revisions, without changing any parents.
+        history.set_max_revisions(Some(4));
+        assert_eq!(history.current(), 8);
+        let ids: Vec<usize> = history.revisions().map(|(id, _)| id).collect();
+        assert_eq!(ids, [5, 6, 7, 8]);
+        for id in 6..=8 {
+            assert_eq!(history.revision(id).unwrap().parent(), Some(id - 1));
+        }
+        assert_tree(&history, &versions);
+
+        for id in (5..8).rev() {
+            history.undo();
+            assert_eq!(history.current(), id);
+            assert_eq!(history.rope(), &versions[id].1);
+        }
+        assert_eq!(history.undo(), None);
+
+        history.goto(8);
+        history.set_max_revisions(Some(0));
+        assert_eq!(history.len(), 1);
+        assert_eq!(history.undo(), None);
+        assert_eq!(history.rope(), &versions[8].1);
+    }
+
+    #[test]
+    fn prune_02() {
+        let mut history = History::new(Rope::from_slice(lorem_ipsum().as_slice()));
+        for i in 0..20 {
+            // Replace everything, so that revisions share nothing. No clones
+            // are kept, since they would keep the pruned nodes alive.
+            *history.rope_mut() = Rope::from_slice(&[Dolor(i); 100]);
+            history.commit().unwrap();
+        }
+        history.goto(15);
+
+        let bytes = history.heap_bytes();
+        assert!(bytes > 20 * history.rope().stats().heap_bytes);
+
+        let max_bytes = bytes / 4;
+        history.set_max_bytes(Some(max_bytes));
+        assert!(history.heap_bytes() <= max_bytes);
+        assert!(history.len() > 1);
+        assert_eq!(history.current(), 15);
+        assert_tree(&history, &[]);
+
+        // The revisions leading up to the current one are kept the longest.
+        let oldest = history.revisions().next().unwrap().0;
+        let ids: Vec<usize> = history.revisions().map(|(id, _)| id).collect();
+        assert_eq!(ids, (oldest..=15).collect::<Vec<_>>());
+
+        // Committing keeps the revisions under the limit.
+        for i in 0..10 {
+            history.rope_mut().insert(0, Dolor(i));
+            history.rope_mut().insert(50, Dolor(i));
+            history.commit();
+            assert!(history.heap_bytes() <= max_bytes);
+        }
+
+        history.set_max_bytes(Some(0));
+        assert_eq!(history.len(), 1);
+        assert_eq!(history.max_bytes(), Some(0));
+    }
+}
@@ -49,6 +49,13 @@
 //! use the [prev()][Iter::prev] method to iterate backwards over all of that [Rope<M>]'s
 //! elements.
 //!
+//! # Owned iterators
+//!
+//! [Iter<T>] and [Chunks<T>] borrow the [Rope<M>] they iterate over. When an
+//! iterator needs to outlive the [Rope<M>], or be sent to another thread while
+//! the [Rope<M>] is being edited, [IntoIter<T>] and [OwnedChunks<T>] can be used
+//! instead. They share the [Rope<M>]'s data, rather than borrowing it.
+//!
 //! # A possible point of confusion
 //!
 //! The Rust standard library has an iterator trait [DoubleEndedIterator] with
@@ -68,8 +75,10 @@
 //! [RopeSlice<T>]: crate::slice::RopeSlice
 //! [rev()]: DoubleEndedIterator::rev
 
+use std::ops::Deref;
 use std::sync::Arc;
 
+use crate::config::{DefaultConfig, RopeConfig};
 use crate::rope::Measurable;
 use crate::slice_utils::{start_width_to_index, index_to_width, width_of};
 use crate::tree::{Node, SliceInfo};
@@ -78,11 +87,12 @@ use crate::tree::{Node, SliceInfo};
 
 /// An iterator over a [Rope<M>][crate::rope::Rope]'s elements.
 #[derive(Debug, Clone)]
-pub struct Iter<'a, M>
+pub struct Iter<'a, M, C = DefaultConfig>
 where
     M: Measurable,
+    C: RopeConfig,
 {
-    chunks: Chunks<'a, M>,
+    chunks: Chunks<'a, M, C>,
     cur_chunk: &'a [M],
     index: usize,
     width: usize,
@@ -96,7 +106,20 @@ impl<'a, M> Iter<'a, M>
 where
     M: Measurable,
 {
-    pub(crate) fn new(node: &'a Arc<Node<M>>) -> Self {
+    /// Used for tests and debugging purposes.
+    #[allow(dead_code)]
+    #[inline(always)]
+    pub(crate) fn from_slice(slice: &'a [M]) -> Self {
+        Iter::from_slice_with_config(slice)
+    }
+}
+
+impl<'a, M, C> Iter<'a, M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    pub(crate) fn new(node: &'a Arc<Node<M, C>>) -> Self {
         let mut chunk_iter = Chunks::new(node);
         let cur_chunk = if let Some(chunk) = chunk_iter.next() {
             chunk
@@ -117,15 +140,28 @@ where
 
     #[inline(always)]
     pub(crate) fn new_with_range(
-        node: &'a Arc<Node<M>>,
+        node: &'a Arc<Node<M, C>>,
         index_range: (usize, usize),
         width_range: (usize, usize),
     ) -> Self {
-        Iter::new_with_range_at_width(node, width_range.0, index_range, width_range)
+        let (mut chunks, _, chunk_start_width) =
+            Chunks::new_with_range_at_index(node, index_range.0, index_range, width_range);
+        let cur_chunk = chunks.next().unwrap_or(&[]);
+
+        Iter {
+            chunks,
+            cur_chunk,
+            index: 0,
+            width: chunk_start_width,
+            last_call_was_prev_impl: false,
+            total_len: index_range.1 - index_range.0,
+            remaining_len: index_range.1 - index_range.0,
+            is_reversed: false,
+        }
     }
 
     pub(crate) fn new_with_range_at_width(
-        node: &'a Arc<Node<M>>,
+        node: &'a Arc<Node<M, C>>,
         at_width: usize,
         index_range: (usize, usize),
         width_range: (usize, usize),
@@ -164,12 +200,12 @@ where
     }
 
     #[inline(always)]
-    pub(crate) fn from_slice(slice: &'a [M]) -> Self {
+    pub(crate) fn from_slice_with_config(slice: &'a [M]) -> Self {
         Iter::from_slice_at(slice, 0)
     }
 
     pub(crate) fn from_slice_at(slice: &'a [M], width: usize) -> Self {
-        let mut chunks = Chunks::from_slice(slice, false);
+        let mut chunks = Chunks::from_slice_with_config(slice, false);
         let cur_chunk = if let Some(chunk) = chunks.next() {
             chunk
         } else {
@@ -286,9 +322,10 @@ where
     }
 }
 
-impl<'a, M> Iterator for Iter<'a, M>
+impl<'a, M, C> Iterator for Iter<'a, M, C>
 where
     M: Measurable,
+    C: RopeConfig,
 {
     type Item = (usize, M);
 
@@ -314,7 +351,12 @@ where
     }
 }
 
-impl<'a, M> ExactSizeIterator for Iter<'a, M> where M: Measurable {}
+impl<'a, M, C> ExactSizeIterator for Iter<'a, M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+}
 
 //==========================================================
 
@@ -335,22 +377,24 @@ impl<'a, M> ExactSizeIterator for Iter<'a, M> where M: Measurable {}
 /// [RopeSlice<T>]: crate::slice::RopeSlice
 /// [rev()]: DoubleEndedIterator::rev
 #[derive(Debug, Clone)]
-pub struct Chunks<'a, M>
+pub struct Chunks<'a, M, C = DefaultConfig>
 where
     M: Measurable,
+    C: RopeConfig,
 {
-    iter: ChunksEnum<'a, M>,
+    iter: ChunksEnum<'a, M, C>,
     is_reversed: bool,
 }
 
 #[derive(Debug, Clone)]
-enum ChunksEnum<'a, M>
+enum ChunksEnum<'a, M, C>
 where
     M: Measurable,
+    C: RopeConfig,
 {
     Full {
         /// (node ref, index of current child)
-        node_stack: Vec<(&'a Arc<Node<M>>, usize)>,
+        node_stack: Vec<(&'a Arc<Node<M, C>>, usize)>,
         /// Total lenght of the data range of the iterator.
         len: usize,
         /// The index of the current element relative to the data range start.
@@ -365,16 +409,28 @@ where
 impl<'a, M> Chunks<'a, M>
 where
     M: Measurable,
+{
+    /// Used for tests and debugging purposes.
+    #[allow(dead_code)]
+    pub(crate) fn from_slice(slice: &'a [M], is_end: bool) -> Self {
+        Chunks::from_slice_with_config(slice, is_end)
+    }
+}
+
+impl<'a, M, C> Chunks<'a, M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
 {
     #[inline(always)]
-    pub(crate) fn new(node: &'a Arc<Node<M>>) -> Self {
+    pub(crate) fn new(node: &'a Arc<Node<M, C>>) -> Self {
         let info = node.slice_info();
         Chunks::new_with_range_at_index(node, 0, (0, info.len as usize), (0, info.width as usize)).0
     }
 
     #[inline(always)]
     pub(crate) fn new_with_range(
-        node: &'a Arc<Node<M>>,
+        node: &'a Arc<Node<M, C>>,
         index_range: (usize, usize),
         width_range: (usize, usize),
     ) -> Self {
@@ -397,11 +453,11 @@ where
     /// Returns the iterator and the index/width of its start relative
     /// to the start of the node.
     pub(crate) fn new_with_range_at_index(
-        node: &Arc<Node<M>>,
+        node: &Arc<Node<M, C>>,
         at_index: usize,
         index_range: (usize, usize),
         width_range: (usize, usize),
-    ) -> (Chunks<M>, usize, usize) {
+    ) -> (Chunks<M, C>, usize, usize) {
         debug_assert!(at_index >= index_range.0);
         debug_assert!(at_index <= index_range.1);
 
@@ -459,7 +515,7 @@ where
         let mut info = SliceInfo::new();
         let mut index = at_index as isize;
         let node_stack = {
-            let mut node_stack: Vec<(&Arc<Node<M>>, usize)> = Vec::new();
+            let mut node_stack: Vec<(&Arc<Node<M, C>>, usize)> = Vec::new();
             let mut node_ref = node;
             loop {
                 match **node_ref {
@@ -506,7 +562,7 @@ where
 
     #[inline(always)]
     pub(crate) fn new_with_range_at_width(
-        node: &'a Arc<Node<M>>,
+        node: &'a Arc<Node<M, C>>,
         at_width: usize,
         index_range: (usize, usize),
         width_range: (usize, usize),
@@ -520,7 +576,7 @@ where
         Chunks::new_with_range_at_index(node, at_index, index_range, width_range)
     }
 
-    pub(crate) fn from_slice(slice: &'a [M], is_end: bool) -> Self {
+    pub(crate) fn from_slice_with_config(slice: &'a [M], is_end: bool) -> Self {
         Chunks {
             iter: ChunksEnum::Light { slice, is_end },
             is_reversed: false,
@@ -713,9 +769,10 @@ where
     }
 }
 
-impl<'a, M> Iterator for Chunks<'a, M>
+impl<'a, M, C> Iterator for Chunks<'a, M, C>
 where
     M: Measurable,
+    C: RopeConfig,
 {
     type Item = &'a [M];
 
@@ -732,6 +789,351 @@ where
     }
 }
 
+//==========================================================
+
+/// A chunk of a [Rope<M>][crate::rope::Rope]'s elements, yielded by [OwnedChunks<M>].
+///
+/// It shares the leaf that it comes from with the [Rope<M>][crate::rope::Rope],
+/// so creating it does not copy any data. It dereferences to a [`&[M]`][Measurable].
+#[derive(Clone)]
+pub struct OwnedChunk<M, C = DefaultConfig>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    leaf: Arc<Node<M, C>>,
+    start: usize,
+    end: usize,
+}
+
+impl<M, C> Deref for OwnedChunk<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    type Target = [M];
+
+    #[inline]
+    fn deref(&self) -> &[M] {
+        &self.leaf.leaf_slice()[self.start..self.end]
+    }
+}
+
+impl<M, C> AsRef<[M]> for OwnedChunk<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    #[inline]
+    fn as_ref(&self) -> &[M] {
+        self
+    }
+}
+
+impl<M, C> std::fmt::Debug for OwnedChunk<M, C>
+where
+    M: Measurable + std::fmt::Debug,
+    C: RopeConfig,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// An owned iterator over a [Rope<M>][crate::rope::Rope]'s contiguous [M][Measurable]
+/// chunks.
+///
+/// This is the owned counterpart of [Chunks<M>]. Instead of borrowing the
+/// [Rope<M>][crate::rope::Rope], it shares its data, so it can outlive it, or be
+/// sent to another thread, while the [Rope<M>][crate::rope::Rope] is edited.
+///
+/// The chunks are yielded as [OwnedChunk<M>]s.
+#[derive(Clone)]
+pub struct OwnedChunks<M, C = DefaultConfig>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    node: Arc<Node<M, C>>,
+    /// The range of the iterator, relative to the start of `node`.
+    index_range: (usize, usize),
+    /// The position of the iterator, relative to the start of `node`.
+    index: usize,
+    /// The branches above the last returned chunk, and the indices where they start.
+    node_stack: Vec<(Arc<Node<M, C>>, usize)>,
+    is_reversed: bool,
+}
+
+impl<M, C> OwnedChunks<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    pub(crate) fn new_with_range(node: Arc<Node<M, C>>, index_range: (usize, usize)) -> Self {
+        OwnedChunks {
+            node,
+            index_range,
+            index: index_range.0,
+            node_stack: Vec::new(),
+            is_reversed: false,
+        }
+    }
+
+    /// Returns the leaf that contains `index`, and the index where it starts.
+    ///
+    /// Only climbs the node stack as far as needed, so fetching the leaf next
+    /// to the last one is amortized O(1).
+    fn leaf_at(&mut self, index: usize) -> (Arc<Node<M, C>>, usize) {
+        while let Some((branch, start)) = self.node_stack.last() {
+            if *start <= index && index < start + branch.len() {
+                break;
+            }
+            self.node_stack.pop();
+        }
+
+        // The lowest branch that contains `index` is pushed back while descending.
+        let (mut node, mut start) = self
+            .node_stack
+            .pop()
+            .unwrap_or_else(|| (Arc::clone(&self.node), 0));
+        while !node.is_leaf() {
+            let (child_i, acc_info) = node.children().search_index(index - start);
+            let child = Arc::clone(&node.children().nodes()[child_i]);
+            self.node_stack.push((node, start));
+            start += acc_info.len as usize;
+            node = child;
+        }
+
+        (node, start)
+    }
+
+    /// Reverses the direction of the iterator in-place.
+    ///
+    /// In other words, swaps the behavior of [prev()][Self::prev]
+    /// and [next()][Self::next].
+    #[inline]
+    pub fn reverse(&mut self) {
+        self.is_reversed = !self.is_reversed;
+    }
+
+    /// Same as [reverse()][Self::reverse], but returns itself.
+    #[inline]
+    #[must_use]
+    pub fn reversed(mut self) -> Self {
+        self.reverse();
+        self
+    }
+
+    /// Advances the iterator backwards and returns the previous value.
+    ///
+    /// Runs in amortized O(1) time and worst-case O(log N) time.
+    #[inline(always)]
+    pub fn prev(&mut self) -> Option<OwnedChunk<M, C>> {
+        if !self.is_reversed {
+            self.prev_impl()
+        } else {
+            self.next_impl()
+        }
+    }
+
+    fn prev_impl(&mut self) -> Option<OwnedChunk<M, C>> {
+        if self.index <= self.index_range.0 {
+            return None;
+        }
+
+        let (leaf, leaf_start) = self.leaf_at(self.index - 1);
+        let start = self.index_range.0.saturating_sub(leaf_start);
+        let end = self.index - leaf_start;
+        self.index = leaf_start + start;
+
+        Some(OwnedChunk { leaf, start, end })
+    }
+
+    fn next_impl(&mut self) -> Option<OwnedChunk<M, C>> {
+        if self.index >= self.index_range.1 {
+            return None;
+        }
+
+        let (leaf, leaf_start) = self.leaf_at(self.index);
+        let start = self.index - leaf_start;
+        let end = leaf.leaf_slice().len().min(self.index_range.1 - leaf_start);
+        self.index = leaf_start + end;
+
+        Some(OwnedChunk { leaf, start, end })
+    }
+}
+
+impl<M, C> Iterator for OwnedChunks<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    type Item = OwnedChunk<M, C>;
+
+    /// Advances the iterator forward and returns the next value.
+    ///
+    /// Runs in amortized O(1) time and worst-case O(log N) time.
+    #[inline(always)]
+    fn next(&mut self) -> Option<OwnedChunk<M, C>> {
+        if !self.is_reversed {
+            self.next_impl()
+        } else {
+            self.prev_impl()
+        }
+    }
+}
+
+/// An owned iterator over a [Rope<M>][crate::rope::Rope]'s elements.
+///
+/// This is the owned counterpart of [Iter<M>]. Instead of borrowing the
+/// [Rope<M>][crate::rope::Rope], it shares its data, so it can outlive it, or be
+/// sent to another thread, while the [Rope<M>][crate::rope::Rope] is edited.
+///
+/// Just like [Iter<M>], it yields values of type `(usize, M)`, where the `usize`
+/// is the width sum where the given [M][Measurable] starts.
+#[derive(Clone)]
+pub struct IntoIter<M, C = DefaultConfig>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    node: Arc<Node<M, C>>,
+    /// The range of the iterator, relative to the start of `node`.
+    index_range: (usize, usize),
+    /// The position of the iterator, relative to the start of `node`.
+    index: usize,
+    /// The width of the iterator, relative to the start of its range.
+    width: usize,
+    /// The leaf of the last returned element, and the index where it starts.
+    cur_leaf: Option<(Arc<Node<M, C>>, usize)>,
+    is_reversed: bool,
+}
+
+impl<M, C> IntoIter<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    pub(crate) fn new_with_range(node: Arc<Node<M, C>>, index_range: (usize, usize)) -> Self {
+        IntoIter {
+            node,
+            index_range,
+            index: index_range.0,
+            width: 0,
+            cur_leaf: None,
+            is_reversed: false,
+        }
+    }
+
+    /// Reverses the direction of the iterator in-place.
+    ///
+    /// In other words, swaps the behavior of [prev()][Self::prev]
+    /// and [next()][Self::next].
+    #[inline]
+    pub fn reverse(&mut self) {
+        self.is_reversed = !self.is_reversed;
+    }
+
+    /// Same as [reverse()][Self::reverse], but returns itself.
+    #[inline]
+    #[must_use]
+    pub fn reversed(mut self) -> Self {
+        self.reverse();
+        self
+    }
+
+    /// Advances the iterator backwards and returns the previous value.
+    ///
+    /// Runs in amortized O(1) time and worst-case O(log N) time.
+    #[inline(always)]
+    pub fn prev(&mut self) -> Option<(usize, M)> {
+        if !self.is_reversed {
+            self.prev_impl()
+        } else {
+            self.next_impl()
+        }
+    }
+
+    /// Returns the element at `index`, fetching its leaf only if it isn't
+    /// the same as the one of the last returned element.
+    #[inline]
+    fn element_at(&mut self, index: usize) -> M {
+        if let Some((ref leaf, leaf_start)) = self.cur_leaf {
+            let slice = leaf.leaf_slice();
+            if index >= leaf_start && index < leaf_start + slice.len() {
+                return slice[index - leaf_start];
+            }
+        }
+
+        let (leaf, info) = Node::get_leaf_at_index(&self.node, index);
+        let leaf_start = info.len as usize;
+        let element = leaf.leaf_slice()[index - leaf_start];
+        self.cur_leaf = Some((leaf, leaf_start));
+        element
+    }
+
+    #[inline]
+    fn prev_impl(&mut self) -> Option<(usize, M)> {
+        if self.index <= self.index_range.0 {
+            return None;
+        }
+
+        self.index -= 1;
+        let element = self.element_at(self.index);
+        self.width -= element.width();
+        Some((self.width, element))
+    }
+
+    #[inline]
+    fn next_impl(&mut self) -> Option<(usize, M)> {
+        if self.index >= self.index_range.1 {
+            return None;
+        }
+
+        let element = self.element_at(self.index);
+        self.index += 1;
+        let old_width = self.width;
+        self.width += element.width();
+        Some((old_width, element))
+    }
+}
+
+impl<M, C> Iterator for IntoIter<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    type Item = (usize, M);
+
+    /// Advances the iterator forward and returns the next value.
+    ///
+    /// Runs in amortized O(1) time and worst-case O(log N) time.
+    #[inline(always)]
+    fn next(&mut self) -> Option<(usize, M)> {
+        if !self.is_reversed {
+            self.next_impl()
+        } else {
+            self.prev_impl()
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = if !self.is_reversed {
+            self.index_range.1 - self.index
+        } else {
+            self.index - self.index_range.0
+        };
+        (remaining, Some(remaining))
+    }
+}
+
+impl<M, C> ExactSizeIterator for IntoIter<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::while_let_on_iterator)]
@@ -869,6 +1271,25 @@ mod tests {
         assert_eq!(iter.next(), Some((27, Lorem)));
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn iter_09() {
+        // Slices that start and end on the same width, spanning several leaves.
+        let rope = Rope::from_slice(&[Sit; 1000]);
+        assert_eq!(rope.iter().count(), 1000);
+        assert_eq!(rope.index_slice(..).iter().count(), 1000);
+        assert_eq!(rope.index_slice(10..990).iter().count(), 980);
+
+        let mut iter = rope.index_slice(10..990).iter();
+        while let Some(_) = iter.next() {}
+        let mut count = 0;
+        while let Some((width, element)) = iter.prev() {
+            assert_eq!((width, element), (0, Sit));
+            count += 1;
+        }
+        assert_eq!(count, 980);
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn iter_at_01() {
@@ -1085,7 +1506,7 @@ mod tests {
     fn chunks_at_03() {
         let rope = Rope::from_slice(lorem_ipsum().as_slice());
         let slice = rope.width_slice(34..34);
-        println!("{}", slice);
+        println!("{:?}", slice);
 
         let (mut chunks, _, _) = slice.chunks_at_index(0);
         assert_eq!(chunks.next(), Some([Sit].as_slice()));
@@ -1282,4 +1703,95 @@ mod tests {
         let rope: Vec<Lipsum> = rope.iter().map(|(_, element)| element).collect();
         assert_eq!(&*rope, [].as_slice())
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn owned_chunks_01() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let mut owned = rope.owned_chunks();
+
+        for chunk in rope.chunks() {
+            assert_eq!(Some(chunk), owned.next().as_deref());
+        }
+        assert!(owned.next().is_none());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn owned_chunks_02() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let slice = rope.width_slice(34..301);
+        let mut owned = rope.owned_width_slice(34..301).into_chunks();
+
+        let mut stack = Vec::new();
+        for chunk in slice.chunks() {
+            let owned_chunk = owned.next().unwrap();
+            assert_eq!(chunk, &*owned_chunk);
+            stack.push(owned_chunk);
+        }
+        assert!(owned.next().is_none());
+
+        owned.reverse();
+        while let Some(chunk) = owned.next() {
+            assert_eq!(&*stack.pop().unwrap(), &*chunk);
+        }
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn owned_chunks_03() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let chunks: Vec<_> = rope.chunks().collect();
+        let mut owned = rope.owned_chunks();
+
+        // Changing direction midway must not leave stale branches in the node stack.
+        for (i, chunk) in chunks.iter().enumerate() {
+            assert_eq!(Some(*chunk), owned.next().as_deref());
+            if i % 3 == 2 {
+                assert_eq!(Some(*chunk), owned.prev().as_deref());
+                assert_eq!(Some(*chunk), owned.next().as_deref());
+            }
+            assert!(owned.node_stack.len() <= rope.root.depth());
+        }
+        assert!(owned.next().is_none());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn into_iter_01() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let iter = rope.owned_width_slice(..).into_iter();
+        rope.remove(..);
+
+        assert_eq!(iter.len(), lorem_ipsum().len());
+        for (i, (width, element)) in iter.enumerate() {
+            assert_eq!(element, lorem_ipsum()[i]);
+            assert_eq!(width, index_to_width(&lorem_ipsum(), i));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn into_iter_02() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let slice = rope.width_slice(34..301);
+        let mut iter_1 = slice.iter();
+        let mut iter_2 = rope.owned_width_slice(34..301).into_iter();
+
+        let mut stack = Vec::new();
+        while let Some((_, element)) = iter_1.next() {
+            let (width, owned_element) = iter_2.next().unwrap();
+            assert_eq!(element, owned_element);
+            stack.push((width, owned_element));
+        }
+        assert_eq!(iter_2.next(), None);
+        assert_eq!(iter_2.len(), 0);
+
+        iter_2.reverse();
+        while let Some(item) = iter_2.next() {
+            assert_eq!(stack.pop(), Some(item));
+        }
+        assert!(stack.is_empty());
+    }
 }
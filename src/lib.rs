@@ -10,12 +10,15 @@
 //! in bits or bytes, but is instead decided by the implementor, and can be whatever
 //! value they want.
 //!
-//! The library is made up of four main components:
+//! The library is made up of seven main components:
 //!
 //! - [Rope<M>]: the main rope type.
 //! - [RopeSlice<T>]: an immutable view into part of a [Rope<M>].
+//! - [OwnedRopeSlice<T>]: an owned, immutable view into part of a [Rope<M>].
 //! - [iter]: iterators over [Rope<M>]/[RopeSlice<T>] data.
 //! - [RopeBuilder<T>]: an efficient incremental [Rope<M>] builder.
+//! - [RopeReader<M>]: a cursor for fast nearby reads from a [Rope<M>].
+//! - [RopeConfig]: compile time parameters for the tree behind a [Rope<M>].
 //!
 //! # A Basic Example
 //!
@@ -100,6 +103,8 @@
 //! - The [chunk_at_*()][Rope::chunk_at_width]
 //!   chunk-fetching methods of [Rope<M>] and [RopeSlice<T>].
 //! - The [Chunks](iter::Chunks) iterator.
+//! - The [NodeRef<M>] view into the tree behind a [Rope<M>], obtained with
+//!   [root()][Rope::root] or [visit()][Rope::visit].
 //! - The functions in [slice_utils] for operating on
 //!   [&`[M]`][Measurable] slices.
 //!
@@ -116,22 +121,44 @@
 #![allow(clippy::redundant_field_names)]
 #![allow(clippy::type_complexity)]
 
+mod compare;
+mod config;
+mod dump;
+mod edit;
+mod hash;
+mod history;
+mod node_ref;
+mod parallel;
+mod patch;
+mod reader;
 mod rope;
 mod rope_builder;
 mod slice;
 mod slice_utils;
+mod stats;
 mod tree;
+mod validate;
 
 pub mod iter;
 
 use std::ops::Bound;
 
+pub use crate::config::{CacheMode, CacheWidths, DefaultConfig, NoCache, RopeConfig, SmallConfig};
+pub use crate::dump::{DebugChunks, DebugTree, DebugWidths};
+pub use crate::edit::{Edit, EditMap};
+pub use crate::hash::HashCache;
+pub use crate::history::{Changes, History, Revision};
+pub use crate::node_ref::{ChildRef, NodeRef};
+pub use crate::patch::{Hunk, HunkKind, Patch};
+pub use crate::reader::RopeReader;
 pub use crate::rope::{Measurable, Rope};
 pub use crate::rope_builder::RopeBuilder;
-pub use crate::slice::RopeSlice;
+pub use crate::slice::{OwnedRopeSlice, RopeSlice};
+pub use crate::stats::{Fragmentation, RopeStats};
+pub use crate::validate::{ValidationError, ValidationErrorKind};
 
 /// Simple test struct, useful in making sure that the systems work.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Lipsum {
     Lorem,
     Ipsum,
@@ -216,6 +243,17 @@ pub enum Error {
         Option<usize>, // End.
         usize,         // Rope char length.
     ),
+
+    /// Indicates that two of the edits passed to
+    /// [apply_edits()][Rope::apply_edits] overlap, or that two of the
+    /// [Hunk<M>]s passed to [apply_patch()][Rope::apply_patch] overlap or
+    /// are out of order.
+    ///
+    /// Contains the widths at the start of both edits, in order.
+    OverlappingEdits(
+        usize, // First start.
+        usize, // Second start.
+    ),
 }
 
 impl std::fmt::Debug for Error {
@@ -259,6 +297,13 @@ impl std::fmt::Debug for Error {
                 write_range(f, start_idx_opt, end_idx_opt)?;
                 write!(f, ", Rope/RopeSlice char length {}", len)
             }
+            Error::OverlappingEdits(first, second) => {
+                write!(
+                    f,
+                    "Overlapping edits: edit at width {} overlaps edit at width {}",
+                    second, first
+                )
+            }
         }
     }
 }
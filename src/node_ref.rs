@@ -0,0 +1,300 @@
+use crate::config::{DefaultConfig, RopeConfig};
+use crate::rope::{Measurable, Rope};
+use crate::tree::Node;
+
+/// A read-only view into a node of the tree behind a [Rope<M>].
+///
+/// Every [Rope<M>] is a B-tree, where the leaves hold the elements, and the
+/// branches hold the leaves, or other branches. A [NodeRef<M>] can be used to
+/// walk that tree, for custom searches that skip over whole branches, or to
+/// inspect its shape. It can be obtained with [root()][Rope::root], or
+/// handed out by [visit()][Rope::visit].
+///
+/// # Examples
+///
+/// ```rust
+/// # use any_rope::{NodeRef, Rope};
+/// # use any_rope::Lipsum::*;
+/// let rope = Rope::from_slice(&[Lorem, Ipsum, Dolor(3), Sit].repeat(1000));
+///
+/// // Finds the first element of width 3, without looking at the elements of
+/// // branches that can't have it.
+/// fn find<C: any_rope::RopeConfig>(node: NodeRef<any_rope::Lipsum, C>) -> Option<usize> {
+///     if let Some(slice) = node.leaf_slice() {
+///         return slice.iter().position(|m| *m == Dolor(3));
+///     }
+///     let mut index = 0;
+///     for child in node.children() {
+///         if child.width >= 3 {
+///             if let Some(found) = find(child.node) {
+///                 return Some(index + found);
+///             }
+///         }
+///         index += child.len;
+///     }
+///     None
+/// }
+///
+/// assert_eq!(find(rope.root()), Some(2));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct NodeRef<'a, M, C = DefaultConfig>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    node: &'a Node<M, C>,
+}
+
+/// A child of a branch [NodeRef<M>], alongside the cached information about
+/// it that its parent keeps, returned by [children()][NodeRef::children].
+#[derive(Debug, Clone, Copy)]
+pub struct ChildRef<'a, M, C = DefaultConfig>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    /// The child node itself.
+    pub node: NodeRef<'a, M, C>,
+    /// The amount of elements in the child.
+    pub len: usize,
+    /// The sum of the widths of the elements in the child.
+    pub width: usize,
+    /// Whether the child ends in an element with a width of 0.
+    pub zero_width_end: bool,
+}
+
+impl<'a, M, C> NodeRef<'a, M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    #[inline]
+    pub(crate) fn new(node: &'a Node<M, C>) -> Self {
+        Self { node }
+    }
+
+    /// Returns `true` if the node is a leaf, holding elements instead of
+    /// other nodes.
+    #[inline]
+    pub fn is_leaf(&self) -> bool {
+        self.node.is_leaf()
+    }
+
+    /// The amount of elements in the node and its children.
+    ///
+    /// Runs in O(1) time for leaves, and O(M) time for branches, where M is
+    /// the amount of children.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.node.len()
+    }
+
+    /// Returns `true` if the node has no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The sum of the widths of the elements in the node and its children.
+    ///
+    /// Runs in O(1) time for leaves, and O(M) time for branches, where M is
+    /// the amount of children.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.node.width()
+    }
+
+    /// How many levels of branches are below this node.
+    ///
+    /// All leaves are at the same depth, so this is 0 for leaves, 1 for
+    /// branches of leaves, and so on.
+    ///
+    /// Runs in O(log N) time.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.node.depth()
+    }
+
+    /// The elements of the node, if it is a leaf, or [None] if it is a
+    /// branch.
+    #[inline]
+    pub fn leaf_slice(&self) -> Option<&'a [M]> {
+        match *self.node {
+            Node::Leaf(ref slice) => Some(slice),
+            Node::Branch(_) => None,
+        }
+    }
+
+    /// Iterates over the children of the node, in order, which is empty if the
+    /// node is a leaf.
+    pub fn children(
+        &self,
+    ) -> impl ExactSizeIterator<Item = ChildRef<'a, M, C>> + DoubleEndedIterator + 'a {
+        let children = match *self.node {
+            Node::Leaf(_) => None,
+            Node::Branch(ref children) => Some(children),
+        };
+
+        children
+            .map_or([].iter().zip([].iter()), |children| children.iter())
+            .map(|((info, zero_width_end), node)| ChildRef {
+                node: NodeRef::new(node),
+                len: info.len as usize,
+                width: info.width as usize,
+                zero_width_end: *zero_width_end,
+            })
+    }
+
+    /// Calls `f` on this node and on its descendants, as described in
+    /// [visit()][Rope::visit], where `index` and `width` are the start of
+    /// this node.
+    fn visit<F>(self, index: usize, width: usize, f: &mut F)
+    where
+        F: FnMut(NodeRef<'a, M, C>, usize, usize) -> bool,
+    {
+        if !f(self, index, width) {
+            return;
+        }
+
+        let (mut index, mut width) = (index, width);
+        for child in self.children() {
+            child.node.visit(index, width, f);
+            index += child.len;
+            width += child.width;
+        }
+    }
+}
+
+impl<M, C> Rope<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    /// A read-only view into the root node of the tree behind the
+    /// [Rope<M>].
+    ///
+    /// Runs in O(1) time.
+    #[inline]
+    pub fn root(&self) -> NodeRef<'_, M, C> {
+        NodeRef::new(&self.root)
+    }
+
+    /// Visits the nodes of the tree behind the [Rope<M>], depth first and in
+    /// order.
+    ///
+    /// `f` is called with each node, and the index and width at which that
+    /// node starts in the [Rope<M>]. If it returns `false`, the children of
+    /// that node are skipped.
+    ///
+    /// Runs in O(N) time if no nodes are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let rope = Rope::from_slice(&[Lorem, Ipsum, Dolor(3), Sit].repeat(1000));
+    ///
+    /// // Collects the start of every leaf that overlaps with a width range.
+    /// let mut starts = Vec::new();
+    /// rope.visit(|node, index, width| {
+    ///     if node.is_leaf() {
+    ///         starts.push(index);
+    ///     }
+    ///     width < 200 && width + node.width() > 100
+    /// });
+    ///
+    /// assert!(starts.len() < rope.chunks().count());
+    /// assert_eq!(starts.windows(2).filter(|w| w[0] >= w[1]).count(), 0);
+    /// ```
+    pub fn visit<'a, F>(&'a self, mut f: F)
+    where
+        F: FnMut(NodeRef<'a, M, C>, usize, usize) -> bool,
+    {
+        NodeRef::new(&*self.root).visit(0, 0, &mut f);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Lipsum::{self, *};
+    use crate::{Measurable, Rope};
+
+    fn lorem_ipsum() -> Vec<Lipsum> {
+        [Lorem, Ipsum, Dolor(3), Sit, Consectur("amet"), Dolor(0)].repeat(50)
+    }
+
+    #[test]
+    fn root_01() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let root = rope.root();
+
+        assert!(!root.is_leaf());
+        assert!(root.leaf_slice().is_none());
+        assert_eq!(root.len(), rope.len());
+        assert_eq!(root.width(), rope.width());
+        assert_eq!(root.depth(), rope.root.depth());
+
+        let children: Vec<_> = root.children().collect();
+        assert_eq!(children.len(), rope.root.child_count());
+        for child in children {
+            assert_eq!(child.len, child.node.len());
+            assert_eq!(child.width, child.node.width());
+            assert_eq!(child.node.depth() + 1, root.depth());
+        }
+    }
+
+    #[test]
+    fn root_02() {
+        let rope = Rope::from_slice(&[Lorem, Dolor(0)]);
+        let root = rope.root();
+
+        assert!(root.is_leaf());
+        assert_eq!(root.depth(), 0);
+        assert_eq!(root.children().len(), 0);
+        assert_eq!(root.leaf_slice(), Some([Lorem, Dolor(0)].as_slice()));
+    }
+
+    #[test]
+    fn visit_01() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+
+        let mut vec = Vec::new();
+        let mut width = 0;
+        rope.visit(|node, node_index, node_width| {
+            if let Some(slice) = node.leaf_slice() {
+                assert_eq!(node_index, vec.len());
+                assert_eq!(node_width, width);
+                vec.extend_from_slice(slice);
+                width += node.width();
+            }
+            true
+        });
+
+        assert_eq!(rope, vec.as_slice());
+    }
+
+    #[test]
+    fn visit_02() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+
+        let mut visited = 0;
+        rope.visit(|_, _, _| {
+            visited += 1;
+            false
+        });
+        assert_eq!(visited, 1);
+
+        // Leaves end in a zero width element if their parent says so.
+        rope.visit(|node, _, _| {
+            for child in node.children() {
+                let last = child.node.leaf_slice().and_then(|slice| slice.last());
+                if let Some(last) = last {
+                    assert_eq!(child.zero_width_end, last.width() == 0);
+                }
+            }
+            true
+        });
+    }
+}
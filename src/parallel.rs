@@ -0,0 +1,332 @@
+//! Parallel construction and scanning of [Rope<M>]s.
+//!
+//! Everything in here runs on [std::thread::scope], so the closures passed
+//! in can borrow from their surroundings. The work is split between as many
+//! threads as [std::thread::available_parallelism] allows.
+
+use std::num::NonZeroUsize;
+use std::thread;
+
+use crate::config::RopeConfig;
+use crate::iter::Chunks;
+use crate::rope::{Measurable, Rope};
+use crate::rope_builder::RopeBuilder;
+use crate::tree::max_len;
+
+/// Below this many leaves per thread, spawning threads isn't worth it.
+const MIN_LEAVES_PER_THREAD: usize = 16;
+
+impl<M> Rope<M>
+where
+    M: Measurable + Send + Sync,
+{
+    /// Creates a [Rope<M>] from an [M][Measurable] slice, building separate
+    /// parts of the tree on separate threads.
+    ///
+    /// The result is the same as that of [from_slice()][Rope::from_slice].
+    ///
+    /// Runs in O(N / T + T * log N) time, where T is the amount of threads.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let vec = [Lorem, Ipsum, Dolor(3), Sit].repeat(100_000);
+    /// let rope = Rope::from_slice_parallel(&vec);
+    ///
+    /// assert_eq!(rope, vec.as_slice());
+    /// ```
+    #[inline]
+    pub fn from_slice_parallel(slice: &[M]) -> Self {
+        Self::from_slice_parallel_with_config(slice)
+    }
+}
+
+impl<M, C> Rope<M, C>
+where
+    M: Measurable + Send + Sync,
+    C: RopeConfig,
+{
+    /// Creates a [Rope<M, C>] from an [M][Measurable] slice, with a given
+    /// [RopeConfig], building separate parts of the tree on separate threads.
+    ///
+    /// Runs in O(N / T + T * log N) time, where T is the amount of threads.
+    pub fn from_slice_parallel_with_config(slice: &[M]) -> Self {
+        let threads = thread_count(slice.len() / max_len::<M, C>());
+        Self::from_slice_on_threads(slice, threads)
+    }
+
+    /// Calls `f` on every chunk of the [Rope<M>], from multiple threads.
+    ///
+    /// The [Rope<M>] is split into contiguous parts with
+    /// [split_into_index()][Rope::split_into_index], and each thread goes
+    /// through the chunks of one of them, in order. No guarantees are made
+    /// about the order of chunks in different parts.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// # use std::sync::atomic::{AtomicUsize, Ordering};
+    /// let rope = Rope::from_slice(&[Lorem, Ipsum, Dolor(3), Sit].repeat(100_000));
+    /// let sits = AtomicUsize::new(0);
+    ///
+    /// rope.par_for_each_chunk(|chunk| {
+    ///     let count = chunk.iter().filter(|m| **m == Sit).count();
+    ///     sits.fetch_add(count, Ordering::Relaxed);
+    /// });
+    ///
+    /// assert_eq!(sits.into_inner(), 100_000);
+    /// ```
+    pub fn par_for_each_chunk<F>(&self, f: F)
+    where
+        F: Fn(&[M]) + Sync,
+    {
+        self.par_map_reduce(f, |(), ()| ());
+    }
+
+    /// Maps every chunk of the [Rope<M>] with `map`, and combines the results
+    /// with `reduce`, from multiple threads.
+    ///
+    /// The [Rope<M>] is split into contiguous parts with
+    /// [split_into_index()][Rope::split_into_index], and each thread maps and
+    /// reduces the chunks of one of them. The results of each part are then
+    /// reduced together. Results are always reduced with the ones that come
+    /// right after them, so `reduce` only has to be associative, not
+    /// commutative.
+    ///
+    /// Returns [None] if the [Rope<M>] is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let rope = Rope::from_slice(&[Lorem, Ipsum, Dolor(3), Sit].repeat(100_000));
+    /// let widest = rope.par_map_reduce(
+    ///     |chunk| chunk.iter().map(|m| match m { Dolor(w) => *w, _ => 0 }).max().unwrap(),
+    ///     usize::max,
+    /// );
+    ///
+    /// assert_eq!(widest, Some(3));
+    /// ```
+    pub fn par_map_reduce<T, F, R>(&self, map: F, reduce: R) -> Option<T>
+    where
+        T: Send,
+        F: Fn(&[M]) -> T + Sync,
+        R: Fn(T, T) -> T + Sync,
+    {
+        let threads = thread_count(self.len() / max_len::<M, C>());
+        self.map_reduce_on_threads(threads, map, reduce)
+    }
+
+    //-----------------------------------------------------------------------
+    // Internal utilities
+
+    /// Same as [from_slice_parallel_with_config()], but on up to `threads`
+    /// threads.
+    ///
+    /// [from_slice_parallel_with_config()]: Rope::from_slice_parallel_with_config
+    fn from_slice_on_threads(slice: &[M], threads: usize) -> Self {
+        if threads <= 1 || slice.is_empty() {
+            return Self::from_slice_with_config(slice);
+        }
+
+        let part_len = slice.len().div_ceil(threads);
+        let parts = thread::scope(|scope| {
+            let handles: Vec<_> = slice
+                .chunks(part_len)
+                .map(|part| scope.spawn(move || Self::from_slice_with_config(part)))
+                .collect();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        Self::concat(parts)
+    }
+
+    /// Same as [par_map_reduce()][Rope::par_map_reduce], but on up to
+    /// `threads` threads.
+    fn map_reduce_on_threads<T, F, R>(&self, threads: usize, map: F, reduce: R) -> Option<T>
+    where
+        T: Send,
+        F: Fn(&[M]) -> T + Sync,
+        R: Fn(T, T) -> T + Sync,
+    {
+        let (map, reduce) = (&map, &reduce);
+        let map_reduce_part = move |chunks: Chunks<M, C>| {
+            chunks
+                .filter(|chunk| !chunk.is_empty())
+                .map(map)
+                .reduce(reduce)
+        };
+
+        if threads <= 1 {
+            return map_reduce_part(self.chunks());
+        }
+
+        thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .split_into_index(threads)
+                .into_iter()
+                .map(|slice| scope.spawn(move || map_reduce_part(slice.chunks())))
+                .collect();
+
+            handles
+                .into_iter()
+                .filter_map(|handle| handle.join().unwrap())
+                .reduce(reduce)
+        })
+    }
+}
+
+impl<M, C> RopeBuilder<M, C>
+where
+    M: Measurable + Send + Sync,
+    C: RopeConfig,
+{
+    /// Finishes the build with `rest` appended to the end, building the
+    /// leaves of `rest` on separate threads, and returns the [Rope<M>].
+    ///
+    /// This is meant for when most of the input is already in one slice,
+    /// after having appended some smaller parts with the builder. The result
+    /// is the same as that of appending `rest` and calling
+    /// [finish()][RopeBuilder::finish].
+    ///
+    /// Runs in O(N / T + T * log N) time for `rest`, where T is the amount of
+    /// threads.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::RopeBuilder;
+    /// # use any_rope::Lipsum::*;
+    /// let body = [Lorem, Ipsum, Dolor(3), Sit].repeat(100_000);
+    ///
+    /// let mut builder = RopeBuilder::new();
+    /// builder.append(Amet);
+    /// let rope = builder.parallel_finish(&body);
+    ///
+    /// assert_eq!(rope.len(), 400_001);
+    /// assert_eq!(rope.index_slice(1..), body.as_slice());
+    /// ```
+    pub fn parallel_finish(self, rest: &[M]) -> Rope<M, C> {
+        let threads = thread_count(rest.len() / max_len::<M, C>());
+        self.finish_on_threads(rest, threads)
+    }
+
+    /// Same as [parallel_finish()][RopeBuilder::parallel_finish], but on up
+    /// to `threads` threads.
+    fn finish_on_threads(self, rest: &[M], threads: usize) -> Rope<M, C> {
+        let head = self.finish();
+        if rest.is_empty() {
+            return head;
+        }
+        Rope::concat([head, Rope::from_slice_on_threads(rest, threads)])
+    }
+}
+
+/// The amount of threads to split work on `leaves` leaves between.
+fn thread_count(leaves: usize) -> usize {
+    let available = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    available.min(leaves / MIN_LEAVES_PER_THREAD).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::Lipsum::{self, *};
+
+    fn lorem_ipsum(n: usize) -> Vec<Lipsum> {
+        (0..n)
+            .map(|i| match i % 5 {
+                0 => Lorem,
+                1 => Ipsum,
+                2 => Dolor(i % 7),
+                3 => Sit,
+                _ => Consectur("amet"),
+            })
+            .collect()
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn from_slice_parallel_01() {
+        for n in [0, 1, 100, 10_000, 100_000] {
+            let vec = lorem_ipsum(n);
+            for threads in [1, 2, 3, 8] {
+                let rope: Rope<Lipsum> = Rope::from_slice_on_threads(&vec, threads);
+
+                assert_eq!(rope, vec.as_slice());
+                rope.assert_integrity();
+                rope.assert_invariants();
+            }
+            assert_eq!(Rope::from_slice_parallel(&vec), vec.as_slice());
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn parallel_finish_01() {
+        for n in [0, 1, 100, 10_000, 100_000] {
+            let vec = lorem_ipsum(n);
+            for head in [0, 1, 7, 100] {
+                let (head, rest) = vec.split_at(head.min(n));
+                for threads in [1, 2, 3, 8] {
+                    let mut builder = RopeBuilder::new();
+                    builder.append_slice(head);
+                    let rope: Rope<Lipsum> = builder.finish_on_threads(rest, threads);
+
+                    assert_eq!(rope, vec.as_slice());
+                    rope.assert_integrity();
+                    rope.assert_invariants();
+                }
+            }
+            assert_eq!(RopeBuilder::new().parallel_finish(&vec), vec.as_slice());
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn par_map_reduce_01() {
+        for n in [0, 1, 100, 10_000, 100_000] {
+            let vec = lorem_ipsum(n);
+            let rope = Rope::from_slice(&vec);
+
+            // Concatenation is associative, but not commutative.
+            let concat = |mut left: Vec<Lipsum>, right| {
+                left.extend(right);
+                left
+            };
+            for threads in [1, 2, 3, 8] {
+                let collected = rope.map_reduce_on_threads(threads, <[Lipsum]>::to_vec, concat);
+                assert_eq!(collected.unwrap_or_default(), vec);
+            }
+            let collected = rope.par_map_reduce(<[Lipsum]>::to_vec, concat);
+            assert_eq!(collected.unwrap_or_default(), vec);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn par_for_each_chunk_01() {
+        let vec = lorem_ipsum(100_000);
+        let rope = Rope::from_slice(&vec);
+        let chunks = Mutex::new(Vec::new());
+
+        rope.par_for_each_chunk(|chunk| chunks.lock().unwrap().push(chunk.to_vec()));
+
+        let mut chunks = chunks.into_inner().unwrap();
+        let mut expected: Vec<Vec<Lipsum>> = rope.chunks().map(<[Lipsum]>::to_vec).collect();
+        chunks.sort();
+        expected.sort();
+        assert_eq!(chunks, expected);
+    }
+}
@@ -0,0 +1,580 @@
+use std::ops::Range;
+
+use crate::config::RopeConfig;
+use crate::edit::EditMap;
+use crate::rope::{Measurable, Rope};
+use crate::{Error, Result};
+
+/// A single change in a [Patch<M>], replacing a range of elements in the old
+/// [Rope<M>] with new ones.
+///
+/// Ranges are given both in indices and in widths, for the old [Rope<M>]
+/// and for the new one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Hunk<M>
+where
+    M: Measurable,
+{
+    /// The index range of the removed elements, in the old [Rope<M>].
+    pub old_range: Range<usize>,
+    /// The width range of the removed elements, in the old [Rope<M>].
+    pub old_width_range: Range<usize>,
+    /// The index range of the inserted elements, in the new [Rope<M>].
+    pub new_range: Range<usize>,
+    /// The width range of the inserted elements, in the new [Rope<M>].
+    pub new_width_range: Range<usize>,
+    /// The inserted elements.
+    pub elements: Vec<M>,
+}
+
+/// What a [Hunk<M>] does, as returned by [Hunk::kind()].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HunkKind {
+    /// Only inserts elements.
+    Insert,
+    /// Only removes elements.
+    Delete,
+    /// Removes elements and inserts others in their place.
+    Replace,
+}
+
+impl<M> Hunk<M>
+where
+    M: Measurable,
+{
+    /// Returns whether the [Hunk<M>] inserts, deletes, or replaces elements.
+    #[inline]
+    pub fn kind(&self) -> HunkKind {
+        match (self.old_range.is_empty(), self.new_range.is_empty()) {
+            (true, _) => HunkKind::Insert,
+            (false, true) => HunkKind::Delete,
+            (false, false) => HunkKind::Replace,
+        }
+    }
+}
+
+/// A list of [Hunk<M>]s that turns one [Rope<M>] into another, returned by
+/// [diff()][Rope::diff], and applied by [apply_patch()][Rope::apply_patch].
+///
+/// The [Hunk<M>]s are sorted, and don't overlap or touch each other.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Patch<M>
+where
+    M: Measurable,
+{
+    hunks: Vec<Hunk<M>>,
+}
+
+impl<M> Patch<M>
+where
+    M: Measurable,
+{
+    /// The [Hunk<M>]s of the [Patch<M>], in order.
+    #[inline]
+    pub fn hunks(&self) -> &[Hunk<M>] {
+        &self.hunks
+    }
+
+    /// Consumes the [Patch<M>], returning its [Hunk<M>]s.
+    #[inline]
+    pub fn into_hunks(self) -> Vec<Hunk<M>> {
+        self.hunks
+    }
+
+    /// The amount of [Hunk<M>]s in the [Patch<M>].
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.hunks.len()
+    }
+
+    /// Returns `true` if the [Patch<M>] changes nothing.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.hunks.is_empty()
+    }
+}
+
+impl<M> From<Vec<Hunk<M>>> for Patch<M>
+where
+    M: Measurable,
+{
+    /// Creates a [Patch<M>] from [Hunk<M>]s, for example, ones received from
+    /// another process.
+    ///
+    /// The [Hunk<M>]s are not checked until the [Patch<M>] is applied.
+    #[inline]
+    fn from(hunks: Vec<Hunk<M>>) -> Self {
+        Self { hunks }
+    }
+}
+
+impl<M, C> Rope<M, C>
+where
+    M: Measurable + PartialEq,
+    C: RopeConfig,
+{
+    /// Finds a minimal [Patch<M>] that turns this [Rope<M>] into `new`.
+    ///
+    /// Unlike [changed_ranges()][Rope::changed_ranges], this doesn't depend
+    /// on the [Rope<M>]s sharing any of their trees, and the result has the
+    /// least amount of inserted and removed elements.
+    ///
+    /// Runs in O(N + (N' + M') D) time, and uses O(N' + M') memory, where N'
+    /// and M' are the lengths of both [Rope<M>]s after trimming their common
+    /// prefix and suffix, and D is the amount of inserted and removed
+    /// elements.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::{HunkKind, Rope};
+    /// # use any_rope::Lipsum::*;
+    /// let old = Rope::from_slice(&[Lorem, Ipsum, Dolor(3), Sit, Amet]);
+    /// let new = Rope::from_slice(&[Lorem, Dolor(3), Dolor(2), Sit]);
+    ///
+    /// let patch = old.diff(&new);
+    /// let kinds: Vec<HunkKind> = patch.hunks().iter().map(|hunk| hunk.kind()).collect();
+    /// assert_eq!(
+    ///     kinds,
+    ///     [HunkKind::Delete, HunkKind::Insert, HunkKind::Delete]
+    /// );
+    ///
+    /// let mut rope = old.clone();
+    /// rope.apply_patch(&patch);
+    /// assert_eq!(rope, new);
+    /// ```
+    pub fn diff(&self, new: &Self) -> Patch<M> {
+        let (old_slice, new_slice) = (self.width_slice(..), new.width_slice(..));
+        let prefix = old_slice.common_prefix_len(&new_slice);
+        let suffix = old_slice
+            .index_slice(prefix..)
+            .common_suffix_len(&new_slice.index_slice(prefix..));
+
+        let old_mid = Vec::from(self.index_slice(prefix..self.len() - suffix));
+        let new_mid = Vec::from(new.index_slice(prefix..new.len() - suffix));
+
+        let old_widths = width_sums(self.index_to_width(prefix), &old_mid);
+        let new_widths = width_sums(new.index_to_width(prefix), &new_mid);
+
+        let hunks = myers_diff(&old_mid, &new_mid)
+            .into_iter()
+            .map(|(old_range, new_range)| Hunk {
+                old_range: prefix + old_range.start..prefix + old_range.end,
+                old_width_range: old_widths[old_range.start]..old_widths[old_range.end],
+                new_range: prefix + new_range.start..prefix + new_range.end,
+                new_width_range: new_widths[new_range.start]..new_widths[new_range.end],
+                elements: new_mid[new_range].to_vec(),
+            })
+            .collect();
+
+        Patch { hunks }
+    }
+}
+
+impl<M, C> Rope<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    /// Applies a [Patch<M>] made by [diff()][Rope::diff], returning an
+    /// [EditMap] from positions before the [Patch<M>] to positions after it.
+    ///
+    /// Only the index ranges and the elements of each [Hunk<M>] are used,
+    /// since index ranges can't be ambiguous when elements have a width of
+    /// 0.
+    ///
+    /// Runs in O(K log N) time, where K is the amount of [Hunk<M>]s, plus
+    /// the time to build the inserted elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the [Hunk<M>]s is out of bounds or has a reversed
+    /// range, or if they are not sorted or overlap.
+    #[inline]
+    pub fn apply_patch(&mut self, patch: &Patch<M>) -> EditMap {
+        self.try_apply_patch(patch).unwrap()
+    }
+
+    /// Non-panicking version of [apply_patch()][Rope::apply_patch].
+    pub fn try_apply_patch(&mut self, patch: &Patch<M>) -> Result<EditMap> {
+        let mut last_end = 0;
+        for (i, hunk) in patch.hunks.iter().enumerate() {
+            let Range { start, end } = hunk.old_range;
+            if start > end {
+                return Err(Error::IndexRangeInvalid(start, end));
+            } else if end > self.len() {
+                return Err(Error::IndexRangeOutOfBounds(
+                    Some(start),
+                    Some(end),
+                    self.len(),
+                ));
+            } else if i > 0 && start < last_end {
+                return Err(Error::OverlappingEdits(
+                    patch.hunks[i - 1].old_width_range.start,
+                    hunk.old_width_range.start,
+                ));
+            }
+            last_end = end;
+        }
+
+        Ok(self.splice_index_ranges(patch.hunks.iter().map(|hunk| {
+            (
+                hunk.old_range.start,
+                hunk.old_range.end,
+                hunk.elements.clone(),
+            )
+        })))
+    }
+}
+
+/// The width at the start of each element, and at the end of the last one.
+fn width_sums<M: Measurable>(start: usize, elements: &[M]) -> Vec<usize> {
+    let mut sums = Vec::with_capacity(elements.len() + 1);
+    sums.push(start);
+    let mut width = start;
+    for element in elements {
+        width += element.width();
+        sums.push(width);
+    }
+    sums
+}
+
+/// Finds the ranges that differ between `old` and `new`, using the linear
+/// space variant of Myers' algorithm, in O((N + M) D) time and O(N + M)
+/// space.
+///
+/// Returns the ranges in `old`, and in `new`, of each change, in order.
+fn myers_diff<M: PartialEq>(old: &[M], new: &[M]) -> Vec<(Range<usize>, Range<usize>)> {
+    let offset = old.len() + new.len() + 2;
+    let mut myers = Myers {
+        old,
+        new,
+        offset: offset as isize,
+        forward: vec![0; 2 * offset + 1],
+        backward: vec![0; 2 * offset + 1],
+        changes: Vec::new(),
+    };
+    myers.diff_ranges(0..old.len(), 0..new.len());
+    myers.changes
+}
+
+/// The state of [myers_diff()], shared between its recursive steps.
+struct Myers<'a, M> {
+    old: &'a [M],
+    new: &'a [M],
+    /// The offset of the diagonal `k = 0` in `forward` and `backward`.
+    offset: isize,
+    /// The furthest `x` reached from the start of the ranges on each
+    /// diagonal `k = x - y`.
+    forward: Vec<isize>,
+    /// The furthest `x` reached from the end of the ranges on each diagonal,
+    /// counting backwards.
+    backward: Vec<isize>,
+    changes: Vec<(Range<usize>, Range<usize>)>,
+}
+
+impl<M: PartialEq> Myers<'_, M> {
+    /// Finds the changes between two ranges, by splitting them at a middle
+    /// snake, and diffing both halves.
+    fn diff_ranges(&mut self, mut old: Range<usize>, mut new: Range<usize>) {
+        while !old.is_empty() && !new.is_empty() && self.old[old.start] == self.new[new.start] {
+            old.start += 1;
+            new.start += 1;
+        }
+        while !old.is_empty() && !new.is_empty() && self.old[old.end - 1] == self.new[new.end - 1] {
+            old.end -= 1;
+            new.end -= 1;
+        }
+
+        if old.is_empty() || new.is_empty() {
+            if !old.is_empty() || !new.is_empty() {
+                self.push_change(old, new);
+            }
+            return;
+        }
+
+        // Since both ranges are now non-empty, and start and end with
+        // different elements, the split is strictly inside of them.
+        let (x, y) = self.middle_snake(old.clone(), new.clone());
+        self.diff_ranges(old.start..x, new.start..y);
+        self.diff_ranges(x..old.end, y..new.end);
+    }
+
+    /// Adds a change, merging it with the previous one if they touch.
+    fn push_change(&mut self, old: Range<usize>, new: Range<usize>) {
+        if let Some((last_old, last_new)) = self.changes.last_mut() {
+            if last_old.end == old.start && last_new.end == new.start {
+                last_old.end = old.end;
+                last_new.end = new.end;
+                return;
+            }
+        }
+        self.changes.push((old, new));
+    }
+
+    /// Searches from both ends of the ranges at once, until the paths meet,
+    /// returning the start of the snake where they do.
+    ///
+    /// An optimal edit script goes through that point, with about half of
+    /// its changes on either side.
+    fn middle_snake(&mut self, old: Range<usize>, new: Range<usize>) -> (usize, usize) {
+        let (n, m) = (old.len() as isize, new.len() as isize);
+        let delta = n - m;
+        let odd = delta % 2 != 0;
+        let i = |k: isize| (k + self.offset) as usize;
+
+        self.forward[i(1)] = 0;
+        self.backward[i(1)] = 0;
+
+        for d in 0..=(n + m + 1) / 2 {
+            for k in (-d..=d).step_by(2) {
+                let v = &mut self.forward;
+                let mut x = if k == -d || (k != d && v[i(k - 1)] < v[i(k + 1)]) {
+                    v[i(k + 1)]
+                } else {
+                    v[i(k - 1)] + 1
+                };
+                let (start_x, start_y) = (x, x - k);
+                let mut y = start_y;
+                while x < n
+                    && y < m
+                    && self.old[old.start + x as usize] == self.new[new.start + y as usize]
+                {
+                    x += 1;
+                    y += 1;
+                }
+                v[i(k)] = x;
+
+                if odd && (k - delta).abs() < d && x + self.backward[i(delta - k)] >= n {
+                    return (old.start + start_x as usize, new.start + start_y as usize);
+                }
+            }
+
+            for k in (-d..=d).step_by(2) {
+                let v = &mut self.backward;
+                let mut x = if k == -d || (k != d && v[i(k - 1)] < v[i(k + 1)]) {
+                    v[i(k + 1)]
+                } else {
+                    v[i(k - 1)] + 1
+                };
+                let mut y = x - k;
+                while x < n
+                    && y < m
+                    && self.old[old.end - 1 - x as usize] == self.new[new.end - 1 - y as usize]
+                {
+                    x += 1;
+                    y += 1;
+                }
+                v[i(k)] = x;
+
+                if !odd && (k - delta).abs() <= d && x + self.forward[i(delta - k)] >= n {
+                    return (old.end - x as usize, new.end - y as usize);
+                }
+            }
+        }
+
+        unreachable!("the forward and backward paths always meet")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SmallConfig;
+    use crate::Lipsum::{self, *};
+
+    fn lorem_ipsum() -> Vec<Lipsum> {
+        (0..300)
+            .map(|i| match i % 7 {
+                0 => Lorem,
+                1 => Ipsum,
+                2 => Dolor(i % 4),
+                3 => Sit,
+                4 => Amet,
+                5 => Consectur("adipiscing"),
+                _ => Adipiscing(i % 3 == 0),
+            })
+            .collect()
+    }
+
+    /// The length of a longest common subsequence, found by brute force.
+    fn lcs_len(old: &[Lipsum], new: &[Lipsum]) -> usize {
+        let mut table = vec![vec![0; new.len() + 1]; old.len() + 1];
+        for i in 0..old.len() {
+            for j in 0..new.len() {
+                table[i + 1][j + 1] = if old[i] == new[j] {
+                    table[i][j] + 1
+                } else {
+                    table[i][j + 1].max(table[i + 1][j])
+                };
+            }
+        }
+        table[old.len()][new.len()]
+    }
+
+    /// Checks that the [Patch] turns `old` into `new`, with the least
+    /// amount of changed elements, and with correct ranges.
+    fn check_diff(old: &[Lipsum], new: &[Lipsum]) {
+        let (old_rope, new_rope) = (Rope::from_slice(old), Rope::from_slice(new));
+        let patch = old_rope.diff(&new_rope);
+
+        let mut rope = old_rope.clone();
+        rope.apply_patch(&patch);
+        assert_eq!(rope, new_rope);
+
+        let changed: usize = patch
+            .hunks()
+            .iter()
+            .map(|hunk| hunk.old_range.len() + hunk.new_range.len())
+            .sum();
+        assert_eq!(changed, old.len() + new.len() - 2 * lcs_len(old, new));
+
+        for pair in patch.hunks().windows(2) {
+            assert!(pair[0].old_range.end < pair[1].old_range.start);
+            assert!(pair[0].new_range.end < pair[1].new_range.start);
+        }
+        for hunk in patch.hunks() {
+            assert_eq!(&new[hunk.new_range.clone()], hunk.elements.as_slice());
+            assert_eq!(
+                hunk.old_width_range,
+                old_rope.index_to_width(hunk.old_range.start)
+                    ..old_rope.index_to_width(hunk.old_range.end)
+            );
+            assert_eq!(
+                hunk.new_width_range,
+                new_rope.index_to_width(hunk.new_range.start)
+                    ..new_rope.index_to_width(hunk.new_range.end)
+            );
+        }
+    }
+
+    #[test]
+    fn diff_01() {
+        let old = lorem_ipsum();
+        check_diff(&old, &old);
+        check_diff(&old, &[]);
+        check_diff(&[], &old);
+        check_diff(&[], &[]);
+        assert!(Rope::from_slice(&old)
+            .diff(&Rope::from_slice(&old))
+            .is_empty());
+    }
+
+    #[test]
+    fn diff_02() {
+        let old = lorem_ipsum();
+        let mut new = old.clone();
+        new.drain(40..45);
+        new.insert(100, Dolor(9));
+        new[150] = Sit;
+        new.splice(200..220, [Lorem, Lorem, Ipsum]);
+        check_diff(&old, &new);
+        check_diff(&new, &old);
+    }
+
+    #[test]
+    fn diff_03() {
+        // Small sequences with many possible edit scripts.
+        let old = [Lorem, Ipsum, Lorem, Sit, Ipsum, Ipsum, Lorem];
+        let new = [Ipsum, Sit, Lorem, Ipsum, Lorem, Sit];
+        check_diff(&old, &new);
+        check_diff(&new, &old);
+        check_diff(&old[..3], &new[3..]);
+
+        let unrelated = [Dolor(1), Dolor(2), Amet];
+        check_diff(&old, &unrelated);
+    }
+
+    #[test]
+    fn diff_04() {
+        // Scattered changes, so that the middle snakes land in many places.
+        let old = lorem_ipsum();
+        let mut new = Vec::new();
+        for (i, &element) in old.iter().enumerate() {
+            match (i * 37) % 11 {
+                0 => {}
+                1 => new.extend([element, Amet]),
+                2 => new.push(Dolor(i)),
+                _ => new.push(element),
+            }
+        }
+        check_diff(&old, &new);
+        check_diff(&new, &old);
+        check_diff(&old[50..], &new[..200]);
+    }
+
+    #[test]
+    fn diff_05() {
+        // Unrelated ropes, where every element is changed.
+        let old: Vec<Lipsum> = (0..2000).map(|i| Dolor(i % 5)).collect();
+        let new: Vec<Lipsum> = (0..3000).map(|i| Dolor(5 + i % 3)).collect();
+        check_diff(&old, &new);
+
+        let patch = Rope::from_slice(&old).diff(&Rope::from_slice(&new));
+        assert_eq!(patch.len(), 1);
+        assert_eq!(patch.hunks()[0].kind(), HunkKind::Replace);
+    }
+
+    #[test]
+    fn diff_06() {
+        // Changes made only of 0 width elements, spanning several leaves.
+        check_diff(&[], &[Sit; 1000]);
+        check_diff(&[Amet; 500], &[Lorem]);
+
+        let old = [Lorem, Dolor(3)];
+        let new: Vec<Lipsum> = [Lorem]
+            .into_iter()
+            .chain([Amet, Sit].repeat(20))
+            .chain([Dolor(3)])
+            .collect();
+        check_diff(&old, &new);
+
+        let old = Rope::<Lipsum, SmallConfig>::from_slice_with_config(&[]);
+        let new = Rope::<Lipsum, SmallConfig>::from_slice_with_config(&[Amet, Sit].repeat(4));
+        let mut rope = old.clone();
+        rope.apply_patch(&old.diff(&new));
+        assert_eq!(rope, new);
+    }
+
+    #[test]
+    fn apply_patch_01() {
+        let old = Rope::from_slice(lorem_ipsum().as_slice());
+        let mut new = old.clone();
+        new.insert(30, Dolor(4));
+        let patch = old.diff(&new);
+        assert_eq!(patch.len(), 1);
+        assert_eq!(patch.hunks()[0].kind(), HunkKind::Insert);
+
+        // Patches also apply to unrelated ropes, as long as they are in bounds.
+        let mut rope = Rope::from_slice(&[Lorem; 100]);
+        let map = rope.apply_patch(&patch);
+        assert_eq!(rope.len(), 101);
+        assert_eq!(map.map_index(100), 101);
+
+        let mut rope = Rope::from_slice(&[Lorem; 3]);
+        assert!(rope.try_apply_patch(&patch).is_err());
+        assert_eq!(rope, [Lorem; 3].as_slice());
+    }
+
+    #[test]
+    fn apply_patch_02() {
+        let hunk = |start, end| Hunk {
+            old_range: start..end,
+            old_width_range: start..end,
+            new_range: start..start,
+            new_width_range: start..start,
+            elements: Vec::new(),
+        };
+        let mut rope = Rope::from_slice(&[Lorem; 10]);
+
+        let patch = Patch::from(vec![hunk(2, 5), hunk(4, 6)]);
+        assert!(matches!(
+            rope.try_apply_patch(&patch),
+            Err(Error::OverlappingEdits(2, 4))
+        ));
+
+        let patch = Patch::from(vec![hunk(2, 5), hunk(7, 8)]);
+        rope.apply_patch(&patch);
+        assert_eq!(rope, [Lorem; 6].as_slice());
+    }
+}
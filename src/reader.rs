@@ -0,0 +1,485 @@
+use crate::config::{DefaultConfig, RopeConfig};
+use crate::rope::{Measurable, Rope};
+use crate::tree::{BranchChildren, LeafSlice, Node, SliceInfo};
+use crate::{Error, Result};
+
+/// A cursor for fast reads of nearby positions in a [Rope<M>].
+///
+/// Every read on a [Rope<M>] descends from the root of its tree, which takes
+/// O(log N) time. A [RopeReader<M>] remembers the path to the last leaf that
+/// it visited, and finds the leaves of later reads by climbing up that path
+/// only as far as needed. This makes reads of the same or neighbouring leaves
+/// O(1), and sequential scans amortized O(1) per read, while reads far away
+/// from the last one still take O(log N) time.
+///
+/// The reader borrows its [Rope<M>], so the rope can't change while it is
+/// being read from.
+///
+/// # Examples
+///
+/// ```rust
+/// # use any_rope::Rope;
+/// # use any_rope::Lipsum::*;
+/// let rope = Rope::from_slice(&[Lorem, Ipsum, Dolor(3), Sit, Amet].repeat(100));
+/// let mut reader = rope.reader();
+///
+/// for index in 0..rope.len() {
+///     assert_eq!(reader.from_index(index), rope.from_index(index));
+/// }
+/// assert_eq!(reader.from_width(300), rope.from_width(300));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RopeReader<'a, M, C = DefaultConfig>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    rope: &'a Rope<M, C>,
+    /// The nodes from the root to the last visited leaf, alongside the
+    /// [SliceInfo]s at their start and end.
+    path: Vec<(&'a Node<M, C>, SliceInfo, SliceInfo)>,
+}
+
+impl<'a, M, C> RopeReader<'a, M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    /// Creates a new [RopeReader<M>] for the given [Rope<M>].
+    ///
+    /// Runs in O(1) time.
+    #[inline]
+    pub fn new(rope: &'a Rope<M, C>) -> Self {
+        let end = rope.root.slice_info();
+        Self {
+            rope,
+            path: vec![(&*rope.root, SliceInfo::new(), end)],
+        }
+    }
+
+    /// The [Rope<M>] being read.
+    #[inline]
+    pub fn rope(&self) -> &'a Rope<M, C> {
+        self.rope
+    }
+
+    //-----------------------------------------------------------------------
+    // Index conversion methods
+
+    /// Returns the width sum at the start of the given index.
+    ///
+    /// Same as [Rope::index_to_width()], but runs in O(1) time if the last
+    /// read was nearby.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `index` is out of bounds (i.e. `index > Rope::len()`).
+    #[inline]
+    pub fn index_to_width(&mut self, index: usize) -> usize {
+        self.try_index_to_width(index).unwrap()
+    }
+
+    /// Returns an index, given a starting width sum.
+    ///
+    /// Same as [Rope::start_width_to_index()], but runs in O(1) time if the
+    /// last read was nearby.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `width` is out of bounds (i.e. `width > Rope::width()`).
+    #[inline]
+    pub fn start_width_to_index(&mut self, width: usize) -> usize {
+        self.try_start_width_to_index(width).unwrap()
+    }
+
+    /// Returns an index, given an ending width sum.
+    ///
+    /// Same as [Rope::end_width_to_index()], but runs in O(1) time if the
+    /// last read was nearby.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `width` is out of bounds (i.e. `width > Rope::width()`).
+    #[inline]
+    pub fn end_width_to_index(&mut self, width: usize) -> usize {
+        self.try_end_width_to_index(width).unwrap()
+    }
+
+    //-----------------------------------------------------------------------
+    // Fetch methods
+
+    /// Returns the [M][Measurable] at `index` and the starting width sum of
+    /// that element.
+    ///
+    /// Same as [Rope::from_index()], but runs in O(1) time if the last read
+    /// was nearby.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `index` is out of bounds (i.e. `index >= Rope::len()`).
+    #[inline]
+    pub fn from_index(&mut self, index: usize) -> (usize, M) {
+        if let Some(out) = self.get_from_index(index) {
+            out
+        } else {
+            panic!(
+                "Attempt to index past end of Rope: index {}, Rope length {}",
+                index,
+                self.len()
+            );
+        }
+    }
+
+    /// Returns the [M][Measurable] at `width` and the starting width sum of
+    /// that element.
+    ///
+    /// Same as [Rope::from_width()], but runs in O(1) time if the last read
+    /// was nearby.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `width` is out of bounds (i.e. `width >= Rope::width()`).
+    #[inline]
+    pub fn from_width(&mut self, width: usize) -> (usize, M) {
+        if let Some(out) = self.get_from_width(width) {
+            out
+        } else {
+            panic!(
+                "Attempt to index past end of Rope: char index {}, Rope char length {}",
+                width,
+                self.width()
+            );
+        }
+    }
+
+    /// Returns the chunk containing the given index.
+    ///
+    /// Same as [Rope::chunk_at_index()], but runs in O(1) time if the last
+    /// read was nearby.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `index` is out of bounds (i.e. `index > Rope::len()`).
+    #[inline]
+    pub fn chunk_at_index(&mut self, index: usize) -> (&'a [M], usize, usize) {
+        if let Some(out) = self.get_chunk_at_index(index) {
+            out
+        } else {
+            panic!(
+                "Attempt to index past end of Rope: index {}, Rope length {}",
+                index,
+                self.len()
+            );
+        }
+    }
+
+    /// Returns the chunk containing the given width.
+    ///
+    /// Same as [Rope::chunk_at_width()], but runs in O(1) time if the last
+    /// read was nearby.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `width` is out of bounds (i.e. `width > Rope::width()`).
+    #[inline]
+    pub fn chunk_at_width(&mut self, width: usize) -> (&'a [M], usize, usize) {
+        if let Some(out) = self.get_chunk_at_width(width) {
+            out
+        } else {
+            panic!(
+                "Attempt to index past end of Rope: char index {}, Rope char length {}",
+                width,
+                self.width()
+            );
+        }
+    }
+
+    //-----------------------------------------------------------------------
+    // Internal utilities
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.path[0].2.len as usize
+    }
+
+    #[inline(always)]
+    fn width(&self) -> usize {
+        self.path[0].2.width as usize
+    }
+
+    /// Same as [Node::get_chunk_at_index()], but starting from the last
+    /// visited leaf.
+    #[inline]
+    fn leaf_at_index(&mut self, index: usize) -> (&'a LeafSlice<M, C>, SliceInfo) {
+        self.leaf_by(
+            |start, end| start.len as usize <= index && index < end.len as usize,
+            |children, start| children.search_index(index - start.len as usize),
+        )
+    }
+
+    /// Same as [Node::get_first_chunk_at_width()], but starting from the last
+    /// visited leaf.
+    #[inline]
+    fn leaf_at_width(&mut self, width: usize) -> (&'a LeafSlice<M, C>, SliceInfo) {
+        self.leaf_by(
+            |start, end| (start.width as usize) < width && width < end.width as usize,
+            |children, start| children.search_start_width(width - start.width as usize),
+        )
+    }
+
+    /// Same as [Node::get_last_chunk_at_width()], but starting from the last
+    /// visited leaf.
+    #[inline]
+    fn last_leaf_at_width(&mut self, width: usize) -> (&'a LeafSlice<M, C>, SliceInfo) {
+        self.leaf_by(
+            |start, end| start.width as usize <= width && width < end.width as usize,
+            |children, start| children.search_end_width(width - start.width as usize),
+        )
+    }
+
+    /// Returns the leaf at the end of the search, and the [SliceInfo] at its
+    /// start.
+    ///
+    /// `inside` tells if a node, given the [SliceInfo]s at its start and end,
+    /// strictly contains the searched position, so that a search from the
+    /// root would have to go through it. `search` finds the child of a
+    /// branch that contains the position, given the [SliceInfo] at the start
+    /// of the branch.
+    fn leaf_by<I, S>(&mut self, inside: I, search: S) -> (&'a LeafSlice<M, C>, SliceInfo)
+    where
+        I: Fn(SliceInfo, SliceInfo) -> bool,
+        S: Fn(&'a BranchChildren<M, C>, SliceInfo) -> (usize, SliceInfo),
+    {
+        // Climb up to the closest node that contains the position.
+        while self.path.len() > 1 {
+            let (_, start, end) = self.path[self.path.len() - 1];
+            if inside(start, end) {
+                break;
+            }
+            self.path.pop();
+        }
+
+        // And then descend from it, the same way a search from the root would.
+        loop {
+            let (node, start, _) = self.path[self.path.len() - 1];
+            match *node {
+                Node::Leaf(ref slice) => return (slice, start),
+                Node::Branch(ref children) => {
+                    let (child_i, acc_info) = search(children, start);
+                    let child_start = start + acc_info;
+                    let child_end = child_start + children.info()[child_i].0;
+                    self.path
+                        .push((&*children.nodes()[child_i], child_start, child_end));
+                }
+            }
+        }
+    }
+}
+
+/// # Non-Panicking
+///
+/// The methods in this impl block provide non-panicking versions of
+/// [RopeReader<M>]'s panicking methods. They return either `Option::None` or
+/// `Result::Err()` when their panicking counterparts would have panicked.
+impl<'a, M, C> RopeReader<'a, M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    /// Non-panicking version of [index_to_width()][RopeReader::index_to_width].
+    #[inline]
+    pub fn try_index_to_width(&mut self, index: usize) -> Result<usize> {
+        // Bounds check
+        if index <= self.len() {
+            let (chunk, info) = self.leaf_at_index(index);
+            let width = chunk.index_to_width(index - info.len as usize);
+            Ok(info.width as usize + width)
+        } else {
+            Err(Error::IndexOutOfBounds(index, self.len()))
+        }
+    }
+
+    /// Non-panicking version of
+    /// [start_width_to_index()][RopeReader::start_width_to_index].
+    #[inline]
+    pub fn try_start_width_to_index(&mut self, width: usize) -> Result<usize> {
+        // Bounds check
+        if width <= self.width() {
+            let (chunk, info) = self.leaf_at_width(width);
+            let index = chunk.start_width_to_index(width - info.width as usize);
+            Ok(info.len as usize + index)
+        } else {
+            Err(Error::WidthOutOfBounds(width, self.width()))
+        }
+    }
+
+    /// Non-panicking version of
+    /// [end_width_to_index()][RopeReader::end_width_to_index].
+    #[inline]
+    pub fn try_end_width_to_index(&mut self, width: usize) -> Result<usize> {
+        // Bounds check
+        if width <= self.width() {
+            let (chunk, info) = self.last_leaf_at_width(width);
+            let index = chunk.end_width_to_index(width - info.width as usize);
+            Ok(info.len as usize + index)
+        } else {
+            Err(Error::WidthOutOfBounds(width, self.width()))
+        }
+    }
+
+    /// Non-panicking version of [from_index()][RopeReader::from_index].
+    #[inline]
+    pub fn get_from_index(&mut self, index: usize) -> Option<(usize, M)> {
+        // Bounds check
+        if index < self.len() {
+            let (chunk, info) = self.leaf_at_index(index);
+            let chunk_rel_index = index - info.len as usize;
+            let width = chunk.index_to_width(chunk_rel_index);
+            Some((width + info.width as usize, chunk[chunk_rel_index]))
+        } else {
+            None
+        }
+    }
+
+    /// Non-panicking version of [from_width()][RopeReader::from_width].
+    #[inline]
+    pub fn get_from_width(&mut self, width: usize) -> Option<(usize, M)> {
+        // Bounds check
+        if width < self.width() {
+            let (chunk, info) = self.leaf_at_width(width);
+            let index = chunk.start_width_to_index(width - info.width as usize);
+            let width = chunk.index_to_width(index);
+            Some((width + info.width as usize, chunk[index]))
+        } else {
+            None
+        }
+    }
+
+    /// Non-panicking version of [chunk_at_index()][RopeReader::chunk_at_index].
+    #[inline]
+    pub fn get_chunk_at_index(&mut self, index: usize) -> Option<(&'a [M], usize, usize)> {
+        // Bounds check
+        if index <= self.len() {
+            let (chunk, info) = self.leaf_at_index(index);
+            Some((chunk, info.len as usize, info.width as usize))
+        } else {
+            None
+        }
+    }
+
+    /// Non-panicking version of [chunk_at_width()][RopeReader::chunk_at_width].
+    #[inline]
+    pub fn get_chunk_at_width(&mut self, width: usize) -> Option<(&'a [M], usize, usize)> {
+        // Bounds check
+        if width <= self.width() {
+            let (chunk, info) = self.leaf_at_width(width);
+            Some((chunk, info.len as usize, info.width as usize))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CachedConfig;
+    use crate::Lipsum::{self, *};
+
+    /// 700 elements, with some of them being 0 width.
+    fn lorem_ipsum() -> Vec<Lipsum> {
+        [
+            Lorem,
+            Ipsum,
+            Dolor(4),
+            Sit,
+            Amet,
+            Consectur("hello"),
+            Adipiscing(true),
+            Lorem,
+            Ipsum,
+            Dolor(0),
+            Sit,
+            Amet,
+            Consectur("bye"),
+            Adipiscing(false),
+        ]
+        .repeat(50)
+    }
+
+    fn compare<C: RopeConfig>(rope: &Rope<Lipsum, C>, widths: impl Iterator<Item = usize>) {
+        let mut reader = rope.reader();
+        for width in widths {
+            assert_eq!(reader.get_from_width(width), rope.get_from_width(width));
+            assert_eq!(
+                reader.get_chunk_at_width(width),
+                rope.get_chunk_at_width(width)
+            );
+
+            assert_eq!(
+                reader.try_start_width_to_index(width).ok(),
+                rope.try_start_width_to_index(width).ok()
+            );
+            assert_eq!(
+                reader.try_end_width_to_index(width).ok(),
+                rope.try_end_width_to_index(width).ok()
+            );
+
+            let index = width.min(rope.len() + 1);
+            assert_eq!(
+                reader.try_index_to_width(index).ok(),
+                rope.try_index_to_width(index).ok()
+            );
+            assert_eq!(reader.get_from_index(index), rope.get_from_index(index));
+            assert_eq!(
+                reader.get_chunk_at_index(index),
+                rope.get_chunk_at_index(index)
+            );
+        }
+    }
+
+    #[test]
+    fn sequential_01() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        compare(&rope, 0..rope.width() + 2);
+        compare(&rope, (0..rope.width() + 2).rev());
+    }
+
+    #[test]
+    fn scattered_01() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let width = rope.width();
+        compare(&rope, (0..width).map(|i| (i * 37) % (width + 1)));
+    }
+
+    #[test]
+    fn cached_01() {
+        let rope = Rope::<_, CachedConfig>::from_slice_with_config(lorem_ipsum().as_slice());
+        compare(&rope, 0..rope.width() + 2);
+        let width = rope.width();
+        compare(&rope, (0..width).map(|i| (i * 53) % (width + 1)));
+    }
+
+    #[test]
+    fn empty_01() {
+        let rope: Rope<Lipsum> = Rope::new();
+        let mut reader = rope.reader();
+        assert_eq!(reader.get_from_index(0), None);
+        assert_eq!(reader.get_from_width(0), None);
+        assert_eq!(reader.chunk_at_width(0), (&[][..], 0, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_index_01() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        rope.reader().from_index(rope.len());
+    }
+
+    #[test]
+    fn send_sync_01() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<RopeReader<Lipsum>>();
+        assert_send_sync::<Rope<Lipsum>>();
+    }
+}
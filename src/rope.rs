@@ -1,13 +1,15 @@
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 use std::iter::FromIterator;
 use std::ops::RangeBounds;
 use std::sync::Arc;
 
-use crate::iter::{Chunks, Iter};
+use crate::config::{DefaultConfig, RopeConfig};
+use crate::edit::{Edit, EditMap};
+use crate::iter::{Chunks, IntoIter, Iter, OwnedChunks};
+use crate::reader::RopeReader;
 use crate::rope_builder::RopeBuilder;
-use crate::slice::RopeSlice;
-use crate::slice_utils::{start_width_to_index, index_to_width, end_width_to_index};
-use crate::tree::{BranchChildren, Node, SliceInfo, MAX_LEN, MIN_LEN};
+use crate::slice::{OwnedRopeSlice, RopeSlice};
+use crate::tree::{max_len, min_len, BranchChildren, LeafSlice, Node, SliceInfo};
 use crate::{end_bound_to_num, start_bound_to_num, Error, Result};
 
 /// A object that has a definite size, that can be interpreted by a [Rope<M>].
@@ -96,11 +98,12 @@ pub trait Measurable: Clone + Copy {
 /// The primary intended use-case for this feature is to allow asynchronous
 /// processing of [Rope<M>]s.
 #[derive(Clone)]
-pub struct Rope<M>
+pub struct Rope<M, C = DefaultConfig>
 where
     M: Measurable,
+    C: RopeConfig,
 {
-    pub(crate) root: Arc<Node<M>>,
+    pub(crate) root: Arc<Node<M, C>>,
 }
 
 impl<M> Rope<M>
@@ -113,9 +116,7 @@ where
     /// Creates an empty [Rope<M>].
     #[inline]
     pub fn new() -> Self {
-        Rope {
-            root: Arc::new(Node::new()),
-        }
+        Self::new_with_config()
     }
 
     /// Creates a [Rope<M>] from an [M][Measurable] slice.
@@ -124,7 +125,74 @@ where
     #[inline]
     #[allow(clippy::should_implement_trait)]
     pub fn from_slice(slice: &[M]) -> Self {
-        RopeBuilder::new().build_at_once(slice)
+        Self::from_slice_with_config(slice)
+    }
+}
+
+impl<M, C> Rope<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    /// Creates an empty [Rope<M, C>], with a given [RopeConfig].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::{Rope, SmallConfig};
+    /// # use any_rope::Lipsum;
+    /// let rope = Rope::<Lipsum, SmallConfig>::new_with_config();
+    /// assert!(rope.is_empty());
+    /// ```
+    #[inline]
+    pub fn new_with_config() -> Self {
+        Rope {
+            root: Arc::new(Node::new()),
+        }
+    }
+
+    /// Creates a [Rope<M, C>] from an [M][Measurable] slice, with a given
+    /// [RopeConfig].
+    ///
+    /// Runs in O(N) time.
+    #[inline]
+    pub fn from_slice_with_config(slice: &[M]) -> Self {
+        RopeBuilder::new_with_config().build_at_once(slice)
+    }
+
+    /// Creates a [Rope<M, C>] from an iterator of [M][Measurable]s, with a
+    /// given [RopeConfig].
+    ///
+    /// This is what [FromIterator] does for [Rope<M>], for other configs.
+    ///
+    /// Runs in O(N) time.
+    pub fn from_iter_with_config<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = M>,
+    {
+        let mut builder = RopeBuilder::new_with_config();
+        for measurable in iter {
+            builder.append(measurable);
+        }
+        builder.finish()
+    }
+
+    /// Creates a [Rope<M, C>] from an iterator of chunks, such as
+    /// [`&[M]`][Measurable] slices or [Vec<M>]s, with a given [RopeConfig].
+    ///
+    /// This is what [FromIterator] does for [Rope<M>], for other configs.
+    ///
+    /// Runs in O(N) time.
+    pub fn from_chunks_with_config<I, S>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<[M]>,
+    {
+        let mut builder = RopeBuilder::new_with_config();
+        for chunk in iter {
+            builder.append_slice(chunk.as_ref());
+        }
+        builder.finish()
     }
 
     //-----------------------------------------------------------------------
@@ -146,6 +214,17 @@ where
         self.root.width()
     }
 
+    /// Returns `true` if the [Rope<M>] has no elements.
+    ///
+    /// Note that a [Rope<M>] with a [width()][Rope::width] of 0 is not
+    /// necessarily empty, as it may contain 0 width elements.
+    ///
+    /// Runs in O(1) time.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     //-----------------------------------------------------------------------
     // Memory management methods
 
@@ -159,7 +238,7 @@ where
     pub fn capacity(&self) -> usize {
         let mut count = 0;
         for chunk in self.chunks() {
-            count += chunk.len().max(MAX_LEN);
+            count += chunk.len().max(max_len::<M, C>());
         }
         count
     }
@@ -183,10 +262,10 @@ where
     /// shrinking.
     pub fn shrink_to_fit(&mut self) {
         let mut node_stack = Vec::new();
-        let mut builder = RopeBuilder::new();
+        let mut builder = RopeBuilder::new_with_config();
 
         node_stack.push(self.root.clone());
-        *self = Rope::new();
+        *self = Rope::new_with_config();
 
         loop {
             if node_stack.is_empty() {
@@ -208,10 +287,54 @@ where
         *self = builder.finish();
     }
 
+    /// Packs the leaves in `width_range` into as few leaves as possible.
+    ///
+    /// Long editing sessions can leave the tree full of half empty leaves,
+    /// which waste memory and slow down traversal. Unlike
+    /// [shrink_to_fit()][Rope::shrink_to_fit], this only rebuilds the parts
+    /// of the tree around the given range, so it can be done in small steps,
+    /// and everything outside of those parts is still shared with any clones
+    /// of the [Rope<M>]. [fragmentation()][Rope::fragmentation] can be used
+    /// to find out when it's worth doing.
+    ///
+    /// Leaves that share a parent node with the leaves in the range are also
+    /// packed.
+    ///
+    /// Runs in O(M + log N) time, where M is the length of the range.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let mut rope = Rope::from_slice(&[Lorem, Ipsum, Dolor(3)].repeat(10_000));
+    /// for width in (0..rope.width()).rev().step_by(12) {
+    ///     rope.remove(width..width + 1);
+    /// }
+    /// let before = rope.fragmentation();
+    ///
+    /// rope.compact_range(..);
+    ///
+    /// assert!(rope.fragmentation().leaves < before.leaves);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end, or if the
+    /// end is out of bounds (i.e. `end > Rope::width()`).
+    #[inline]
+    pub fn compact_range<R>(&mut self, width_range: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        self.try_compact_range(width_range).unwrap()
+    }
+
     //-----------------------------------------------------------------------
     // Edit methods
 
-    /// Inserts [`slice`][Measurable] at `width`.
+    /// Inserts [`slice`][Measurable] at `width`, after any elements with a
+    /// width of 0 there.
     ///
     /// Runs in O(L + log N) time, where N is the length of the [Rope<M>] and L
     /// is the length of [`slice`][Measurable].
@@ -225,7 +348,8 @@ where
         self.try_insert_slice(width, slice).unwrap()
     }
 
-    /// Inserts a single [M][Measurable] at `width`.
+    /// Inserts a single [M][Measurable] at `width`, after any elements with a
+    /// width of 0 there.
     ///
     /// Runs in O(log N) time.
     ///
@@ -238,51 +362,33 @@ where
     }
 
     /// Private internal-only method that does a single insertion of
-    /// a sufficiently small slice.
+    /// a sufficiently small slice, right before the element at `index`.
     ///
     /// This only works correctly for insertion slices smaller than or equal to
     /// `MAX_BYTES - 4`.
-    fn insert_internal(&mut self, width: usize, ins_slice: &[M]) {
+    fn insert_internal_at_index(&mut self, index: usize, ins_slice: &[M]) {
         let root_info = self.root.slice_info();
 
-        let (l_info, residual) = Arc::make_mut(&mut self.root).edit_chunk_at_width(
-            width,
+        let (l_info, residual) = Arc::make_mut(&mut self.root).edit_chunk_at_index(
+            index,
             root_info,
-            |index, cur_info, leaf_slice| {
-                // Find our index
-                let index = end_width_to_index(leaf_slice, index);
-
-                // No node splitting
-                if (leaf_slice.len() + ins_slice.len()) <= MAX_LEN {
-                    // Calculate new info without doing a full re-scan of cur_slice.
-                    let new_info = cur_info + SliceInfo::from_slice(ins_slice);
-                    leaf_slice.insert_slice(index, ins_slice);
-                    (new_info, None)
-                }
-                // We're splitting the node
-                else {
-                    let r_slice = leaf_slice.insert_slice_split(index, ins_slice);
-                    let l_slice_info = SliceInfo::from_slice(leaf_slice);
-                    if r_slice.len() > 0 {
-                        let r_slice_info = SliceInfo::from_slice(&r_slice);
-                        (
-                            l_slice_info,
-                            Some((r_slice_info, Arc::new(Node::Leaf(r_slice)))),
-                        )
-                    } else {
-                        // Leaf couldn't be validly split, so leave it oversized
-                        (l_slice_info, None)
-                    }
-                }
-            },
+            |index, cur_info, leaf_slice| insert_into_leaf(leaf_slice, index, cur_info, ins_slice),
         );
 
-        // Handle root splitting, if any.
+        self.handle_root_split(l_info, residual);
+    }
+
+    /// Handles root splitting after an edit, if any.
+    fn handle_root_split(
+        &mut self,
+        l_info: SliceInfo,
+        residual: Option<(SliceInfo, Arc<Node<M, C>>)>,
+    ) {
         if let Some((r_info, r_node)) = residual {
             let mut l_node = Arc::new(Node::new());
             std::mem::swap(&mut l_node, &mut self.root);
 
-            let mut children = BranchChildren::new();
+            let mut children = BranchChildren::new_with_config();
             children.push((l_info, l_node));
             children.push((r_info, r_node));
 
@@ -363,6 +469,151 @@ where
         self.try_remove(width_range).unwrap()
     }
 
+    /// Removes the slice in the given width range, returning it as a new
+    /// [Rope<M>].
+    ///
+    /// Uses range syntax, e.g. `2..7`, `2..`, etc.
+    ///
+    /// The removed elements are exactly the ones that
+    /// [remove()][Rope::remove] would remove. Unlike
+    /// [remove()][Rope::remove], nothing is discarded or copied, the nodes
+    /// covered by the range are detached from this [Rope<M>] and handed over
+    /// to the returned one.
+    ///
+    /// Runs in O(log N) time.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let mut rope = Rope::from_slice(
+    ///     &[Lorem, Ipsum, Dolor(3), Sit, Amet, Consectur("hi"), Adipiscing(true)]
+    /// );
+    /// let cut = rope.cut(1..6);
+    ///
+    /// assert_eq!(rope, [Lorem, Consectur("hi"), Adipiscing(true)].as_slice());
+    /// assert_eq!(cut, [Ipsum, Dolor(3), Sit, Amet].as_slice());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end, or if the
+    /// end is out of bounds (i.e. `end > self.width()`).
+    #[inline]
+    pub fn cut<R>(&mut self, width_range: R) -> Self
+    where
+        R: RangeBounds<usize>,
+    {
+        self.try_cut(width_range).unwrap()
+    }
+
+    /// Removes the elements in the given index range, returning them as a new
+    /// [Rope<M>].
+    ///
+    /// Uses range syntax, e.g. `2..7`, `2..`, etc.
+    ///
+    /// Runs in O(log N) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end, or if the
+    /// end is out of bounds (i.e. `end > self.len()`).
+    #[inline]
+    pub fn cut_index<R>(&mut self, index_range: R) -> Self
+    where
+        R: RangeBounds<usize>,
+    {
+        self.try_cut_index(index_range).unwrap()
+    }
+
+    /// Appends a [M][Measurable] to the end of the [Rope<M>].
+    ///
+    /// The element is always placed after every other element, including any
+    /// 0 width elements at the end of the [Rope<M>].
+    ///
+    /// Runs in O(log N) time.
+    #[inline]
+    pub fn push(&mut self, measurable: M) {
+        self.insert_internal_at_index(self.len(), &[measurable]);
+    }
+
+    /// Removes the last [M][Measurable] from the [Rope<M>] and returns it, or
+    /// [None] if the [Rope<M>] is empty.
+    ///
+    /// Runs in O(log N) time.
+    pub fn pop(&mut self) -> Option<M> {
+        let last = self.last()?;
+        self.remove_index_range(self.len() - 1, self.len());
+        Some(last)
+    }
+
+    /// Shortens the [Rope<M>], removing everything from `width` onwards.
+    ///
+    /// The removed elements are the same that [remove()][Rope::remove] would
+    /// remove, given `width..`. If `width` is greater than the
+    /// [Rope<M>]'s width, this has no effect.
+    ///
+    /// Runs in O(log N) time.
+    pub fn truncate_width(&mut self, width: usize) {
+        if let Ok((start, end)) = self.width_range_to_index_range(width..) {
+            self.remove_index_range(start, end);
+        }
+    }
+
+    /// Shortens the [Rope<M>], keeping only the first `index` elements.
+    ///
+    /// If `index` is greater than the [Rope<M>]'s length, this has no effect.
+    ///
+    /// Runs in O(log N) time.
+    pub fn truncate_index(&mut self, index: usize) {
+        if index < self.len() {
+            self.remove_index_range(index, self.len());
+        }
+    }
+
+    /// Removes all elements from the [Rope<M>].
+    ///
+    /// Runs in O(1) time, not counting the dropping of the elements.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.root = Arc::new(Node::new());
+    }
+
+    /// Removes the slice in the given width range, returning an iterator over
+    /// the removed elements.
+    ///
+    /// The removed elements are the same that [remove()][Rope::remove] would
+    /// remove. Unlike [Vec::drain()], the elements are removed right away,
+    /// whether the iterator is consumed or not. The widths yielded by the
+    /// iterator are relative to the start of the removed range.
+    ///
+    /// Runs in O(log N) time.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let mut rope = Rope::from_slice(&[Lorem, Ipsum, Dolor(3), Sit]);
+    /// let drained: Vec<_> = rope.drain(1..3).collect();
+    ///
+    /// assert_eq!(drained, [(0, Ipsum)]);
+    /// assert_eq!(rope, [Lorem, Dolor(3), Sit].as_slice());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end, or if the
+    /// end is out of bounds (i.e. `end > self.width()`).
+    #[inline]
+    pub fn drain<R>(&mut self, width_range: R) -> IntoIter<M, C>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.try_drain(width_range).unwrap()
+    }
+
     /// Splits the [Rope<M>] at `width`, returning the right part of the split.
     ///
     /// Runs in O(log N) time.
@@ -378,10 +629,10 @@ where
     ///
     /// Runs in O(log N) time.
     pub fn append(&mut self, mut other: Self) {
-        if self.width() == 0 {
+        if self.is_empty() {
             // Special case
             std::mem::swap(self, &mut other);
-        } else if other.width() > 0 {
+        } else if !other.is_empty() {
             let left_info = self.root.slice_info();
             let right_info = other.root.slice_info();
 
@@ -392,7 +643,7 @@ where
                 let extra =
                     Arc::make_mut(&mut self.root).append_at_depth(other.root, l_depth - r_depth);
                 if let Some(node) = extra {
-                    let mut children = BranchChildren::new();
+                    let mut children = BranchChildren::new_with_config();
                     children.push((self.root.slice_info(), Arc::clone(&self.root)));
                     children.push((node.slice_info(), node));
                     self.root = Arc::new(Node::Branch(children));
@@ -402,7 +653,7 @@ where
                 let extra = Arc::make_mut(&mut other.root)
                     .prepend_at_depth(Arc::clone(&self.root), r_depth - l_depth);
                 if let Some(node) = extra {
-                    let mut children = BranchChildren::new();
+                    let mut children = BranchChildren::new_with_config();
                     children.push((node.slice_info(), node));
                     children.push((other.root.slice_info(), Arc::clone(&other.root)));
                     other.root = Arc::new(Node::Branch(children));
@@ -412,88 +663,376 @@ where
 
             // Fix up any mess left behind.
             let root = Arc::make_mut(&mut self.root);
-            if (left_info.len as usize) < MIN_LEN || (right_info.len as usize) < MIN_LEN {
-                root.fix_tree_seam(left_info.width as usize);
+            if (left_info.len as usize) < min_len::<M, C>()
+                || (right_info.len as usize) < min_len::<M, C>()
+            {
+                root.fix_tree_seam_at_index(left_info.len as usize);
             }
             self.pull_up_singular_nodes();
         }
     }
 
-    //-----------------------------------------------------------------------
-    // Index conversion methods
-
-    /// Returns the width sum at the start of the given index.
+    /// Concatenates a list of [Rope<M>]s into a single one.
     ///
-    /// Notes:
+    /// The [Rope<M>]s are joined in pairs, then the results of that are
+    /// joined in pairs, and so on. This keeps the ropes being joined at
+    /// similar sizes, so their nodes are spliced together without having to
+    /// fix up deep seams over and over again, as would happen with repeated
+    /// calls to [append()][Rope::append].
     ///
-    /// Runs in O(log N) time.
+    /// Runs in O(K * log N) time, where K is the amount of ropes.
     ///
-    /// # Panics
+    /// # Examples
     ///
-    /// Panics if the `index` is out of bounds (i.e. `index > Rope::len()`).
-    #[inline]
-    pub fn index_to_width(&self, index: usize) -> usize {
-        self.try_index_to_width(index).unwrap()
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let ropes = (0..1000).map(|i| Rope::from_slice(&[Dolor(i), Sit]));
+    /// let rope = Rope::concat(ropes);
+    ///
+    /// assert_eq!(rope.len(), 2000);
+    /// assert_eq!(rope.from_index(2 * 500), (124750, Dolor(500)));
+    /// ```
+    pub fn concat<I>(ropes: I) -> Self
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        let mut ropes: Vec<Self> = ropes.into_iter().filter(|rope| !rope.is_empty()).collect();
+
+        while ropes.len() > 1 {
+            let mut iter = ropes.into_iter();
+            let mut joined = Vec::with_capacity(iter.len().div_ceil(2));
+            while let Some(mut left) = iter.next() {
+                if let Some(right) = iter.next() {
+                    left.append(right);
+                }
+                joined.push(left);
+            }
+            ropes = joined;
+        }
+
+        ropes.pop().unwrap_or_else(Rope::new_with_config)
     }
 
-    /// Returns an index, given a starting width sum.
+    /// Creates a [Rope<M>] made up of `n` copies of this one.
     ///
-    /// Runs in O(log N) time.
+    /// The copy is made by repeatedly doubling the [Rope<M>], so every copy
+    /// shares its nodes with the others. Only the nodes along the seams
+    /// between copies are new, so this takes O(log n) extra memory, no
+    /// matter how large `n` is.
     ///
-    /// # Panics
+    /// Runs in O(log n * log N) time.
     ///
-    /// Panics if the `width` is out of bounds (i.e. `width > Rope::width()`).
-    #[inline]
-    pub fn start_width_to_index(&self, width: usize) -> usize {
-        self.try_start_width_to_index(width).unwrap()
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let rope = Rope::from_slice(&[Lorem, Ipsum, Dolor(3)]);
+    /// let repeated = rope.repeat(1_000_000);
+    ///
+    /// assert_eq!(repeated.len(), 3_000_000);
+    /// assert_eq!(repeated.width(), 6_000_000);
+    /// assert_eq!(repeated.from_width(3_000_004), (3_000_003, Dolor(3)));
+    /// ```
+    pub fn repeat(&self, mut n: usize) -> Self {
+        let mut repeated = Rope::new_with_config();
+        let mut doubled = self.clone();
+
+        while n > 0 {
+            if n & 1 == 1 {
+                repeated.append(doubled.clone());
+            }
+            n >>= 1;
+            if n > 0 {
+                doubled.append(doubled.clone());
+            }
+        }
+
+        repeated
     }
 
-    /// Returns an index, given an ending width sum.
+    /// Inserts a [Rope<M>] at `width`, consuming it.
     ///
-    /// Runs in O(log N) time.
+    /// Unlike [insert_slice()][Rope::insert_slice], this doesn't copy the
+    /// elements of `rope`, its nodes are spliced directly into this
+    /// [Rope<M>]. This makes it a cheap way of pasting large amounts of
+    /// content, especially when `rope` shares its nodes with other ropes.
+    ///
+    /// Just like [insert()][Rope::insert], the [Rope<M>] will be inserted
+    /// after any 0 width elements at `width`.
+    ///
+    /// Runs in O(log N + log M) time, where M is the length of `rope`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let mut rope = Rope::from_slice(&[Lorem, Ipsum, Dolor(3)]);
+    /// let other = Rope::from_slice(&[Consectur("hi"), Adipiscing(true)]);
+    /// rope.insert_rope(3, other);
+    ///
+    /// assert_eq!(
+    ///     rope,
+    ///     [Lorem, Ipsum, Consectur("hi"), Adipiscing(true), Dolor(3)].as_slice()
+    /// );
+    /// ```
     ///
     /// # Panics
     ///
     /// Panics if the `width` is out of bounds (i.e. `width > Rope::width()`).
     #[inline]
-    pub fn end_width_to_index(&self, width: usize) -> usize {
-        self.try_end_width_to_index(width).unwrap()
+    pub fn insert_rope(&mut self, width: usize, rope: Self) {
+        self.try_insert_rope(width, rope).unwrap()
     }
 
-    //-----------------------------------------------------------------------
-    // Fetch methods
-
-    /// Returns the [M][Measurable] at `index` and the starting width sum of
-    /// that element.
+    /// Inserts a [RopeSlice<M>] at `width`.
     ///
-    /// Runs in O(log N) time.
+    /// The nodes that the [RopeSlice<M>] fully covers are shared with the
+    /// [Rope<M>] it came from, instead of being copied.
+    ///
+    /// Just like [insert()][Rope::insert], the [RopeSlice<M>] will be
+    /// inserted after any 0 width elements at `width`.
+    ///
+    /// Runs in O(log N + log M) time, where M is the length of `slice`.
     ///
     /// # Panics
     ///
-    /// Panics if the `index` is out of bounds (i.e. `index > Rope::len()`).
+    /// Panics if the `width` is out of bounds (i.e. `width > Rope::width()`).
     #[inline]
-    pub fn from_index(&self, index: usize) -> (usize, M) {
-        // Bounds check
-        if let Some(out) = self.get_from_index(index) {
-            out
-        } else {
-            panic!(
-                "Attempt to index past end of Rope: index {}, Rope length {}",
-                index,
-                self.len()
-            );
-        }
+    pub fn insert_rope_slice(&mut self, width: usize, slice: RopeSlice<M, C>) {
+        self.try_insert_rope_slice(width, slice).unwrap()
     }
 
-    /// Returns the [M][Measurable] at `width` and the starting width sum of
-    /// that element.
+    /// Copies the elements in `src_range` to `dest_width`.
+    ///
+    /// The copied elements are the same that [remove()][Rope::remove] would
+    /// remove, given `src_range`, and they are placed in the same position
+    /// that [insert()][Rope::insert] would place them, given `dest_width`.
+    /// `dest_width` is relative to the [Rope<M>] before the copy.
+    ///
+    /// The copy shares its nodes with the source range, so this is cheap
+    /// even for very large ranges.
     ///
     /// Runs in O(log N) time.
     ///
-    /// # Panics
+    /// # Examples
     ///
-    /// Panics if the `width` is out of bounds (i.e. `width > Rope::width()`).
-    #[inline]
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let mut rope = Rope::from_slice(&[Lorem, Ipsum, Dolor(3), Sit]);
+    /// rope.copy_range_to(0..3, 3);
+    ///
+    /// assert_eq!(rope, [Lorem, Ipsum, Lorem, Ipsum, Dolor(3), Sit].as_slice());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end, or if either
+    /// the end of the range or `dest_width` are out of bounds (i.e.
+    /// `end > self.width()`).
+    #[inline]
+    pub fn copy_range_to<R>(&mut self, src_range: R, dest_width: usize)
+    where
+        R: RangeBounds<usize>,
+    {
+        self.try_copy_range_to(src_range, dest_width).unwrap()
+    }
+
+    /// Moves the elements in `src_range` to `dest_width`.
+    ///
+    /// The moved elements are the same that [remove()][Rope::remove] would
+    /// remove, given `src_range`, and they are placed in the same position
+    /// that [insert()][Rope::insert] would place them, given `dest_width`.
+    /// `dest_width` is relative to the [Rope<M>] before the move. If it
+    /// falls inside of `src_range`, the [Rope<M>] is left unchanged.
+    ///
+    /// No elements are copied, the nodes in between the two positions are
+    /// just reattached in a different order.
+    ///
+    /// Runs in O(log N) time.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let mut rope = Rope::from_slice(&[Lorem, Ipsum, Dolor(3), Sit]);
+    /// // Moved after the 0 width `Sit`.
+    /// rope.move_range(0..1, 6);
+    ///
+    /// assert_eq!(rope, [Ipsum, Dolor(3), Sit, Lorem].as_slice());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end, or if either
+    /// the end of the range or `dest_width` are out of bounds (i.e.
+    /// `end > self.width()`).
+    #[inline]
+    pub fn move_range<R>(&mut self, src_range: R, dest_width: usize)
+    where
+        R: RangeBounds<usize>,
+    {
+        self.try_move_range(src_range, dest_width).unwrap()
+    }
+
+    /// Applies a list of [Edit<M>]s, returning a mapping from positions
+    /// before the edits to positions after them.
+    ///
+    /// All [Edit<M>]s use positions from before any of them are applied, so
+    /// there is no need to adjust them for each other. The [Edit<M>]s may
+    /// come in any order, but they must not overlap. Insertions at the same
+    /// position are applied in the order that they were given.
+    ///
+    /// Only the parts of the tree around each edit are rebuilt, the rest is
+    /// shared with the [Rope<M>] before the edits.
+    ///
+    /// Runs in O(E * log N + M) time, where E is the amount of edits and M
+    /// is the length of all elements being inserted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::{Edit, Rope};
+    /// # use any_rope::Lipsum::*;
+    /// let mut rope = Rope::from_slice(&[Lorem, Ipsum, Dolor(3), Lorem]);
+    /// let map = rope.apply_edits([
+    ///     Edit::Insert(6, vec![Dolor(2), Lorem]),
+    ///     Edit::Remove(0..1),
+    ///     Edit::Replace(3..6, vec![Sit]),
+    /// ]);
+    ///
+    /// assert_eq!(rope, [Ipsum, Sit, Dolor(2), Lorem, Lorem].as_slice());
+    /// // The last `Lorem` was at a width of 6, and now it's at a width of 5.
+    /// assert_eq!(map.map_width(6), 5);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the [Edit<M>]s is out of bounds or has a reversed
+    /// range, or if any two of them overlap.
+    #[inline]
+    pub fn apply_edits<E>(&mut self, edits: E) -> EditMap
+    where
+        E: IntoIterator<Item = Edit<M>>,
+    {
+        self.try_apply_edits(edits).unwrap()
+    }
+
+    //-----------------------------------------------------------------------
+    // Index conversion methods
+
+    /// Returns the width sum at the start of the given index.
+    ///
+    /// Notes:
+    ///
+    /// Runs in O(log N) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `index` is out of bounds (i.e. `index > Rope::len()`).
+    #[inline]
+    pub fn index_to_width(&self, index: usize) -> usize {
+        self.try_index_to_width(index).unwrap()
+    }
+
+    /// Returns an index, given a starting width sum.
+    ///
+    /// Runs in O(log N) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `width` is out of bounds (i.e. `width > Rope::width()`).
+    #[inline]
+    pub fn start_width_to_index(&self, width: usize) -> usize {
+        self.try_start_width_to_index(width).unwrap()
+    }
+
+    /// Returns an index, given an ending width sum.
+    ///
+    /// Runs in O(log N) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `width` is out of bounds (i.e. `width > Rope::width()`).
+    #[inline]
+    pub fn end_width_to_index(&self, width: usize) -> usize {
+        self.try_end_width_to_index(width).unwrap()
+    }
+
+    /// Returns the width sums at the start of each of the given indices.
+    ///
+    /// Same as calling [index_to_width()][Rope::index_to_width] on each
+    /// index, but all of them share a single traversal of the tree.
+    ///
+    /// Runs in O(M log N) time in the worst case, where M is the amount of
+    /// `indices`. Each lookup only climbs the tree as far as needed from the
+    /// last one, so sorted `indices` that are close to each other take
+    /// amortized O(1) time each.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any index is out of bounds (i.e. `index > Rope::len()`).
+    #[inline]
+    pub fn indices_to_widths(&self, indices: &[usize]) -> Vec<usize> {
+        self.try_indices_to_widths(indices).unwrap()
+    }
+
+    /// Returns the indices of each of the given starting width sums.
+    ///
+    /// Same as calling [start_width_to_index()][Rope::start_width_to_index]
+    /// on each width, but all of them share a single traversal of the tree.
+    ///
+    /// Runs in O(M log N) time in the worst case, where M is the amount of
+    /// `widths`. Each lookup only climbs the tree as far as needed from the
+    /// last one, so sorted `widths` that are close to each other take
+    /// amortized O(1) time each.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any width is out of bounds (i.e. `width > Rope::width()`).
+    #[inline]
+    pub fn widths_to_indices(&self, widths: &[usize]) -> Vec<usize> {
+        self.try_widths_to_indices(widths).unwrap()
+    }
+
+    //-----------------------------------------------------------------------
+    // Fetch methods
+
+    /// Returns the [M][Measurable] at `index` and the starting width sum of
+    /// that element.
+    ///
+    /// Runs in O(log N) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `index` is out of bounds (i.e. `index > Rope::len()`).
+    #[inline]
+    pub fn from_index(&self, index: usize) -> (usize, M) {
+        // Bounds check
+        if let Some(out) = self.get_from_index(index) {
+            out
+        } else {
+            panic!(
+                "Attempt to index past end of Rope: index {}, Rope length {}",
+                index,
+                self.len()
+            );
+        }
+    }
+
+    /// Returns the [M][Measurable] at `width` and the starting width sum of
+    /// that element.
+    ///
+    /// Runs in O(log N) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `width` is out of bounds (i.e. `width > Rope::width()`).
+    #[inline]
     pub fn from_width(&self, width: usize) -> (usize, M) {
         if let Some(out) = self.get_from_width(width) {
             out
@@ -506,6 +1045,48 @@ where
         }
     }
 
+    /// Returns the [M][Measurable]s at each of the given widths, and the
+    /// starting width sums of those elements.
+    ///
+    /// Same as calling [from_width()][Rope::from_width] on each width, but
+    /// all of them share a single traversal of the tree.
+    ///
+    /// Runs in O(M log N) time in the worst case, where M is the amount of
+    /// `widths`. Each lookup only climbs the tree as far as needed from the
+    /// last one, so sorted `widths` that are close to each other take
+    /// amortized O(1) time each.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any width is out of bounds (i.e. `width >= Rope::width()`).
+    #[inline]
+    pub fn from_widths(&self, widths: &[usize]) -> Vec<(usize, M)> {
+        let mut reader = self.reader();
+        widths
+            .iter()
+            .map(|&width| reader.from_width(width))
+            .collect()
+    }
+
+    /// Returns the first [M][Measurable] of the [Rope<M>], or [None] if it is
+    /// empty.
+    ///
+    /// Runs in O(log N) time.
+    #[inline]
+    pub fn first(&self) -> Option<M> {
+        self.get_from_index(0).map(|(_, measurable)| measurable)
+    }
+
+    /// Returns the last [M][Measurable] of the [Rope<M>], or [None] if it is
+    /// empty.
+    ///
+    /// Runs in O(log N) time.
+    #[inline]
+    pub fn last(&self) -> Option<M> {
+        let index = self.len().checked_sub(1)?;
+        self.get_from_index(index).map(|(_, measurable)| measurable)
+    }
+
     /// Returns the chunk containing the given index.
     ///
     /// Also returns the index and widht of the beginning of the chunk.
@@ -590,7 +1171,7 @@ where
     /// Panics if the start of the range is greater than the end, or if the
     /// end is out of bounds (i.e. `end > Rope::width()`).
     #[inline]
-    pub fn width_slice<R>(&self, width_range: R) -> RopeSlice<M>
+    pub fn width_slice<R>(&self, width_range: R) -> RopeSlice<M, C>
     where
         R: RangeBounds<usize>,
     {
@@ -608,7 +1189,7 @@ where
     /// Panics if:
     /// - The start of the range is greater than the end.
     /// - The end is out of bounds (i.e. `end > Rope::len()`).
-    pub fn index_slice<R>(&self, index_range: R) -> RopeSlice<M>
+    pub fn index_slice<R>(&self, index_range: R) -> RopeSlice<M, C>
     where
         R: RangeBounds<usize>,
     {
@@ -618,6 +1199,161 @@ where
         }
     }
 
+    /// Creates a new [Rope<M>] out of the given width range.
+    ///
+    /// The new [Rope<M>] shares all of its data with this one, except for the
+    /// chunks at the edges of the range, so this is much cheaper than copying
+    /// the elements over. The range is interpreted in the same way as in
+    /// [width_slice()][Rope::width_slice].
+    ///
+    /// Uses range syntax, e.g. `2..7`, `2..`, etc.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let rope = Rope::from_slice(
+    ///     &[Lorem, Ipsum, Dolor(3), Sit, Amet, Consectur("hi"), Adipiscing(true)]
+    /// );
+    /// let extracted = rope.extract(3..8);
+    ///
+    /// assert_eq!(extracted, [Dolor(3), Sit, Amet, Consectur("hi")].as_slice());
+    /// ```
+    ///
+    /// Runs in O(log N) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end, or if the
+    /// end is out of bounds (i.e. `end > Rope::width()`).
+    #[inline]
+    pub fn extract<R>(&self, width_range: R) -> Self
+    where
+        R: RangeBounds<usize>,
+    {
+        self.width_slice(width_range).into()
+    }
+
+    /// Gets an owned slice of the [Rope<M>], using a width range.
+    ///
+    /// Unlike [width_slice()][Rope::width_slice], the returned
+    /// [OwnedRopeSlice<M>] does not borrow the [Rope<M>], so the [Rope<M>]
+    /// can keep being edited while the slice is alive.
+    ///
+    /// Uses range syntax, e.g. `2..7`, `2..`, etc.
+    ///
+    /// Runs in O(log N) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end, or if the
+    /// end is out of bounds (i.e. `end > Rope::width()`).
+    #[inline]
+    pub fn owned_width_slice<R>(&self, width_range: R) -> OwnedRopeSlice<M, C>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.width_slice(width_range).into()
+    }
+
+    /// Gets an owned slice of the [Rope<M>], using an index range.
+    ///
+    /// Unlike [index_slice()][Rope::index_slice], the returned
+    /// [OwnedRopeSlice<M>] does not borrow the [Rope<M>], so the [Rope<M>]
+    /// can keep being edited while the slice is alive.
+    ///
+    /// Uses range syntax, e.g. `2..7`, `2..`, etc.
+    ///
+    /// Runs in O(log N) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - The start of the range is greater than the end.
+    /// - The end is out of bounds (i.e. `end > Rope::len()`).
+    #[inline]
+    pub fn owned_index_slice<R>(&self, index_range: R) -> OwnedRopeSlice<M, C>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.index_slice(index_range).into()
+    }
+
+    /// Splits the [Rope<M>] into at most `n` slices of similar widths.
+    ///
+    /// The slices are cut at the boundaries of the leaves in the tree that
+    /// are closest to multiples of `Rope::width() / n`, so no elements are
+    /// copied, and no leaf is split between two slices. Fewer than `n`
+    /// slices are returned if there are not enough leaves, and none are
+    /// returned if the [Rope<M>] is empty.
+    ///
+    /// This is useful for fanning work out to multiple threads.
+    ///
+    /// Runs in O(n * log N) time.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let rope = Rope::from_slice(&[Lorem, Ipsum, Dolor(3), Sit].repeat(1000));
+    /// let slices = rope.split_into(4);
+    ///
+    /// assert!(slices.len() <= 4);
+    /// assert_eq!(slices.iter().map(|slice| slice.width()).sum::<usize>(), rope.width());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    pub fn split_into(&self, n: usize) -> Vec<RopeSlice<'_, M, C>> {
+        assert!(n > 0, "Attempt to split a Rope into 0 parts");
+        let width = self.width();
+        let mut reader = self.reader();
+
+        let bounds = (1..n).map(|k| {
+            let target = width / n * k + width % n * k / n;
+            let (chunk, index, start) = reader.chunk_at_width(target);
+            let end = start + chunk.iter().map(Measurable::width).sum::<usize>();
+            if target - start <= end - target {
+                index
+            } else {
+                index + chunk.len()
+            }
+        });
+
+        self.slices_between(bounds)
+    }
+
+    /// Splits the [Rope<M>] into at most `n` slices of similar lengths.
+    ///
+    /// Same as [split_into()][Rope::split_into], except that the slices are
+    /// cut near multiples of `Rope::len() / n`.
+    ///
+    /// Runs in O(n * log N) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    pub fn split_into_index(&self, n: usize) -> Vec<RopeSlice<'_, M, C>> {
+        assert!(n > 0, "Attempt to split a Rope into 0 parts");
+        let len = self.len();
+        let mut reader = self.reader();
+
+        let bounds = (1..n).map(|k| {
+            let target = len / n * k + len % n * k / n;
+            let (chunk, index, _) = reader.chunk_at_index(target);
+            if target - index <= index + chunk.len() - target {
+                index
+            } else {
+                index + chunk.len()
+            }
+        });
+
+        self.slices_between(bounds)
+    }
+
     //-----------------------------------------------------------------------
     // Iterator methods
 
@@ -628,7 +1364,7 @@ where
     ///
     /// Runs in O(log N) time.
     #[inline]
-    pub fn iter(&self) -> Iter<M> {
+    pub fn iter(&self) -> Iter<M, C> {
         Iter::new(&self.root)
     }
 
@@ -648,7 +1384,7 @@ where
     ///
     /// Panics if the `width` is out of bounds (i.e. `width > Rope::width()`).
     #[inline]
-    pub fn iter_at_width(&self, width: usize) -> Iter<M> {
+    pub fn iter_at_width(&self, width: usize) -> Iter<M, C> {
         if let Some(out) = self.get_iter_at_width(width) {
             out
         } else {
@@ -664,18 +1400,38 @@ where
     ///
     /// Runs in O(log N) time.
     #[inline]
-    pub fn chunks(&self) -> Chunks<M> {
+    pub fn chunks(&self) -> Chunks<M, C> {
         Chunks::new(&self.root)
     }
 
-    /// Creates an iterator over the chunks of the [Rope<M>], with the
-    /// iterator starting at the chunk containing the `index`.
+    /// Creates an owned iterator over the chunks of the [Rope<M>].
     ///
-    /// Also returns the index and width of the beginning of the first
-    /// chunk to be yielded.
+    /// Unlike [chunks()][Rope::chunks], the returned iterator does not
+    /// borrow the [Rope<M>], so the [Rope<M>] can keep being edited while
+    /// the iterator is alive.
     ///
-    /// If `index == Rope::len()` an iterator at the end of the [Rope<M>]
-    /// (yielding [None] on a call to [next()][crate::iter::Iter::next]) is created.
+    /// Runs in O(1) time.
+    #[inline]
+    pub fn owned_chunks(&self) -> OwnedChunks<M, C> {
+        OwnedChunks::new_with_range(Arc::clone(&self.root), (0, self.len()))
+    }
+
+    /// Creates a [RopeReader<M>], for fast reads of nearby positions.
+    ///
+    /// Runs in O(1) time.
+    #[inline]
+    pub fn reader(&self) -> RopeReader<'_, M, C> {
+        RopeReader::new(self)
+    }
+
+    /// Creates an iterator over the chunks of the [Rope<M>], with the
+    /// iterator starting at the chunk containing the `index`.
+    ///
+    /// Also returns the index and width of the beginning of the first
+    /// chunk to be yielded.
+    ///
+    /// If `index == Rope::len()` an iterator at the end of the [Rope<M>]
+    /// (yielding [None] on a call to [next()][crate::iter::Iter::next]) is created.
     ///
     /// The return value is organized as `(iterator, chunk_index, chunk_width)`.
     ///
@@ -685,7 +1441,7 @@ where
     ///
     /// Panics if the `index` is out of bounds (i.e. `index > Rope::len()`).
     #[inline]
-    pub fn chunks_at_index(&self, index: usize) -> (Chunks<M>, usize, usize) {
+    pub fn chunks_at_index(&self, index: usize) -> (Chunks<M, C>, usize, usize) {
         if let Some(out) = self.get_chunks_at_index(index) {
             out
         } else {
@@ -714,7 +1470,7 @@ where
     ///
     /// Panics if the `width` is out of bounds (i.e. `width > Rope::width()`).
     #[inline]
-    pub fn chunks_at_width(&self, width: usize) -> (Chunks<M>, usize, usize) {
+    pub fn chunks_at_width(&self, width: usize) -> (Chunks<M, C>, usize, usize) {
         if let Some(out) = self.get_chunks_at_width(width) {
             out
         } else {
@@ -789,6 +1545,219 @@ where
             self.root = child;
         }
     }
+
+    /// Splits the [Rope<M>] right before the element at `index`, returning
+    /// the right part of the split.
+    ///
+    /// Unlike [split_off()][Rope::split_off], this can split in between 0
+    /// width elements.
+    pub(crate) fn split_off_at_index(&mut self, index: usize) -> Self {
+        if index == 0 {
+            let mut new_rope = Rope::new_with_config();
+            std::mem::swap(self, &mut new_rope);
+            new_rope
+        } else if index == self.len() {
+            Rope::new_with_config()
+        } else {
+            let mut new_rope = Rope {
+                root: Arc::new(Arc::make_mut(&mut self.root).split_at_index(index)),
+            };
+
+            // Fix up the edges
+            Arc::make_mut(&mut self.root).zip_fix_right();
+            Arc::make_mut(&mut new_rope.root).zip_fix_left();
+            self.pull_up_singular_nodes();
+            new_rope.pull_up_singular_nodes();
+
+            new_rope
+        }
+    }
+
+    /// Returns the non-empty slices between each of the increasing indices
+    /// in `bounds`, as well as the start and end of the [Rope<M>].
+    pub(crate) fn slices_between(
+        &self,
+        bounds: impl Iterator<Item = usize>,
+    ) -> Vec<RopeSlice<'_, M, C>> {
+        let mut slices = Vec::new();
+        let mut start = 0;
+        for end in bounds.chain([self.len()]) {
+            if end > start {
+                slices.push(self.index_slice(start..end));
+                start = end;
+            }
+        }
+
+        slices
+    }
+
+    /// Inserts a [Rope<M>] right before the element at `index`.
+    pub(crate) fn insert_rope_at_index(&mut self, index: usize, rope: Self) {
+        let right = self.split_off_at_index(index);
+        self.append(rope);
+        self.append(right);
+    }
+
+    /// Removes the elements in the index range `[start, end)`.
+    pub(crate) fn remove_index_range(&mut self, start: usize, end: usize) {
+        if start == end {
+            return;
+        } else if start == 0 && end == self.len() {
+            self.root = Arc::new(Node::new());
+            return;
+        }
+
+        let root = Arc::make_mut(&mut self.root);
+
+        let root_info = root.slice_info();
+        let (_, needs_fix) = root.remove_range(start, end, root_info);
+
+        if needs_fix {
+            root.fix_tree_seam_at_index(start);
+        }
+
+        self.pull_up_singular_nodes();
+    }
+
+    /// Removes the elements in the index range `[start, end)`, returning them
+    /// as a new [Rope<M>].
+    pub(crate) fn cut_index_range(&mut self, start: usize, end: usize) -> Self {
+        if start == 0 && end == self.len() {
+            std::mem::replace(self, Rope::new_with_config())
+        } else {
+            let right = self.split_off_at_index(end);
+            let cut = self.split_off_at_index(start);
+            self.append(right);
+            cut
+        }
+    }
+
+    /// Replaces each `start..end` index range with its elements, returning
+    /// the resulting [EditMap].
+    ///
+    /// The ranges must be in bounds, sorted, and not overlapping.
+    pub(crate) fn splice_index_ranges<E>(&mut self, edits: E) -> EditMap
+    where
+        E: IntoIterator<Item = (usize, usize, Vec<M>)>,
+    {
+        let edits: Vec<_> = edits.into_iter().collect();
+        let mut reader = self.reader();
+        let widths: Vec<(usize, usize)> = edits
+            .iter()
+            .map(|&(start, end, _)| (reader.index_to_width(start), reader.index_to_width(end)))
+            .collect();
+
+        // Stitch the untouched parts back together with the new elements.
+        let mut map = EditMap::new();
+        let mut rest = std::mem::replace(self, Rope::new_with_config());
+        let mut consumed = 0;
+        for ((start, end, slice), (start_width, end_width)) in edits.into_iter().zip(widths) {
+            let mut removed = rest.split_off_at_index(start - consumed);
+            let right = removed.split_off_at_index(end - start);
+            self.append(std::mem::replace(&mut rest, right));
+
+            let new = Rope::from_slice_with_config(&slice);
+            map.push(
+                (start, start_width),
+                (end, end_width),
+                (new.len(), new.width()),
+            );
+            self.append(new);
+            consumed = end;
+        }
+        self.append(rest);
+
+        map
+    }
+
+    /// Returns the bounds of `index_range`, checking that they are valid.
+    pub(crate) fn checked_index_range<R>(&self, index_range: R) -> Result<(usize, usize)>
+    where
+        R: RangeBounds<usize>,
+    {
+        let start_opt = start_bound_to_num(index_range.start_bound());
+        let end_opt = end_bound_to_num(index_range.end_bound());
+        let start = start_opt.unwrap_or(0);
+        let end = end_opt.unwrap_or_else(|| self.len());
+        if end.max(start) > self.len() {
+            Err(Error::IndexRangeOutOfBounds(start_opt, end_opt, self.len()))
+        } else if start > end {
+            Err(Error::IndexRangeInvalid(start, end))
+        } else {
+            Ok((start, end))
+        }
+    }
+
+    /// Returns the index range of the elements that [remove()][Rope::remove]
+    /// would remove, given `width_range`.
+    pub(crate) fn width_range_to_index_range<R>(&self, width_range: R) -> Result<(usize, usize)>
+    where
+        R: RangeBounds<usize>,
+    {
+        let start_opt = start_bound_to_num(width_range.start_bound());
+        let end_opt = end_bound_to_num(width_range.end_bound());
+        let start = start_opt.unwrap_or(0);
+        let end = end_opt.unwrap_or_else(|| self.width());
+        if end.max(start) > self.width() {
+            Err(Error::WidthRangeOutOfBounds(
+                start_opt,
+                end_opt,
+                self.width(),
+            ))
+        } else if start > end {
+            Err(Error::WidthRangeInvalid(start, end))
+        } else {
+            let start_index = self.root.start_width_to_slice_info(start).len as usize;
+            let end_index = self.root.end_width_to_slice_info(end).len as usize;
+
+            // An empty range inside of an element with a width doesn't remove it.
+            match self.get_from_index(start_index) {
+                Some((_, measurable)) if start == end && measurable.width() > 0 => {
+                    Ok((start_index, start_index))
+                }
+                _ => Ok((start_index, end_index)),
+            }
+        }
+    }
+}
+
+/// Inserts `ins_slice` in `leaf_slice` at `index`, splitting the leaf if it
+/// gets too big.
+///
+/// Returns the same as the closures passed to
+/// [edit_chunk_at_width()][Node::edit_chunk_at_width].
+fn insert_into_leaf<M, C>(
+    leaf_slice: &mut LeafSlice<M, C>,
+    index: usize,
+    cur_info: SliceInfo,
+    ins_slice: &[M],
+) -> (SliceInfo, Option<(SliceInfo, Arc<Node<M, C>>)>)
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    // No node splitting
+    if (leaf_slice.len() + ins_slice.len()) <= max_len::<M, C>() {
+        // Calculate new info without doing a full re-scan of cur_slice.
+        let new_info = cur_info + SliceInfo::from_slice(ins_slice);
+        leaf_slice.insert_slice(index, ins_slice);
+        (new_info, None)
+    }
+    // We're splitting the node
+    else {
+        let r_slice = leaf_slice.insert_slice_split(index, ins_slice);
+        let l_slice_info = SliceInfo::from_slice(leaf_slice);
+        if r_slice.len() > 0 {
+            let r_slice_info = SliceInfo::from_slice(&r_slice);
+            (
+                l_slice_info,
+                Some((r_slice_info, Arc::new(Node::Leaf(r_slice)))),
+            )
+        } else {
+            // Leaf couldn't be validly split, so leave it oversized
+            (l_slice_info, None)
+        }
+    }
 }
 
 /// # Non-Panicking
@@ -796,15 +1765,33 @@ where
 /// The methods in this impl block provide non-panicking versions of
 /// [Rope<M>]'s panicking methods. They return either `Option::None` or
 /// `Result::Err()` when their panicking counterparts would have panicked.
-impl<M> Rope<M>
+impl<M, C> Rope<M, C>
 where
     M: Measurable,
+    C: RopeConfig,
 {
+    /// Non-panicking version of [compact_range()][Rope::compact_range].
+    pub fn try_compact_range<R>(&mut self, width_range: R) -> Result<()>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = self.width_range_to_index_range(width_range)?;
+        if start < end {
+            Arc::make_mut(&mut self.root).compact_range(start, end);
+            self.pull_up_singular_nodes();
+        }
+        Ok(())
+    }
+
     /// Non-panicking version of [insert()][Rope::insert].
     #[inline]
     pub fn try_insert_slice(&mut self, width: usize, mut slice: &[M]) -> Result<()> {
         // Bounds check
         if width <= self.width() {
+            // The slice goes after any 0 width elements at `width`, even if
+            // they are in the next leaf.
+            let index = self.root.end_width_to_slice_info(width).len as usize;
+
             // We have three cases here:
             // 1. The insertion slice is very large, in which case building a new
             //    Rope out of it and splicing it into the existing Rope is most
@@ -822,10 +1809,10 @@ where
             // The boundary for what constitutes "very large" slice was arrived at
             // experimentally, by testing at what point Rope build + splice becomes
             // faster than split + repeated insert.
-            if slice.len() > MAX_LEN * 6 {
+            if slice.len() > max_len::<M, C>() * 6 {
                 // Case #1: very large slice, build rope and splice it in.
-                let rope = Rope::from_slice(slice);
-                let right = self.split_off(width);
+                let rope = Rope::from_slice_with_config(slice);
+                let right = self.split_off_at_index(index);
                 self.append(rope);
                 self.append(right);
             } else {
@@ -835,12 +1822,12 @@ where
                     // We do this from the end instead of the front so that
                     // the repeated insertions can keep re-using the same
                     // insertion point.
-                    let split_index = slice.len() - (MAX_LEN - 4).min(slice.len());
+                    let split_index = slice.len() - (max_len::<M, C>() - 4).min(slice.len());
                     let ins_slice = &slice[split_index..];
                     slice = &slice[..split_index];
 
                     // Do the insertion.
-                    self.insert_internal(width, ins_slice);
+                    self.insert_internal_at_index(index, ins_slice);
                 }
             }
             Ok(())
@@ -854,7 +1841,8 @@ where
     pub fn try_insert(&mut self, width: usize, measurable: M) -> Result<()> {
         // Bounds check
         if width <= self.width() {
-            self.insert_internal(width, &[measurable]);
+            let index = self.root.end_width_to_slice_info(width).len as usize;
+            self.insert_internal_at_index(index, &[measurable]);
             Ok(())
         } else {
             Err(Error::WidthOutOfBounds(width, self.width()))
@@ -866,37 +1854,35 @@ where
     where
         R: RangeBounds<usize>,
     {
-        let start_opt = start_bound_to_num(width_range.start_bound());
-        let end_opt = end_bound_to_num(width_range.end_bound());
-        let start = start_opt.unwrap_or(0);
-        let end = end_opt.unwrap_or_else(|| self.width());
-        if end.max(start) > self.width() {
-            Err(Error::WidthRangeOutOfBounds(
-                start_opt,
-                end_opt,
-                self.width(),
-            ))
-        } else if start > end {
-            // A special case that the rest of the logic doesn't handle correctly
-            Err(Error::WidthRangeInvalid(start, end))
-        } else {
-            if start == 0 && end == self.width() {
-                self.root = Arc::new(Node::new());
-                return Ok(());
-            }
-
-            let root = Arc::make_mut(&mut self.root);
+        let (start, end) = self.width_range_to_index_range(width_range)?;
+        self.remove_index_range(start, end);
+        Ok(())
+    }
 
-            let root_info = root.slice_info();
-            let (_, needs_fix) = root.remove_range(start, end, root_info);
+    /// Non-panicking version of [drain()][Rope::drain].
+    pub fn try_drain<R>(&mut self, width_range: R) -> Result<IntoIter<M, C>>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.try_cut(width_range).map(IntoIterator::into_iter)
+    }
 
-            if needs_fix {
-                root.fix_tree_seam(start);
-            }
+    /// Non-panicking version of [cut()][Rope::cut].
+    pub fn try_cut<R>(&mut self, width_range: R) -> Result<Self>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = self.width_range_to_index_range(width_range)?;
+        Ok(self.cut_index_range(start, end))
+    }
 
-            self.pull_up_singular_nodes();
-            Ok(())
-        }
+    /// Non-panicking version of [cut_index()][Rope::cut_index].
+    pub fn try_cut_index<R>(&mut self, index_range: R) -> Result<Self>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = self.checked_index_range(index_range)?;
+        Ok(self.cut_index_range(start, end))
     }
 
     /// Non-panicking version of [split_off()][Rope::split_off].
@@ -905,12 +1891,12 @@ where
         if width <= self.width() {
             if width == 0 {
                 // Special case 1
-                let mut new_rope = Rope::new();
+                let mut new_rope = Rope::new_with_config();
                 std::mem::swap(self, &mut new_rope);
                 Ok(new_rope)
             } else if width == self.width() {
                 // Special case 2
-                Ok(Rope::new())
+                Ok(Rope::new_with_config())
             } else {
                 // Do the split
                 let mut new_rope = Rope {
@@ -930,13 +1916,111 @@ where
         }
     }
 
+    /// Non-panicking version of [insert_rope()][Rope::insert_rope].
+    pub fn try_insert_rope(&mut self, width: usize, rope: Self) -> Result<()> {
+        // Bounds check
+        if width <= self.width() {
+            let index = self.root.end_width_to_slice_info(width).len as usize;
+            self.insert_rope_at_index(index, rope);
+            Ok(())
+        } else {
+            Err(Error::WidthOutOfBounds(width, self.width()))
+        }
+    }
+
+    /// Non-panicking version of [insert_rope_slice()][Rope::insert_rope_slice].
+    #[inline]
+    pub fn try_insert_rope_slice(&mut self, width: usize, slice: RopeSlice<M, C>) -> Result<()> {
+        self.try_insert_rope(width, Rope::from(slice))
+    }
+
+    /// Non-panicking version of [copy_range_to()][Rope::copy_range_to].
+    pub fn try_copy_range_to<R>(&mut self, src_range: R, dest_width: usize) -> Result<()>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = self.width_range_to_index_range(src_range)?;
+        if dest_width > self.width() {
+            return Err(Error::WidthOutOfBounds(dest_width, self.width()));
+        }
+
+        let copy = Rope::from(self.index_slice(start..end));
+        let dest = self.root.end_width_to_slice_info(dest_width).len as usize;
+        self.insert_rope_at_index(dest, copy);
+        Ok(())
+    }
+
+    /// Non-panicking version of [move_range()][Rope::move_range].
+    pub fn try_move_range<R>(&mut self, src_range: R, dest_width: usize) -> Result<()>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = self.width_range_to_index_range(src_range)?;
+        if dest_width > self.width() {
+            return Err(Error::WidthOutOfBounds(dest_width, self.width()));
+        }
+
+        let dest = self.root.end_width_to_slice_info(dest_width).len as usize;
+        if (start..=end).contains(&dest) {
+            return Ok(());
+        }
+
+        let moved = self.cut_index_range(start, end);
+        if dest > end {
+            self.insert_rope_at_index(dest - (end - start), moved);
+        } else {
+            self.insert_rope_at_index(dest, moved);
+        }
+        Ok(())
+    }
+
+    /// Non-panicking version of [apply_edits()][Rope::apply_edits].
+    pub fn try_apply_edits<E>(&mut self, edits: E) -> Result<EditMap>
+    where
+        E: IntoIterator<Item = Edit<M>>,
+    {
+        // Find the index range of every edit, alongside its starting width.
+        let mut edits = edits
+            .into_iter()
+            .map(|edit| match edit {
+                Edit::Insert(width, slice) => {
+                    if width <= self.width() {
+                        let index = self.root.end_width_to_slice_info(width).len as usize;
+                        Ok((index, index, slice, width))
+                    } else {
+                        Err(Error::WidthOutOfBounds(width, self.width()))
+                    }
+                }
+                Edit::Remove(range) => {
+                    let (start, end) = self.width_range_to_index_range(range.clone())?;
+                    Ok((start, end, Vec::new(), range.start))
+                }
+                Edit::Replace(range, slice) => {
+                    let (start, end) = self.width_range_to_index_range(range.clone())?;
+                    Ok((start, end, slice, range.start))
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Insertions go before removals that start at the same index.
+        edits.sort_by_key(|&(start, end, ..)| (start, end));
+        if let Some(pair) = edits.windows(2).find(|pair| pair[1].0 < pair[0].1) {
+            return Err(Error::OverlappingEdits(pair[0].3, pair[1].3));
+        }
+
+        Ok(self.splice_index_ranges(
+            edits
+                .into_iter()
+                .map(|(start, end, slice, _)| (start, end, slice)),
+        ))
+    }
+
     /// Non-panicking version of [index_to_width()][Rope::index_to_width].
     #[inline]
     pub fn try_index_to_width(&self, index: usize) -> Result<usize> {
         // Bounds check
         if index <= self.len() {
-            let (chunk, b, c) = self.chunk_at_index(index);
-            Ok(c + index_to_width(chunk, index - b))
+            Ok(self.root.index_to_slice_info(index).width as usize)
         } else {
             Err(Error::IndexOutOfBounds(index, self.len()))
         }
@@ -947,8 +2031,7 @@ where
     pub fn try_start_width_to_index(&self, width: usize) -> Result<usize> {
         // Bounds check
         if width <= self.width() {
-            let (chunk, b, c) = self.chunk_at_width(width);
-            Ok(b + start_width_to_index(chunk, width - c))
+            Ok(self.root.start_width_to_slice_info(width).len as usize)
         } else {
             Err(Error::WidthOutOfBounds(width, self.width()))
         }
@@ -959,22 +2042,43 @@ where
     pub fn try_end_width_to_index(&self, width: usize) -> Result<usize> {
         // Bounds check
         if width <= self.width() {
-            let (chunk, b, c) = self.chunk_at_width(width);
-            Ok(b + end_width_to_index(chunk, width - c))
+            let (chunk, info) = self.root.get_last_chunk_at_width(width);
+            let index = chunk.end_width_to_index(width - info.width as usize);
+            Ok(info.len as usize + index)
         } else {
             Err(Error::WidthOutOfBounds(width, self.width()))
         }
     }
 
+    /// Non-panicking version of [indices_to_widths()][Rope::indices_to_widths].
+    #[inline]
+    pub fn try_indices_to_widths(&self, indices: &[usize]) -> Result<Vec<usize>> {
+        let mut reader = self.reader();
+        indices
+            .iter()
+            .map(|&index| reader.try_index_to_width(index))
+            .collect()
+    }
+
+    /// Non-panicking version of [widths_to_indices()][Rope::widths_to_indices].
+    #[inline]
+    pub fn try_widths_to_indices(&self, widths: &[usize]) -> Result<Vec<usize>> {
+        let mut reader = self.reader();
+        widths
+            .iter()
+            .map(|&width| reader.try_start_width_to_index(width))
+            .collect()
+    }
+
     /// Non-panicking version of [from_index()][Rope::from_index].
     #[inline]
     pub fn get_from_index(&self, index: usize) -> Option<(usize, M)> {
         // Bounds check
         if index < self.len() {
-            let (chunk, chunk_index, chunk_width) = self.chunk_at_index(index);
-            let chunk_rel_index = index - chunk_index;
-            let width = index_to_width(chunk, chunk_rel_index);
-            Some((width + chunk_width, chunk[chunk_rel_index]))
+            let (chunk, info) = self.root.get_chunk_at_index(index);
+            let chunk_rel_index = index - info.len as usize;
+            let width = chunk.index_to_width(chunk_rel_index);
+            Some((width + info.width as usize, chunk[chunk_rel_index]))
         } else {
             None
         }
@@ -985,15 +2089,25 @@ where
     pub fn get_from_width(&self, width: usize) -> Option<(usize, M)> {
         // Bounds check
         if width < self.width() {
-            let (chunk, _, chunk_width) = self.chunk_at_width(width);
-            let index = start_width_to_index(chunk, width - chunk_width);
-            let width = index_to_width(chunk, index);
-            Some((width + chunk_width, chunk[index]))
+            let (chunk, info) = self.root.get_first_chunk_at_width(width);
+            let index = chunk.start_width_to_index(width - info.width as usize);
+            let width = chunk.index_to_width(index);
+            Some((width + info.width as usize, chunk[index]))
         } else {
             None
         }
     }
 
+    /// Non-panicking version of [from_widths()][Rope::from_widths].
+    #[inline]
+    pub fn get_from_widths(&self, widths: &[usize]) -> Option<Vec<(usize, M)>> {
+        let mut reader = self.reader();
+        widths
+            .iter()
+            .map(|&width| reader.get_from_width(width))
+            .collect()
+    }
+
     /// Non-panicking version of [chunk_at_index()][Rope::chunk_at_index].
     #[inline]
     pub fn get_chunk_at_index(&self, index: usize) -> Option<(&[M], usize, usize)> {
@@ -1020,7 +2134,7 @@ where
 
     /// Non-panicking version of [width_slice()][Rope::width_slice].
     #[inline]
-    pub fn get_width_slice<R>(&self, width_range: R) -> Option<RopeSlice<M>>
+    pub fn get_width_slice<R>(&self, width_range: R) -> Option<RopeSlice<M, C>>
     where
         R: RangeBounds<usize>,
     {
@@ -1037,14 +2151,14 @@ where
 
     /// Non-panicking version of [index_slice()][Rope::index_slice].
     #[inline]
-    pub fn get_index_slice<R>(&self, index_range: R) -> Option<RopeSlice<M>>
+    pub fn get_index_slice<R>(&self, index_range: R) -> Option<RopeSlice<M, C>>
     where
         R: RangeBounds<usize>,
     {
         self.get_index_slice_impl(index_range).ok()
     }
 
-    pub(crate) fn get_index_slice_impl<R>(&self, index_range: R) -> Result<RopeSlice<M>>
+    pub(crate) fn get_index_slice_impl<R>(&self, index_range: R) -> Result<RopeSlice<M, C>>
     where
         R: RangeBounds<usize>,
     {
@@ -1091,7 +2205,7 @@ where
 
     /// Non-panicking version of [iter_at_width()][Rope::iter_at_width].
     #[inline]
-    pub fn get_iter_at_width(&self, width: usize) -> Option<Iter<M>> {
+    pub fn get_iter_at_width(&self, width: usize) -> Option<Iter<M, C>> {
         // Bounds check
         if width <= self.width() {
             Some(Iter::new_with_range_at_width(
@@ -1107,7 +2221,7 @@ where
 
     /// Non-panicking version of [chunks_at_index()][Rope::chunks_at_index].
     #[inline]
-    pub fn get_chunks_at_index(&self, index: usize) -> Option<(Chunks<M>, usize, usize)> {
+    pub fn get_chunks_at_index(&self, index: usize) -> Option<(Chunks<M, C>, usize, usize)> {
         // Bounds check
         if index <= self.len() {
             Some(Chunks::new_with_range_at_index(
@@ -1123,7 +2237,7 @@ where
 
     /// Non-panicking version of [chunks_at_width()][Rope::chunks_at_width].
     #[inline]
-    pub fn get_chunks_at_width(&self, width: usize) -> Option<(Chunks<M>, usize, usize)> {
+    pub fn get_chunks_at_width(&self, width: usize) -> Option<(Chunks<M, C>, usize, usize)> {
         // Bounds check
         if width <= self.width() {
             Some(Chunks::new_with_range_at_width(
@@ -1173,12 +2287,16 @@ where
 
 /// Will share data where possible.
 ///
+/// Only the nodes along the two edges of the [RopeSlice<M>] are copied, and
+/// every subtree in between is shared with the original [Rope<M>].
+///
 /// Runs in O(log N) time.
-impl<'a, M> From<RopeSlice<'a, M>> for Rope<M>
+impl<'a, M, C> From<RopeSlice<'a, M, C>> for Rope<M, C>
 where
     M: Measurable,
+    C: RopeConfig,
 {
-    fn from(s: RopeSlice<'a, M>) -> Self {
+    fn from(s: RopeSlice<'a, M, C>) -> Self {
         use crate::slice::RSEnum;
         match s {
             RopeSlice(RSEnum::Full {
@@ -1186,25 +2304,29 @@ where
                 start_info,
                 end_info,
             }) => {
+                if start_info.len == end_info.len {
+                    return Rope::new_with_config();
+                }
+
                 let mut rope = Rope {
                     root: Arc::clone(node),
                 };
 
                 // Chop off right end if needed
-                if end_info.width < node.slice_info().width {
+                if end_info.len < node.slice_info().len {
                     {
                         let root = Arc::make_mut(&mut rope.root);
-                        root.end_split(end_info.width as usize);
+                        root.split_at_index(end_info.len as usize);
                         root.zip_fix_right();
                     }
                     rope.pull_up_singular_nodes();
                 }
 
                 // Chop off left end if needed
-                if start_info.width > 0 {
+                if start_info.len > 0 {
                     {
                         let root = Arc::make_mut(&mut rope.root);
-                        *root = root.start_split(start_info.width as usize);
+                        *root = root.split_at_index(start_info.len as usize);
                         root.zip_fix_left();
                     }
                     rope.pull_up_singular_nodes();
@@ -1213,27 +2335,29 @@ where
                 // Return the rope
                 rope
             }
-            RopeSlice(RSEnum::Light { slice, .. }) => Rope::from_slice(slice),
+            RopeSlice(RSEnum::Light { slice, .. }) => Rope::from_slice_with_config(slice),
         }
     }
 }
 
-impl<M> From<Rope<M>> for Vec<M>
+impl<M, C> From<Rope<M, C>> for Vec<M>
 where
     M: Measurable,
+    C: RopeConfig,
 {
     #[inline]
-    fn from(r: Rope<M>) -> Self {
+    fn from(r: Rope<M, C>) -> Self {
         Vec::from(&r)
     }
 }
 
-impl<'a, M> From<&'a Rope<M>> for Vec<M>
+impl<'a, M, C> From<&'a Rope<M, C>> for Vec<M>
 where
     M: Measurable,
+    C: RopeConfig,
 {
     #[inline]
-    fn from(r: &'a Rope<M>) -> Self {
+    fn from(r: &'a Rope<M, C>) -> Self {
         let mut vec = Vec::with_capacity(r.len());
         vec.extend(
             r.chunks()
@@ -1245,12 +2369,13 @@ where
     }
 }
 
-impl<'a, M> From<Rope<M>> for std::borrow::Cow<'a, [M]>
+impl<'a, M, C> From<Rope<M, C>> for std::borrow::Cow<'a, [M]>
 where
     M: Measurable,
+    C: RopeConfig,
 {
     #[inline]
-    fn from(r: Rope<M>) -> Self {
+    fn from(r: Rope<M, C>) -> Self {
         std::borrow::Cow::Owned(Vec::from(r))
     }
 }
@@ -1259,12 +2384,13 @@ where
 /// owned [`[M]`][Measurable] if the contents is not contiguous in memory.
 ///
 /// Runs in best case O(1), worst case O(N).
-impl<'a, M> From<&'a Rope<M>> for std::borrow::Cow<'a, [M]>
+impl<'a, M, C> From<&'a Rope<M, C>> for std::borrow::Cow<'a, [M]>
 where
     M: Measurable,
+    C: RopeConfig,
 {
     #[inline]
-    fn from(r: &'a Rope<M>) -> Self {
+    fn from(r: &'a Rope<M, C>) -> Self {
         if let Node::Leaf(ref slice) = *r.root {
             std::borrow::Cow::Borrowed(slice)
         } else {
@@ -1321,25 +2447,119 @@ where
     }
 }
 
+impl<M> FromIterator<M> for Rope<M>
+where
+    M: Measurable,
+{
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = M>,
+    {
+        let mut builder = RopeBuilder::new();
+        for measurable in iter {
+            builder.append(measurable);
+        }
+        builder.finish()
+    }
+}
+
+impl<M, C> Extend<M> for Rope<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    /// Appends the elements of an iterator to the end of the [Rope<M>].
+    ///
+    /// The elements are first built into a separate [Rope<M>], which is then
+    /// [appended][Rope::append], so this runs in O(M + log N) time, where M
+    /// is the number of elements in the iterator.
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = M>,
+    {
+        self.append(Rope::from_iter_with_config(iter));
+    }
+}
+
+impl<'a, M, C> Extend<&'a [M]> for Rope<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    /// Appends the slices of an iterator to the end of the [Rope<M>].
+    ///
+    /// The slices are first built into a separate [Rope<M>], which is then
+    /// [appended][Rope::append], so this runs in O(M + log N) time, where M
+    /// is the number of elements in the iterator.
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = &'a [M]>,
+    {
+        self.append(Rope::from_chunks_with_config(iter));
+    }
+}
+
+impl<M, C> IntoIterator for Rope<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    type Item = (usize, M);
+    type IntoIter = IntoIter<M, C>;
+
+    /// Creates an owned iterator over the [Rope<M>].
+    ///
+    /// Runs in O(1) time.
+    #[inline]
+    fn into_iter(self) -> IntoIter<M, C> {
+        let len = self.len();
+        IntoIter::new_with_range(self.root, (0, len))
+    }
+}
+
+impl<'a, M, C> IntoIterator for &'a Rope<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    type Item = (usize, M);
+    type IntoIter = Iter<'a, M, C>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, M, C> {
+        self.iter()
+    }
+}
+
 //==============================================================
 // Other impls
 
-impl<M> std::fmt::Debug for Rope<M>
+/// Prints the elements of the [Rope<M>] as a list, no matter how they are
+/// split into chunks, so equal [Rope<M>]s always print the same way.
+///
+/// See [debug_chunks()][Rope::debug_chunks] and
+/// [debug_widths()][Rope::debug_widths] for other ways to print them.
+impl<M, C> std::fmt::Debug for Rope<M, C>
 where
     M: Measurable + Debug,
+    C: RopeConfig,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.debug_list().entries(self.chunks()).finish()
+        f.debug_list().entries(self.chunks().flatten()).finish()
     }
 }
 
-impl<M> std::fmt::Display for Rope<M>
+/// Prints the elements of the [Rope<M>] one after the other, with nothing in
+/// between them.
+impl<M, C> std::fmt::Display for Rope<M, C>
 where
-    M: Measurable + Debug,
+    M: Measurable + Display,
+    C: RopeConfig,
 {
-    #[inline]
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.debug_list().entries(self.chunks()).finish()
+        self.chunks()
+            .flatten()
+            .try_for_each(|measurable| Display::fmt(measurable, f))
     }
 }
 
@@ -1353,21 +2573,28 @@ where
     }
 }
 
-impl<M> std::cmp::Eq for Rope<M> where M: Measurable + Eq {}
+impl<M, C> std::cmp::Eq for Rope<M, C>
+where
+    M: Measurable + Eq,
+    C: RopeConfig,
+{
+}
 
-impl<M> std::cmp::PartialEq<Rope<M>> for Rope<M>
+impl<M, C> std::cmp::PartialEq<Rope<M, C>> for Rope<M, C>
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
     #[inline]
-    fn eq(&self, other: &Rope<M>) -> bool {
+    fn eq(&self, other: &Rope<M, C>) -> bool {
         self.width_slice(..) == other.width_slice(..)
     }
 }
 
-impl<'a, M> std::cmp::PartialEq<&'a [M]> for Rope<M>
+impl<'a, M, C> std::cmp::PartialEq<&'a [M]> for Rope<M, C>
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
     #[inline]
     fn eq(&self, other: &&'a [M]) -> bool {
@@ -1375,19 +2602,21 @@ where
     }
 }
 
-impl<'a, M> std::cmp::PartialEq<Rope<M>> for &'a [M]
+impl<'a, M, C> std::cmp::PartialEq<Rope<M, C>> for &'a [M]
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
     #[inline]
-    fn eq(&self, other: &Rope<M>) -> bool {
+    fn eq(&self, other: &Rope<M, C>) -> bool {
         *self == other.width_slice(..)
     }
 }
 
-impl<M> std::cmp::PartialEq<[M]> for Rope<M>
+impl<M, C> std::cmp::PartialEq<[M]> for Rope<M, C>
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
     #[inline]
     fn eq(&self, other: &[M]) -> bool {
@@ -1395,19 +2624,21 @@ where
     }
 }
 
-impl<M> std::cmp::PartialEq<Rope<M>> for [M]
+impl<M, C> std::cmp::PartialEq<Rope<M, C>> for [M]
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
     #[inline]
-    fn eq(&self, other: &Rope<M>) -> bool {
+    fn eq(&self, other: &Rope<M, C>) -> bool {
         self == other.width_slice(..)
     }
 }
 
-impl<'a, M> std::cmp::PartialEq<Vec<M>> for Rope<M>
+impl<'a, M, C> std::cmp::PartialEq<Vec<M>> for Rope<M, C>
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
     #[inline]
     fn eq(&self, other: &Vec<M>) -> bool {
@@ -1415,19 +2646,21 @@ where
     }
 }
 
-impl<'a, M> std::cmp::PartialEq<Rope<M>> for Vec<M>
+impl<'a, M, C> std::cmp::PartialEq<Rope<M, C>> for Vec<M>
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
     #[inline]
-    fn eq(&self, other: &Rope<M>) -> bool {
+    fn eq(&self, other: &Rope<M, C>) -> bool {
         self.as_slice() == other.width_slice(..)
     }
 }
 
-impl<'a, M> std::cmp::PartialEq<std::borrow::Cow<'a, [M]>> for Rope<M>
+impl<'a, M, C> std::cmp::PartialEq<std::borrow::Cow<'a, [M]>> for Rope<M, C>
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
     #[inline]
     fn eq(&self, other: &std::borrow::Cow<'a, [M]>) -> bool {
@@ -1435,41 +2668,63 @@ where
     }
 }
 
-impl<'a, M> std::cmp::PartialEq<Rope<M>> for std::borrow::Cow<'a, [M]>
+impl<'a, M, C> std::cmp::PartialEq<Rope<M, C>> for std::borrow::Cow<'a, [M]>
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
     #[inline]
-    fn eq(&self, other: &Rope<M>) -> bool {
+    fn eq(&self, other: &Rope<M, C>) -> bool {
         **self == other.width_slice(..)
     }
 }
 
-impl<M> std::cmp::Ord for Rope<M>
+impl<M, C> std::cmp::Ord for Rope<M, C>
 where
     M: Measurable + Ord,
+    C: RopeConfig,
 {
     #[inline]
-    fn cmp(&self, other: &Rope<M>) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Rope<M, C>) -> std::cmp::Ordering {
         self.width_slice(..).cmp(&other.width_slice(..))
     }
 }
 
-impl<M> std::cmp::PartialOrd<Rope<M>> for Rope<M>
+impl<M, C> std::cmp::PartialOrd<Rope<M, C>> for Rope<M, C>
 where
     M: Measurable + PartialOrd + Ord,
+    C: RopeConfig,
 {
     #[inline]
-    fn partial_cmp(&self, other: &Rope<M>) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Rope<M, C>) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
+/// Hashes the elements of the [Rope<M>] one by one, so equal [Rope<M>]s and
+/// [RopeSlice<M>]s hash the same, no matter how they are split into chunks.
+///
+/// This always goes through every element. To avoid that when hashing the
+/// same [Rope<M>] many times between small edits, see
+/// [HashCache][crate::HashCache].
+impl<M, C> std::hash::Hash for Rope<M, C>
+where
+    M: Measurable + std::hash::Hash,
+    C: RopeConfig,
+{
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.width_slice(..).hash(state)
+    }
+}
+
 //==============================================================
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::CachedConfig;
+    use crate::slice_utils::{index_to_width, start_width_to_index};
     use crate::Lipsum::{self, *};
 
     /// 70 elements, total width of 135.
@@ -1627,6 +2882,22 @@ mod tests {
         rope.assert_invariants();
     }
 
+    #[test]
+    fn insert_07() {
+        // The 0 width elements at width 3 span several leaves.
+        let vec = [[Dolor(3)].as_slice(), &[Sit; 20], &[Lorem]].concat();
+        let mut rope = Rope::from_slice(vec.as_slice());
+        assert!(rope.root.child_count() > 1);
+
+        rope.insert(3, Ipsum);
+        rope.insert_slice(3, &[Lorem, Amet]);
+        let expected = [&vec[..21], &[Lorem, Amet, Ipsum], &vec[21..]].concat();
+        assert_eq!(rope, expected.as_slice());
+
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+
     #[test]
     fn remove_01() {
         let slice = &[Dolor(15), Sit, Amet, Dolor(24), Lorem, Ipsum, Dolor(7)];
@@ -1680,139 +2951,557 @@ mod tests {
     }
 
     #[test]
-    fn remove_05() {
-        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+    fn remove_05() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+
+        // Make sure removing a large range works.
+        rope.remove(3..135);
+        assert_eq!(rope, &lorem_ipsum()[..2]);
+
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+
+    #[test]
+    #[should_panic]
+    fn remove_06() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        #[allow(clippy::reversed_empty_ranges)]
+        rope.remove(56..55); // Wrong ordering of start/end on purpose.
+    }
+
+    #[test]
+    #[should_panic]
+    fn remove_07() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        rope.remove(134..136); // Removing past the end
+    }
+
+    #[test]
+    fn split_off_01() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+
+        let split = rope.split_off(50);
+        assert_eq!(rope, &lorem_ipsum()[..24]);
+        assert_eq!(split, &lorem_ipsum()[24..]);
+
+        rope.assert_integrity();
+        split.assert_integrity();
+        rope.assert_invariants();
+        split.assert_invariants();
+    }
+
+    #[test]
+    fn split_off_02() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+
+        let split = rope.split_off(1);
+        assert_eq!(rope, [Lorem].as_slice());
+        assert_eq!(split, &lorem_ipsum()[1..]);
+
+        rope.assert_integrity();
+        split.assert_integrity();
+        rope.assert_invariants();
+        split.assert_invariants();
+    }
+
+    #[test]
+    fn split_off_03() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+
+        let split = rope.split_off(134);
+        assert_eq!(rope, &lorem_ipsum()[..69]);
+        assert_eq!(split, [Adipiscing(false)].as_slice());
+
+        rope.assert_integrity();
+        split.assert_integrity();
+        rope.assert_invariants();
+        split.assert_invariants();
+    }
+
+    #[test]
+    fn split_off_04() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+
+        let split = rope.split_off(0);
+        assert_eq!(rope, [].as_slice());
+        assert_eq!(split, lorem_ipsum().as_slice());
+
+        rope.assert_integrity();
+        split.assert_integrity();
+        rope.assert_invariants();
+        split.assert_invariants();
+    }
+
+    #[test]
+    fn split_off_05() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+
+        let split = rope.split_off(135);
+        assert_eq!(rope, lorem_ipsum().as_slice());
+        assert_eq!(split, [].as_slice());
+
+        rope.assert_integrity();
+        split.assert_integrity();
+        rope.assert_invariants();
+        split.assert_invariants();
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_off_06() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        rope.split_off(136); // One past the end of the rope
+    }
+
+    #[test]
+    fn cut_01() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let cut = rope.cut(3..27);
+
+        assert_eq!(rope, [&lorem_ipsum()[..2], &lorem_ipsum()[14..]].concat());
+        assert_eq!(cut, &lorem_ipsum()[2..14]);
+
+        rope.assert_integrity();
+        rope.assert_invariants();
+        cut.assert_integrity();
+        cut.assert_invariants();
+    }
+
+    #[test]
+    fn cut_02() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let cut = rope.cut(..);
+
+        assert_eq!(rope, [].as_slice());
+        assert_eq!(cut, lorem_ipsum());
+    }
+
+    #[test]
+    fn cut_03() {
+        // Should always agree with `remove()`.
+        let lorem_ipsum = lorem_ipsum();
+        for start in 0..=135 {
+            for end in (start..=135).step_by(7) {
+                let mut rope_1 = Rope::from_slice(lorem_ipsum.as_slice());
+                let mut rope_2 = Rope::from_slice(lorem_ipsum.as_slice());
+                rope_1.remove(start..end);
+                let cut = rope_2.cut(start..end);
+
+                assert_eq!(rope_1, rope_2);
+                assert_eq!(cut.len() + rope_2.len(), lorem_ipsum.len());
+
+                rope_2.assert_integrity();
+                rope_2.assert_invariants();
+                cut.assert_integrity();
+                cut.assert_invariants();
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn cut_04() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        #[allow(clippy::reversed_empty_ranges)]
+        rope.cut(53..52); // Wrong ordering on purpose.
+    }
+
+    #[test]
+    fn cut_index_01() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        // Splits up the 0 width lists.
+        let cut = rope.cut_index(4..11);
+
+        assert_eq!(rope, [&lorem_ipsum()[..4], &lorem_ipsum()[11..]].concat());
+        assert_eq!(cut, &lorem_ipsum()[4..11]);
+
+        rope.assert_integrity();
+        rope.assert_invariants();
+        cut.assert_integrity();
+        cut.assert_invariants();
+    }
+
+    #[test]
+    #[should_panic]
+    fn cut_index_02() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        rope.cut_index(20..71);
+    }
+
+    #[test]
+    fn append_01() {
+        let mut rope = Rope::from_slice(&lorem_ipsum()[..35]);
+        let append = Rope::from_slice(&lorem_ipsum()[35..]);
+
+        rope.append(append);
+        assert_eq!(rope, lorem_ipsum().as_slice());
+
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+
+    #[test]
+    fn append_02() {
+        let mut rope = Rope::from_slice(&lorem_ipsum()[..68]);
+        let append = Rope::from_slice(&[Consectur("bye"), Adipiscing(false)]);
+
+        rope.append(append);
+        assert_eq!(rope, lorem_ipsum());
+
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+
+    #[test]
+    fn append_03() {
+        let mut rope = Rope::from_slice(&[Lorem, Ipsum]);
+        let append = Rope::from_slice(&lorem_ipsum()[2..]);
+
+        rope.append(append);
+        assert_eq!(rope, lorem_ipsum());
+
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+
+    #[test]
+    fn append_04() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let append = Rope::from_slice([].as_slice());
+
+        rope.append(append);
+        assert_eq!(rope, lorem_ipsum());
+
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+
+    #[test]
+    fn append_05() {
+        let mut rope = Rope::from_slice([].as_slice());
+        let append = Rope::from_slice(lorem_ipsum().as_slice());
+
+        rope.append(append);
+        assert_eq!(rope, lorem_ipsum());
+
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+
+    #[test]
+    fn append_06() {
+        let mut rope = Rope::from_slice(&[Sit, Amet]);
+        rope.append(Rope::from_slice(&[Amet]));
+        assert_eq!(rope, [Sit, Amet, Amet].as_slice());
+
+        rope.append(Rope::from_slice(lorem_ipsum().as_slice()));
+        let mut lorem_ipsum = lorem_ipsum();
+        lorem_ipsum.splice(0..0, [Sit, Amet, Amet]);
+        assert_eq!(rope, lorem_ipsum);
+
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+
+    #[test]
+    fn insert_rope_01() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        rope.insert_rope(7, Rope::from_slice(SHORT_LOREM));
+
+        let mut lorem_ipsum = lorem_ipsum();
+        lorem_ipsum.splice(5..5, SHORT_LOREM.iter().copied());
+        assert_eq!(rope, lorem_ipsum);
+
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+
+    #[test]
+    fn insert_rope_02() {
+        let mut rope = Rope::from_slice(SHORT_LOREM);
+        rope.insert_rope(0, Rope::from_slice(lorem_ipsum().as_slice()));
+        rope.insert_rope(rope.width(), Rope::from_slice(lorem_ipsum().as_slice()));
+
+        let mut lorem_ipsum_2 = lorem_ipsum();
+        lorem_ipsum_2.extend_from_slice(SHORT_LOREM);
+        lorem_ipsum_2.extend(lorem_ipsum());
+        assert_eq!(rope, lorem_ipsum_2);
+
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+
+    #[test]
+    fn insert_rope_03() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        rope.insert_rope(24, rope.clone());
+
+        let mut lorem_ipsum_2 = lorem_ipsum();
+        lorem_ipsum_2.splice(12..12, lorem_ipsum());
+        assert_eq!(rope, lorem_ipsum_2);
+
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_rope_04() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        rope.insert_rope(136, Rope::from_slice(SHORT_LOREM));
+    }
+
+    #[test]
+    fn insert_rope_slice_01() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let other = Rope::from_slice(lorem_ipsum().as_slice());
+        rope.insert_rope_slice(13, other.width_slice(16..27));
+
+        let mut lorem_ipsum = lorem_ipsum();
+        lorem_ipsum.splice(7..7, lorem_ipsum[9..14].to_vec());
+        assert_eq!(rope, lorem_ipsum);
+
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+
+    #[test]
+    fn copy_range_to_01() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        rope.copy_range_to(3..7, 27);
+
+        let mut lorem_ipsum = lorem_ipsum();
+        lorem_ipsum.splice(14..14, lorem_ipsum[2..5].to_vec());
+        assert_eq!(rope, lorem_ipsum);
+
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+
+    #[test]
+    fn copy_range_to_02() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        // Empty range at the start of a 0 width list.
+        rope.copy_range_to(24..24, 0);
+
+        let mut lorem_ipsum = lorem_ipsum();
+        lorem_ipsum.splice(0..0, [Sit, Amet]);
+        assert_eq!(rope, lorem_ipsum);
+
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+
+    #[test]
+    fn copy_range_to_03() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        // Empty range in the middle of `Dolor(4)`.
+        rope.copy_range_to(4..4, 0);
+
+        assert_eq!(rope, lorem_ipsum());
+    }
+
+    #[test]
+    fn move_range_01() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        rope.move_range(0..7, 27);
+
+        let mut lorem_ipsum = lorem_ipsum();
+        lorem_ipsum[0..14].rotate_left(5);
+        assert_eq!(rope, lorem_ipsum);
+
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+
+    #[test]
+    fn move_range_02() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        rope.move_range(27..54, 0);
+
+        let mut lorem_ipsum = lorem_ipsum();
+        lorem_ipsum[0..28].rotate_left(13);
+        assert_eq!(rope, lorem_ipsum);
+
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+
+    #[test]
+    fn move_range_03() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        // Destination inside of the range.
+        rope.move_range(10..40, 20);
+
+        assert_eq!(rope, lorem_ipsum());
+    }
+
+    #[test]
+    #[should_panic]
+    fn move_range_04() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        rope.move_range(10..40, 136);
+    }
+
+    #[test]
+    fn push_01() {
+        let mut rope = Rope::new();
+        for lipsum in lorem_ipsum() {
+            rope.push(lipsum);
+        }
+
+        assert_eq!(rope, lorem_ipsum());
+
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+
+    #[test]
+    fn push_02() {
+        // Always pushes after the trailing 0 width elements.
+        let mut rope = Rope::from_slice(&lorem_ipsum()[..12]);
+        rope.push(Lorem);
+        rope.push(Sit);
+        rope.push(Ipsum);
 
-        // Make sure removing a large range works.
-        rope.remove(3..135);
-        assert_eq!(rope, &lorem_ipsum()[..2]);
+        let mut lorem_ipsum = lorem_ipsum()[..12].to_vec();
+        lorem_ipsum.extend([Lorem, Sit, Ipsum]);
+        assert_eq!(rope, lorem_ipsum);
 
         rope.assert_integrity();
         rope.assert_invariants();
     }
 
     #[test]
-    #[should_panic]
-    fn remove_06() {
+    fn pop_01() {
         let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
-        #[allow(clippy::reversed_empty_ranges)]
-        rope.remove(56..55); // Wrong ordering of start/end on purpose.
-    }
+        let mut lorem_ipsum = lorem_ipsum();
 
-    #[test]
-    #[should_panic]
-    fn remove_07() {
-        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
-        rope.remove(134..136); // Removing past the end
+        while let Some(lipsum) = lorem_ipsum.pop() {
+            assert_eq!(rope.pop(), Some(lipsum));
+            assert_eq!(rope, lorem_ipsum);
+
+            rope.assert_integrity();
+            rope.assert_invariants();
+        }
+
+        assert_eq!(rope.pop(), None);
+        assert!(rope.is_empty());
     }
 
     #[test]
-    fn split_off_01() {
-        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
-
-        let split = rope.split_off(50);
-        assert_eq!(rope, &lorem_ipsum()[..24]);
-        assert_eq!(split, &lorem_ipsum()[24..]);
+    fn first_last_01() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        assert_eq!(rope.first(), Some(Lorem));
+        assert_eq!(rope.last(), Some(Adipiscing(false)));
 
-        rope.assert_integrity();
-        split.assert_integrity();
-        rope.assert_invariants();
-        split.assert_invariants();
+        let rope = Rope::<Lipsum>::new();
+        assert_eq!(rope.first(), None);
+        assert_eq!(rope.last(), None);
     }
 
     #[test]
-    fn split_off_02() {
+    fn truncate_width_01() {
         let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
 
-        let split = rope.split_off(1);
-        assert_eq!(rope, [Lorem].as_slice());
-        assert_eq!(split, &lorem_ipsum()[1..]);
+        // Does nothing past the end.
+        rope.truncate_width(136);
+        assert_eq!(rope, lorem_ipsum());
+
+        // Removes the 0 width elements at the truncation point.
+        rope.truncate_width(24);
+        assert_eq!(rope, &lorem_ipsum()[..10]);
+
+        // Removes the element in the middle of the truncation point.
+        rope.truncate_width(20);
+        assert_eq!(rope, &lorem_ipsum()[..9]);
 
         rope.assert_integrity();
-        split.assert_integrity();
         rope.assert_invariants();
-        split.assert_invariants();
     }
 
     #[test]
-    fn split_off_03() {
+    fn truncate_index_01() {
         let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
 
-        let split = rope.split_off(134);
-        assert_eq!(rope, &lorem_ipsum()[..69]);
-        assert_eq!(split, [Adipiscing(false)].as_slice());
+        rope.truncate_index(71);
+        assert_eq!(rope, lorem_ipsum());
+
+        rope.truncate_index(11);
+        assert_eq!(rope, &lorem_ipsum()[..11]);
 
         rope.assert_integrity();
-        split.assert_integrity();
         rope.assert_invariants();
-        split.assert_invariants();
+
+        rope.truncate_index(0);
+        assert!(rope.is_empty());
     }
 
     #[test]
-    fn split_off_04() {
+    fn clear_01() {
         let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        rope.clear();
 
-        let split = rope.split_off(0);
+        assert!(rope.is_empty());
         assert_eq!(rope, [].as_slice());
-        assert_eq!(split, lorem_ipsum().as_slice());
 
-        rope.assert_integrity();
-        split.assert_integrity();
-        rope.assert_invariants();
-        split.assert_invariants();
+        // 0 width elements are still elements.
+        let rope = Rope::from_slice(&[Sit, Amet]);
+        assert_eq!(rope.width(), 0);
+        assert!(!rope.is_empty());
     }
 
     #[test]
-    fn split_off_05() {
+    fn drain_01() {
         let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let drained: Vec<Lipsum> = rope.drain(3..27).map(|(_, lipsum)| lipsum).collect();
 
-        let split = rope.split_off(135);
-        assert_eq!(rope, lorem_ipsum().as_slice());
-        assert_eq!(split, [].as_slice());
+        assert_eq!(drained, &lorem_ipsum()[2..14]);
+        assert_eq!(rope, [&lorem_ipsum()[..2], &lorem_ipsum()[14..]].concat());
 
         rope.assert_integrity();
-        split.assert_integrity();
         rope.assert_invariants();
-        split.assert_invariants();
     }
 
     #[test]
-    #[should_panic]
-    fn split_off_06() {
+    fn drain_02() {
         let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
-        rope.split_off(136); // One past the end of the rope
+        // Removes the range even if the iterator is never consumed.
+        drop(rope.drain(..27));
+
+        assert_eq!(rope, &lorem_ipsum()[14..]);
     }
 
     #[test]
-    fn append_01() {
-        let mut rope = Rope::from_slice(&lorem_ipsum()[..35]);
-        let append = Rope::from_slice(&lorem_ipsum()[35..]);
+    fn from_iter_02() {
+        let rope: Rope<Lipsum> = lorem_ipsum().into_iter().collect();
 
-        rope.append(append);
-        assert_eq!(rope, lorem_ipsum().as_slice());
+        assert_eq!(rope, lorem_ipsum());
 
         rope.assert_integrity();
         rope.assert_invariants();
     }
 
     #[test]
-    fn append_02() {
-        let mut rope = Rope::from_slice(&lorem_ipsum()[..68]);
-        let append = Rope::from_slice(&[Consectur("bye"), Adipiscing(false)]);
+    fn from_iter_03() {
+        // These must infer the default config, without any annotations.
+        let vec = lorem_ipsum();
+        let rope = Rope::from_iter(vec.chunks(7));
+        let slice = RopeSlice::from(vec.as_slice());
+        let mut default = Rope::default();
+        default.push(Lorem);
 
-        rope.append(append);
-        assert_eq!(rope, lorem_ipsum());
-
-        rope.assert_integrity();
-        rope.assert_invariants();
+        assert_eq!(rope.len(), vec.len());
+        assert_eq!(slice.len(), vec.len());
+        assert_eq!(default.len(), 1);
     }
 
     #[test]
-    fn append_03() {
-        let mut rope = Rope::from_slice(&[Lorem, Ipsum]);
-        let append = Rope::from_slice(&lorem_ipsum()[2..]);
+    fn extend_01() {
+        let mut rope = Rope::from_slice(&lorem_ipsum()[..30]);
+        rope.extend(lorem_ipsum()[30..].iter().copied());
 
-        rope.append(append);
         assert_eq!(rope, lorem_ipsum());
 
         rope.assert_integrity();
@@ -1820,27 +3509,27 @@ mod tests {
     }
 
     #[test]
-    fn append_04() {
-        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
-        let append = Rope::from_slice([].as_slice());
+    fn extend_02() {
+        let lorem_ipsum = lorem_ipsum();
+        let mut rope = Rope::from_slice(&lorem_ipsum[..4]);
+        rope.extend(lorem_ipsum[4..].chunks(6));
 
-        rope.append(append);
-        assert_eq!(rope, lorem_ipsum());
+        assert_eq!(rope, lorem_ipsum);
 
         rope.assert_integrity();
         rope.assert_invariants();
     }
 
     #[test]
-    fn append_05() {
-        let mut rope = Rope::from_slice([].as_slice());
-        let append = Rope::from_slice(lorem_ipsum().as_slice());
+    fn into_iter_01() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
 
-        rope.append(append);
-        assert_eq!(rope, lorem_ipsum());
+        let borrowed: Vec<(usize, Lipsum)> = (&rope).into_iter().collect();
+        let owned: Vec<(usize, Lipsum)> = rope.into_iter().collect();
 
-        rope.assert_integrity();
-        rope.assert_invariants();
+        assert_eq!(borrowed, owned);
+        assert_eq!(owned.len(), 70);
+        assert!(owned.iter().map(|(_, lipsum)| *lipsum).eq(lorem_ipsum()));
     }
 
     #[test]
@@ -1860,6 +3549,20 @@ mod tests {
         assert_eq!(rope.start_width_to_index(103), 51);
     }
 
+    #[test]
+    fn width_to_index_02() {
+        // The 0 width elements at width 3 span several leaves.
+        let vec = [[Dolor(3)].as_slice(), &[Sit; 20], &[Lorem]].concat();
+        let rope = Rope::from_slice(vec.as_slice());
+        assert!(rope.root.child_count() > 1);
+
+        assert_eq!(rope.start_width_to_index(3), 1);
+        assert_eq!(rope.end_width_to_index(3), 21);
+        assert_eq!(rope.width_slice(..).end_width_to_index(3), 21);
+        assert_eq!(rope.index_slice(5..).end_width_to_index(0), 16);
+        assert_eq!(rope.reader().end_width_to_index(3), 21);
+    }
+
     #[test]
     fn from_index_01() {
         let rope = Rope::from_slice(lorem_ipsum().as_slice());
@@ -2202,6 +3905,46 @@ mod tests {
         assert_eq!(slice, rope_2);
     }
 
+    #[test]
+    fn from_rope_slice_05() {
+        let rope_1 = Rope::from_slice(lorem_ipsum().as_slice());
+
+        // Cut through the middle of lists of 0 width elements on both ends.
+        for (start, end) in [(4, 60), (11, 25), (3, 68), (24, 25)] {
+            let slice = rope_1.index_slice(start..end);
+            let rope_2: Rope<Lipsum> = slice.into();
+
+            assert_eq!(rope_2, &lorem_ipsum()[start..end]);
+            rope_2.assert_integrity();
+            rope_2.assert_invariants();
+        }
+    }
+
+    #[test]
+    fn extract_01() {
+        let rope_1 = Rope::from_slice(lorem_ipsum().as_slice());
+        let rope_2 = rope_1.extract(13..110);
+
+        assert_eq!(rope_2, rope_1.width_slice(13..110));
+        rope_2.assert_integrity();
+        rope_2.assert_invariants();
+
+        // The chunks in the middle must be shared, not copied.
+        let chunks: Vec<_> = rope_1.chunks().map(|chunk| chunk.as_ptr()).collect();
+        assert!(rope_2
+            .chunks()
+            .any(|chunk| chunks.contains(&chunk.as_ptr())));
+    }
+
+    #[test]
+    fn extract_02() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+
+        assert_eq!(rope.extract(..), rope);
+        assert_eq!(rope.extract(53..53), rope.width_slice(53..53));
+        assert_eq!(rope.extract(135..), rope.width_slice(135..));
+    }
+
     #[test]
     fn from_iter_01() {
         let rope_1 = Rope::from_slice(lorem_ipsum().as_slice());
@@ -2227,5 +3970,400 @@ mod tests {
         assert!(!c1.is_instance(&c2));
     }
 
+    #[test]
+    fn cached_widths_01() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let mut cached = Rope::<_, CachedConfig>::from_slice_with_config(lorem_ipsum().as_slice());
+
+        for (width, slice) in [(3, SHORT_LOREM), (40, &[Sit, Sit][..]), (100, SHORT_LOREM)] {
+            for width in 0..=rope.width() {
+                assert_eq!(
+                    rope.start_width_to_index(width),
+                    cached.start_width_to_index(width)
+                );
+                assert_eq!(
+                    rope.end_width_to_index(width),
+                    cached.end_width_to_index(width)
+                );
+                assert_eq!(rope.get_from_width(width), cached.get_from_width(width));
+            }
+            for index in 0..=rope.len() {
+                assert_eq!(rope.index_to_width(index), cached.index_to_width(index));
+                assert_eq!(rope.get_from_index(index), cached.get_from_index(index));
+            }
+
+            rope.insert_slice(width, slice);
+            cached.insert_slice(width, slice);
+            rope.remove(width / 2..width);
+            cached.remove(width / 2..width);
+        }
+
+        assert_eq!(Vec::from(&rope), Vec::from(&cached));
+        cached.assert_integrity();
+        cached.assert_invariants();
+    }
+
+    #[test]
+    fn batched_lookups_01() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+
+        let widths: Vec<usize> = (0..=rope.width()).collect();
+        let indices: Vec<usize> = widths
+            .iter()
+            .map(|&w| rope.start_width_to_index(w))
+            .collect();
+        assert_eq!(rope.widths_to_indices(&widths), indices);
+
+        let expected: Vec<usize> = (0..=rope.len()).map(|i| rope.index_to_width(i)).collect();
+        let indices: Vec<usize> = (0..=rope.len()).collect();
+        assert_eq!(rope.indices_to_widths(&indices), expected);
+
+        let widths: Vec<usize> = (0..rope.width()).collect();
+        let expected: Vec<(usize, Lipsum)> = widths.iter().map(|&w| rope.from_width(w)).collect();
+        assert_eq!(rope.from_widths(&widths), expected);
+    }
+
+    #[test]
+    fn batched_lookups_02() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+
+        // Unsorted, with repeats.
+        let widths = [100, 3, 3, 134, 0, 57, 12, 135, 56];
+        let indices: Vec<usize> = widths
+            .iter()
+            .map(|&w| rope.start_width_to_index(w))
+            .collect();
+        assert_eq!(rope.widths_to_indices(&widths), indices);
+
+        let expected: Vec<usize> = widths.iter().map(|&i| rope.index_to_width(i / 2)).collect();
+        let indices: Vec<usize> = widths.iter().map(|&i| i / 2).collect();
+        assert_eq!(rope.indices_to_widths(&indices), expected);
+
+        assert_eq!(rope.from_widths(&[]), []);
+        assert_eq!(rope.get_from_widths(&[4, 135]), None);
+        assert!(rope.try_widths_to_indices(&[0, 136]).is_err());
+        assert!(rope.try_indices_to_widths(&[71, 0]).is_err());
+    }
+
+    #[test]
+    fn concat_01() {
+        let vec = lorem_ipsum();
+        let rope = Rope::concat(vec.chunks(3).map(Rope::from_slice));
+
+        assert_eq!(rope, vec.as_slice());
+        rope.assert_integrity();
+        rope.assert_invariants();
+
+        // Ropes of very different sizes, and empty ropes.
+        let ropes = [
+            Rope::new(),
+            Rope::from_slice(&vec[..1]),
+            Rope::from_slice(&vec[1..60]),
+            Rope::new(),
+            Rope::from_slice(&vec[60..62]),
+            Rope::from_slice(&vec[62..]),
+        ];
+        let rope = Rope::concat(ropes);
+
+        assert_eq!(rope, vec.as_slice());
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+
+    #[test]
+    fn concat_02() {
+        let rope: Rope<Lipsum> = Rope::concat([]);
+        assert!(rope.is_empty());
+
+        let rope: Rope<Lipsum> = Rope::concat([Rope::new(), Rope::new()]);
+        assert!(rope.is_empty());
+        rope.assert_integrity();
+    }
+
+    #[test]
+    fn concat_03() {
+        let vec = lorem_ipsum().repeat(20);
+        let rope = Rope::concat(vec.chunks(1).map(Rope::from_slice));
+
+        // The tree should be about as deep as one built in one go.
+        let built = Rope::from_slice(vec.as_slice());
+        assert!(rope.root.depth() <= built.root.depth() + 1);
+        assert_eq!(rope, built);
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+
+    #[test]
+    fn repeat_01() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+
+        for n in [0, 1, 2, 3, 7, 16, 37] {
+            let repeated = rope.repeat(n);
+            assert_eq!(repeated, lorem_ipsum().repeat(n).as_slice());
+            repeated.assert_integrity();
+            repeated.assert_invariants();
+        }
+
+        let rope = Rope::from_slice(SHORT_LOREM);
+        let repeated = rope.repeat(1000);
+        assert_eq!(repeated, SHORT_LOREM.repeat(1000).as_slice());
+        repeated.assert_integrity();
+        repeated.assert_invariants();
+
+        let empty: Rope<Lipsum> = Rope::new();
+        assert!(empty.repeat(10).is_empty());
+    }
+
+    /// Checks that the slices cover the [Rope<M>], and are cut at leaves.
+    fn check_split(rope: &Rope<Lipsum>, slices: &[RopeSlice<Lipsum>]) {
+        let mut index = 0;
+        for slice in slices {
+            assert!(!slice.is_empty());
+            assert_eq!(rope.chunk_at_index(index).1, index);
+            assert_eq!(*slice, rope.index_slice(index..index + slice.len()));
+            index += slice.len();
+        }
+        assert_eq!(index, rope.len());
+    }
+
+    #[test]
+    fn split_into_01() {
+        let rope = Rope::from_slice(lorem_ipsum().repeat(30).as_slice());
+
+        for n in [1, 2, 3, 4, 7, 10] {
+            let slices = rope.split_into(n);
+            assert_eq!(slices.len(), n);
+            check_split(&rope, &slices);
+
+            // No slice is off by more than a leaf from the ideal width.
+            let max_width = lorem_ipsum().iter().map(|m| m.width()).max().unwrap();
+            let leaf_width = max_len::<Lipsum, DefaultConfig>() * max_width;
+            for slice in &slices {
+                assert!(slice.width().abs_diff(rope.width() / n) <= leaf_width);
+            }
+
+            let slices = rope.split_into_index(n);
+            assert_eq!(slices.len(), n);
+            check_split(&rope, &slices);
+
+            let leaf_len = max_len::<Lipsum, DefaultConfig>();
+            for slice in &slices {
+                assert!(slice.len().abs_diff(rope.len() / n) <= leaf_len);
+            }
+        }
+    }
+
+    #[test]
+    fn split_into_02() {
+        // More parts than leaves.
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let slices = rope.split_into(1000);
+        assert!(slices.len() < 1000);
+        check_split(&rope, &slices);
+
+        let slices = rope.split_into_index(1000);
+        assert!(slices.len() < 1000);
+        check_split(&rope, &slices);
+
+        let rope: Rope<Lipsum> = Rope::new();
+        assert!(rope.split_into(4).is_empty());
+        assert!(rope.split_into_index(4).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_into_03() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        rope.split_into(0);
+    }
+
+    /// A [Rope<M>] with a lot of half empty leaves.
+    fn fragmented() -> Rope<Lipsum> {
+        let mut rope = Rope::from_slice(lorem_ipsum().repeat(20).as_slice());
+        for index in (0..rope.len()).rev().step_by(2) {
+            rope.remove_index_range(index, index + 1);
+        }
+        rope
+    }
+
+    #[test]
+    fn compact_range_01() {
+        let mut rope = fragmented();
+        let vec = Vec::from(&rope);
+        let before = rope.fragmentation();
+
+        rope.compact_range(..);
+        let after = rope.fragmentation();
+
+        assert_eq!(rope, vec.as_slice());
+        assert!(after.leaves < before.leaves);
+        // Only the last leaf of each parent should be left partially empty.
+        assert!(after.fill_ratio > 0.85);
+        assert!(after.leaves <= after.min_leaves + after.min_leaves / 5);
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+
+    #[test]
+    fn compact_range_02() {
+        let rope = fragmented();
+        let mut compacted = rope.clone();
+        let vec = Vec::from(&rope);
+
+        compacted.compact_range(10..60);
+
+        assert_eq!(compacted, vec.as_slice());
+        assert!(compacted.fragmentation().leaves < rope.fragmentation().leaves);
+        compacted.assert_integrity();
+        compacted.assert_invariants();
+
+        // Leaves far away from the range are still shared.
+        let (chunk, ..) = rope.chunk_at_index(rope.len() - 1);
+        let (compacted_chunk, ..) = compacted.chunk_at_index(rope.len() - 1);
+        assert_eq!(chunk.as_ptr(), compacted_chunk.as_ptr());
+    }
+
+    #[test]
+    fn compact_range_03() {
+        let mut rope = fragmented();
+        let vec = Vec::from(&rope);
+
+        for start in (0..rope.width()).step_by(37) {
+            rope.compact_range(start..(start + 20).min(rope.width()));
+            assert_eq!(rope, vec.as_slice());
+            rope.assert_integrity();
+            rope.assert_invariants();
+        }
+
+        assert!(rope.try_compact_range(10..rope.width() + 1).is_err());
+        let mut empty: Rope<Lipsum> = Rope::new();
+        empty.compact_range(..);
+        assert_eq!(empty.fragmentation().leaves, 1);
+    }
+
+    #[test]
+    fn stats_01() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let stats = rope.stats();
+
+        assert_eq!(stats.depth, rope.root.depth());
+        assert_eq!(stats.leaves, rope.fragmentation().leaves);
+        assert!(stats.branches > 0);
+        assert_eq!(stats.occupied, rope.len());
+        assert!(stats.allocated >= stats.occupied);
+        assert_eq!(rope.unique_bytes(), stats.heap_bytes);
+    }
+
+    #[test]
+    fn stats_02() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let repeated = rope.repeat(4);
+
+        // The repeated leaves are shared, so they are only counted once.
+        assert_eq!(repeated.stats().leaves, rope.stats().leaves);
+        assert_eq!(repeated.stats().occupied, rope.len());
+    }
+
+    #[test]
+    fn unique_bytes_01() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let heap_bytes = rope.stats().heap_bytes;
+
+        let clone = rope.clone();
+        assert_eq!(rope.unique_bytes(), 0);
+        assert_eq!(clone.unique_bytes(), 0);
+
+        rope.insert(20, Sit);
+        let unique = rope.unique_bytes();
+        assert!(unique > 0);
+        assert!(unique < heap_bytes / 2);
+        assert!(clone.unique_bytes() > 0);
+
+        drop(clone);
+        assert_eq!(rope.unique_bytes(), rope.stats().heap_bytes);
+    }
+
+    #[test]
+    fn unique_bytes_02() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let repeated = rope.repeat(4);
+
+        // The repeated nodes are also held by `rope`.
+        assert!(repeated.unique_bytes() < repeated.stats().heap_bytes);
+
+        // Once they aren't, they are freed along with `repeated`.
+        drop(rope);
+        assert_eq!(repeated.unique_bytes(), repeated.stats().heap_bytes);
+
+        let clone = repeated.clone();
+        assert_eq!(repeated.unique_bytes(), 0);
+        drop(clone);
+
+        let mut sliced = repeated.clone();
+        sliced.remove(0..10);
+        assert!(sliced.unique_bytes() > 0);
+        assert!(sliced.unique_bytes() < sliced.stats().heap_bytes);
+    }
+
+    #[test]
+    fn apply_edits_01() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let mut expected = rope.clone();
+
+        let map = rope.apply_edits([
+            Edit::Remove(100..120),
+            Edit::Insert(3, SHORT_LOREM.to_vec()),
+            Edit::Replace(10..30, vec![Dolor(7)]),
+            Edit::Insert(3, vec![Lorem]),
+        ]);
+
+        // The replacement goes where the removed elements started.
+        let (start, _) = expected.width_range_to_index_range(10..30).unwrap();
+        expected.remove(100..120);
+        expected.remove(10..30);
+        expected.insert_internal_at_index(start, &[Dolor(7)]);
+        expected.insert_slice(3, &[Lorem]);
+        expected.insert_slice(3, SHORT_LOREM);
+
+        assert_eq!(rope, expected);
+        assert_eq!(map.map_width(0), 0);
+        assert_eq!(map.map_width(3), 10);
+        assert_eq!(map.map_width(90) - map.map_width(80), 10);
+        assert_eq!(map.map_width(135), rope.width());
+        assert_eq!(map.map_index(70), rope.len());
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+
+    #[test]
+    fn apply_edits_02() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let copy = rope.clone();
+
+        assert!(rope.try_apply_edits([]).is_ok());
+        assert_eq!(rope, copy);
+
+        // The edits overlap, so nothing should be applied.
+        let edits = [
+            Edit::Remove(10..20),
+            Edit::Insert(0, vec![Lorem]),
+            Edit::Insert(15, vec![Lorem]),
+        ];
+        assert!(rope.try_apply_edits(edits).is_err());
+        assert!(rope.try_apply_edits([Edit::Remove(10..136)]).is_err());
+        assert!(rope.try_apply_edits([Edit::Insert(136, vec![])]).is_err());
+        assert_eq!(rope, copy);
+
+        rope.apply_edits([Edit::Remove(0..135)]);
+        assert!(rope.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn batched_lookups_03() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        rope.widths_to_indices(&[0, 10, 136]);
+    }
+
     // Iterator tests are in the iter module
 }
@@ -1,9 +1,8 @@
 use std::sync::Arc;
 
-use smallvec::SmallVec;
-
+use crate::config::{DefaultConfig, RopeConfig};
 use crate::rope::{Measurable, Rope};
-use crate::tree::{BranchChildren, LeafSlice, Node, MAX_LEN, MAX_CHILDREN, MIN_LEN};
+use crate::tree::{max_children, max_len, min_len, BranchChildren, LeafSlice, Node};
 
 /// An efficient incremental [Rope<M>] builder.
 ///
@@ -32,11 +31,12 @@ use crate::tree::{BranchChildren, LeafSlice, Node, MAX_LEN, MAX_CHILDREN, MIN_LE
 /// assert_eq!(rope, [Lorem, Ipsum, Dolor(70), Sit, Amet].as_slice());
 /// ```
 #[derive(Debug, Clone)]
-pub struct RopeBuilder<M>
+pub struct RopeBuilder<M, C = DefaultConfig>
 where
     M: Measurable,
+    C: RopeConfig,
 {
-    stack: SmallVec<[Arc<Node<M>>; 4]>,
+    stack: Vec<Arc<Node<M, C>>>,
     buffer: Vec<M>,
     last_chunk_len: usize,
 }
@@ -47,12 +47,20 @@ where
 {
     /// Creates a new RopeBuilder, ready for input.
     pub fn new() -> Self {
+        Self::new_with_config()
+    }
+}
+
+impl<M, C> RopeBuilder<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    /// Creates a new RopeBuilder for a [Rope<M, C>], with a given
+    /// [RopeConfig], ready for input.
+    pub fn new_with_config() -> Self {
         RopeBuilder {
-            stack: {
-                let mut stack = SmallVec::new();
-                stack.push(Arc::new(Node::new()));
-                stack
-            },
+            stack: vec![Arc::new(Node::new())],
             buffer: Vec::new(),
             last_chunk_len: 0,
         }
@@ -79,7 +87,7 @@ where
     /// Note: this method consumes the builder. If you want to continue
     /// building other ropes with the same prefix, you can clone the builder
     /// before calling this function.
-    pub fn finish(mut self) -> Rope<M> {
+    pub fn finish(mut self) -> Rope<M, C> {
         // Append the last leaf
         self.append_internal(&[], true);
         self.finish_internal(true)
@@ -90,7 +98,7 @@ where
     /// This avoids the creation and use of the internal buffer. This is
     /// for internal use only, because the public-facing API has
     /// [Rope::from_slice()], which actually uses this for its implementation.
-    pub(crate) fn build_at_once(mut self, chunk: &[M]) -> Rope<M> {
+    pub(crate) fn build_at_once(mut self, chunk: &[M]) -> Rope<M, C> {
         self.append_internal(chunk, true);
         self.finish_internal(true)
     }
@@ -115,7 +123,7 @@ where
     /// to the btree invariants. To be used with [RopeBuilder::append_chunk()] to
     /// construct ropes with specific chunk boundaries for testing.
     #[doc(hidden)]
-    pub fn _finish_no_fix(self) -> Rope<M> {
+    pub fn _finish_no_fix(self) -> Rope<M, C> {
         self.finish_internal(false)
     }
 
@@ -154,7 +162,7 @@ where
     // When `fix_tree` is false, the resulting node tree is NOT fixed up
     // to adhere to the btree invariants. This is useful for some testing
     // code. But generally, `fix_tree` should be set to true.
-    fn finish_internal(mut self, fix_tree: bool) -> Rope<M> {
+    fn finish_internal(mut self, fix_tree: bool) -> Rope<M, C> {
         // Zip up all the remaining nodes on the stack
         let mut stack_index = self.stack.len() - 1;
         while stack_index >= 1 {
@@ -176,7 +184,7 @@ where
         // Fix up the tree to be well-formed.
         if fix_tree {
             Arc::make_mut(&mut rope.root).zip_fix_right();
-            if self.last_chunk_len < MIN_LEN && self.last_chunk_len != rope.len() {
+            if self.last_chunk_len < min_len::<M, C>() && self.last_chunk_len != rope.len() {
                 // Merge the last chunk if it was too small.
                 let index = rope.width() - rope.index_to_width(rope.len() - self.last_chunk_len);
                 Arc::make_mut(&mut rope.root).fix_tree_seam(index);
@@ -195,15 +203,15 @@ where
         is_last_chunk: bool,
     ) -> (NextSlice<'a, M>, &'a [M]) {
         assert!(
-            self.buffer.len() < MAX_LEN,
+            self.buffer.len() < max_len::<M, C>(),
             "RopeBuilder: buffer is already full when receiving a chunk! \
              This should never happen!",
         );
 
         // Simplest case: empty buffer and enough in `slice` for a full
         // chunk, so just chop a chunk off from `slice` and use that.
-        if self.buffer.is_empty() && slice.len() >= MAX_LEN {
-            let split_index = MAX_LEN.min(slice.len() - 1);
+        if self.buffer.is_empty() && slice.len() >= max_len::<M, C>() {
+            let split_index = max_len::<M, C>().min(slice.len() - 1);
             return (
                 NextSlice::Slice(&slice[..split_index]),
                 &slice[split_index..],
@@ -211,8 +219,8 @@ where
         }
         // If the buffer + `slice` is enough for a full chunk, push enough
         // of `slice` onto the buffer to fill it and use that.
-        else if (slice.len() + self.buffer.len()) >= MAX_LEN {
-            let split_index = MAX_LEN - self.buffer.len();
+        else if (slice.len() + self.buffer.len()) >= max_len::<M, C>() {
+            let split_index = max_len::<M, C>() - self.buffer.len();
             self.buffer.extend_from_slice(&slice[..split_index]);
             return (NextSlice::UseBuffer, &slice[split_index..]);
         }
@@ -239,14 +247,14 @@ where
         }
     }
 
-    fn append_leaf_node(&mut self, leaf: Arc<Node<M>>) {
+    fn append_leaf_node(&mut self, leaf: Arc<Node<M, C>>) {
         let last = self.stack.pop().unwrap();
         match *last {
             Node::Leaf(_) => {
                 if last.leaf_slice().is_empty() {
                     self.stack.push(leaf);
                 } else {
-                    let mut children = BranchChildren::new();
+                    let mut children = BranchChildren::new_with_config();
                     children.push((last.slice_info(), last));
                     children.push((leaf.slice_info(), leaf));
                     self.stack.push(Arc::new(Node::Branch(children)));
@@ -260,11 +268,13 @@ where
                 loop {
                     if stack_index < 0 {
                         // We're above the root, so do a root split.
-                        let mut children = BranchChildren::new();
+                        let mut children = BranchChildren::new_with_config();
                         children.push((left.slice_info(), left));
                         self.stack.insert(0, Arc::new(Node::Branch(children)));
                         break;
-                    } else if self.stack[stack_index as usize].child_count() < (MAX_CHILDREN - 1) {
+                    } else if self.stack[stack_index as usize].child_count()
+                        < (max_children::<C>() - 1)
+                    {
                         // There's room to add a child, so do that.
                         Arc::make_mut(&mut self.stack[stack_index as usize])
                             .children_mut()
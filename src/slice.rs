@@ -1,8 +1,9 @@
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 use std::ops::RangeBounds;
 use std::sync::Arc;
 
-use crate::iter::{Chunks, Iter};
+use crate::config::{DefaultConfig, RopeConfig};
+use crate::iter::{Chunks, IntoIter, Iter, OwnedChunks};
 use crate::rope::{Measurable, Rope};
 use crate::slice_utils::{end_width_to_index, index_to_width, start_width_to_index, width_of};
 use crate::tree::{Count, Node, SliceInfo};
@@ -19,17 +20,19 @@ use crate::{end_bound_to_num, start_bound_to_num, Error, Result};
 /// of a full [Rope<M>] created from the same slice range. Nothing should be
 /// surprising here.
 #[derive(Copy, Clone)]
-pub struct RopeSlice<'a, M>(pub(crate) RSEnum<'a, M>)
+pub struct RopeSlice<'a, M, C = DefaultConfig>(pub(crate) RSEnum<'a, M, C>)
 where
-    M: Measurable;
+    M: Measurable,
+    C: RopeConfig;
 
 #[derive(Copy, Clone, Debug)]
-pub(crate) enum RSEnum<'a, M>
+pub(crate) enum RSEnum<'a, M, C>
 where
     M: Measurable,
+    C: RopeConfig,
 {
     Full {
-        node: &'a Arc<Node<M>>,
+        node: &'a Arc<Node<M, C>>,
         start_info: SliceInfo,
         end_info: SliceInfo,
     },
@@ -38,9 +41,10 @@ where
     },
 }
 
-impl<'a, M> RopeSlice<'a, M>
+impl<'a, M, C> RopeSlice<'a, M, C>
 where
     M: Measurable,
+    C: RopeConfig,
 {
     /// Used for tests and debugging purposes.
     #[allow(dead_code)]
@@ -48,7 +52,7 @@ where
         matches!(&self.0, RSEnum::Light { .. })
     }
 
-    pub(crate) fn new_with_range(node: &'a Arc<Node<M>>, start: usize, end: usize) -> Self {
+    pub(crate) fn new_with_range(node: &'a Arc<Node<M, C>>, start: usize, end: usize) -> Self {
         assert!(start <= end);
         assert!(end <= node.slice_info().width as usize);
 
@@ -74,7 +78,7 @@ where
         let mut n_end = end;
         let mut node = node;
         'outer: loop {
-            match *(node as &Node<M>) {
+            match *(node as &Node<M, C>) {
                 // Early out if we reach a leaf, because we can do the
                 // simpler lightweight slice then.
                 Node::Leaf(ref slice) => {
@@ -114,7 +118,7 @@ where
     }
 
     pub(crate) fn new_with_index_range(
-        node: &'a Arc<Node<M>>,
+        node: &'a Arc<Node<M, C>>,
         start: usize,
         end: usize,
     ) -> Result<Self> {
@@ -132,7 +136,7 @@ where
                     start_info: SliceInfo { len: 0, width: 0 },
                     end_info: SliceInfo {
                         len: node.len() as Count,
-                        width: node.len() as Count,
+                        width: node.width() as Count,
                     },
                 }));
             }
@@ -143,7 +147,7 @@ where
         let mut n_end = end;
         let mut node = node;
         'outer: loop {
-            match *(node as &Node<M>) {
+            match *(node as &Node<M, C>) {
                 // Early out if we reach a leaf, because we can do the
                 // simpler lightweight slice then.
                 Node::Leaf(ref slice) => {
@@ -178,6 +182,18 @@ where
         }))
     }
 
+    /// Creates a [RopeSlice<M, C>] directly from a [`&[M]`][Measurable]
+    /// slice, with a given [RopeConfig].
+    ///
+    /// This is what [From<&[M]>][From] does for [RopeSlice<M>], for other
+    /// configs.
+    ///
+    /// Runs in O(1) time.
+    #[inline]
+    pub fn from_slice_with_config(slice: &'a [M]) -> Self {
+        RopeSlice(RSEnum::Light { slice })
+    }
+
     //-----------------------------------------------------------------------
     // Informational methods
 
@@ -211,6 +227,14 @@ where
         }
     }
 
+    /// Returns `true` if the [RopeSlice<M>] has no elements.
+    ///
+    /// Runs in O(1) time.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     //-----------------------------------------------------------------------
     // Index conversion methods
 
@@ -464,7 +488,7 @@ where
     ///
     /// Runs in O(log N) time.
     #[inline]
-    pub fn iter(&self) -> Iter<'a, M> {
+    pub fn iter(&self) -> Iter<'a, M, C> {
         match *self {
             RopeSlice(RSEnum::Full {
                 node,
@@ -475,7 +499,7 @@ where
                 (start_info.len as usize, end_info.len as usize),
                 (start_info.width as usize, end_info.width as usize),
             ),
-            RopeSlice(RSEnum::Light { slice, .. }) => Iter::from_slice(slice),
+            RopeSlice(RSEnum::Light { slice, .. }) => Iter::from_slice_with_config(slice),
         }
     }
 
@@ -495,7 +519,7 @@ where
     ///
     /// Panics if the `width` is out of bounds (i.e. `width > RopeSlice::width()`).
     #[inline]
-    pub fn iter_at(&self, index: usize) -> Iter<'a, M> {
+    pub fn iter_at(&self, index: usize) -> Iter<'a, M, C> {
         if let Some(out) = self.get_iter_at(index) {
             out
         } else {
@@ -511,7 +535,7 @@ where
     ///
     /// Runs in O(log N) time.
     #[inline]
-    pub fn chunks(&self) -> Chunks<'a, M> {
+    pub fn chunks(&self) -> Chunks<'a, M, C> {
         match *self {
             RopeSlice(RSEnum::Full {
                 node,
@@ -522,7 +546,7 @@ where
                 (start_info.len as usize, end_info.len as usize),
                 (start_info.width as usize, end_info.width as usize),
             ),
-            RopeSlice(RSEnum::Light { slice, .. }) => Chunks::from_slice(slice, false),
+            RopeSlice(RSEnum::Light { slice, .. }) => Chunks::from_slice_with_config(slice, false),
         }
     }
 
@@ -543,7 +567,7 @@ where
     ///
     /// Panics if the `index` is out of bounds (i.e. `index > RopeSlice::len()`).
     #[inline]
-    pub fn chunks_at_index(&self, index: usize) -> (Chunks<'a, M>, usize, usize) {
+    pub fn chunks_at_index(&self, index: usize) -> (Chunks<'a, M, C>, usize, usize) {
         if let Some(out) = self.get_chunks_at_index(index) {
             out
         } else {
@@ -572,7 +596,7 @@ where
     ///
     /// Panics if the `width` is out of bounds (i.e. `width > RopeSlice::width()`).
     #[inline]
-    pub fn chunks_at_width(&self, width_index: usize) -> (Chunks<'a, M>, usize, usize) {
+    pub fn chunks_at_width(&self, width_index: usize) -> (Chunks<'a, M, C>, usize, usize) {
         if let Some(out) = self.get_chunks_at_width(width_index) {
             out
         } else {
@@ -590,9 +614,10 @@ where
 /// The methods in this impl block provide non-panicking versions of
 /// [RopeSlice<M>]'s panicking methods. They return either `Option::None` or
 /// `Result::Err()` when their panicking counterparts would have panicked.
-impl<'a, M> RopeSlice<'a, M>
+impl<'a, M, C> RopeSlice<'a, M, C>
 where
     M: Measurable,
+    C: RopeConfig,
 {
     /// Non-panicking version of [index_to_width()][RopeSlice::index_to_width].
     #[inline]
@@ -623,8 +648,19 @@ where
     pub fn try_end_width_to_index(&self, width: usize) -> Result<usize> {
         // Bounds check
         if width <= self.width() {
-            let (chunk, b, c) = self.chunk_at_width(width);
-            Ok(b + end_width_to_index(chunk, width - c))
+            match *self {
+                RopeSlice(RSEnum::Full {
+                    node,
+                    start_info,
+                    end_info,
+                }) => {
+                    // The elements at `width` may go on into the next chunk,
+                    // so the search starts from the last one there.
+                    let info = node.end_width_to_slice_info(width + start_info.width as usize);
+                    Ok(info.len.min(end_info.len).saturating_sub(start_info.len) as usize)
+                }
+                RopeSlice(RSEnum::Light { slice }) => Ok(end_width_to_index(slice, width)),
+            }
         } else {
             Err(Error::WidthOutOfBounds(width, self.width()))
         }
@@ -726,7 +762,7 @@ where
     }
 
     /// Non-panicking version of [width_slice()][RopeSlice::width_slice].
-    pub fn get_width_slice<R>(&self, width_range: R) -> Option<RopeSlice<'a, M>>
+    pub fn get_width_slice<R>(&self, width_range: R) -> Option<RopeSlice<'a, M, C>>
     where
         R: RangeBounds<usize>,
     {
@@ -768,14 +804,14 @@ where
     }
 
     /// Non-panicking version of [index_slice()][RopeSlice::index_slice].
-    pub fn get_index_slice<R>(&self, index_range: R) -> Option<RopeSlice<'a, M>>
+    pub fn get_index_slice<R>(&self, index_range: R) -> Option<RopeSlice<'a, M, C>>
     where
         R: RangeBounds<usize>,
     {
         self.get_slice_impl(index_range).ok()
     }
 
-    pub(crate) fn get_slice_impl<R>(&self, index_range: R) -> Result<RopeSlice<'a, M>>
+    pub(crate) fn get_slice_impl<R>(&self, index_range: R) -> Result<RopeSlice<'a, M, C>>
     where
         R: RangeBounds<usize>,
     {
@@ -841,7 +877,7 @@ where
 
     /// Non-panicking version of [iter_at()][RopeSlice::iter_at].
     #[inline]
-    pub fn get_iter_at(&self, width: usize) -> Option<Iter<'a, M>> {
+    pub fn get_iter_at(&self, width: usize) -> Option<Iter<'a, M, C>> {
         // Bounds check
         if width <= self.width() {
             match *self {
@@ -864,7 +900,7 @@ where
 
     /// Non-panicking version of [chunks_at_index()][RopeSlice::chunks_at_index].
     #[inline]
-    pub fn get_chunks_at_index(&self, index: usize) -> Option<(Chunks<'a, M>, usize, usize)> {
+    pub fn get_chunks_at_index(&self, index: usize) -> Option<(Chunks<'a, M, C>, usize, usize)> {
         // Bounds check
         if index <= self.len() {
             match *self {
@@ -887,7 +923,7 @@ where
                     ))
                 }
                 RopeSlice(RSEnum::Light { slice }) => {
-                    let chunks = Chunks::from_slice(slice, index == slice.len());
+                    let chunks = Chunks::from_slice_with_config(slice, index == slice.len());
 
                     if index == slice.len() {
                         Some((chunks, slice.len(), width_of(slice)))
@@ -903,7 +939,7 @@ where
 
     /// Non-panicking version of [chunks_at_width()][RopeSlice::chunks_at_width].
     #[inline]
-    pub fn get_chunks_at_width(&self, width: usize) -> Option<(Chunks<'a, M>, usize, usize)> {
+    pub fn get_chunks_at_width(&self, width: usize) -> Option<(Chunks<'a, M, C>, usize, usize)> {
         // Bounds check
         if width <= self.width() {
             match *self {
@@ -927,7 +963,7 @@ where
                 }
                 RopeSlice(RSEnum::Light { slice, .. }) => {
                     let slice_width = width_of(slice);
-                    let chunks = Chunks::from_slice(slice, width == slice_width);
+                    let chunks = Chunks::from_slice_with_config(slice, width == slice_width);
 
                     if width == slice_width as usize {
                         Some((chunks, slice.len(), slice_width))
@@ -942,6 +978,271 @@ where
     }
 }
 
+//==============================================================
+// Owned slices
+
+/// An owned, immutable view into part of a [Rope<M>].
+///
+/// This is the owned counterpart of [RopeSlice<M>]. Instead of borrowing the
+/// [Rope<M>] it was created from, it shares its data, so it can be stored
+/// alongside the [Rope<M>], or sent to another thread, while that [Rope<M>] is
+/// edited. Since the data is copied on write, edits to the [Rope<M>] are never
+/// seen by the [OwnedRopeSlice<M>].
+///
+/// The full [RopeSlice<M>] API is available through
+/// [as_rope_slice()][OwnedRopeSlice::as_rope_slice].
+///
+/// # Example
+///
+/// ```
+/// # use any_rope::{OwnedRopeSlice, Rope};
+/// # use any_rope::Lipsum::*;
+/// let mut rope = Rope::from_slice(&[Lorem, Ipsum, Dolor(3), Sit, Amet]);
+/// let slice = rope.owned_width_slice(1..3);
+///
+/// rope.remove(..);
+///
+/// let handle = std::thread::spawn(move || slice.into_iter().count());
+/// assert_eq!(handle.join().unwrap(), 1);
+/// ```
+#[derive(Clone)]
+pub struct OwnedRopeSlice<M, C = DefaultConfig>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    node: Arc<Node<M, C>>,
+    start_info: SliceInfo,
+    end_info: SliceInfo,
+}
+
+impl<M, C> OwnedRopeSlice<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    pub(crate) fn new_with_info(
+        node: Arc<Node<M, C>>,
+        start_info: SliceInfo,
+        end_info: SliceInfo,
+    ) -> Self {
+        OwnedRopeSlice {
+            node,
+            start_info,
+            end_info,
+        }
+    }
+
+    /// Total number of elements in the [OwnedRopeSlice<M>].
+    ///
+    /// Runs in O(1) time.
+    #[inline]
+    pub fn len(&self) -> usize {
+        (self.end_info.len - self.start_info.len) as usize
+    }
+
+    /// Returns `true` if the [OwnedRopeSlice<M>] has no elements.
+    ///
+    /// Runs in O(1) time.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Sum of all widths of in [OwnedRopeSlice<M>].
+    ///
+    /// Runs in O(1) time.
+    #[inline]
+    pub fn width(&self) -> usize {
+        (self.end_info.width - self.start_info.width) as usize
+    }
+
+    /// Borrows the [OwnedRopeSlice<M>] as a [RopeSlice<M>].
+    ///
+    /// Runs in O(1) time.
+    #[inline]
+    pub fn as_rope_slice(&self) -> RopeSlice<'_, M, C> {
+        if let Node::Leaf(ref slice) = *self.node {
+            let slice = &slice[self.start_info.len as usize..self.end_info.len as usize];
+            RopeSlice(RSEnum::Light { slice })
+        } else {
+            RopeSlice(RSEnum::Full {
+                node: &self.node,
+                start_info: self.start_info,
+                end_info: self.end_info,
+            })
+        }
+    }
+
+    /// Creates an iterator over the [OwnedRopeSlice<M>].
+    ///
+    /// Runs in O(log N) time.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, M, C> {
+        self.as_rope_slice().iter()
+    }
+
+    /// Creates an iterator over the chunks of the [OwnedRopeSlice<M>].
+    ///
+    /// Runs in O(log N) time.
+    #[inline]
+    pub fn chunks(&self) -> Chunks<'_, M, C> {
+        self.as_rope_slice().chunks()
+    }
+
+    /// Creates an owned iterator over the chunks of the [OwnedRopeSlice<M>].
+    ///
+    /// Runs in O(1) time.
+    #[inline]
+    pub fn into_chunks(self) -> OwnedChunks<M, C> {
+        let index_range = (self.start_info.len as usize, self.end_info.len as usize);
+        OwnedChunks::new_with_range(self.node, index_range)
+    }
+}
+
+impl<M, C> IntoIterator for OwnedRopeSlice<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    type Item = (usize, M);
+    type IntoIter = IntoIter<M, C>;
+
+    /// Creates an owned iterator over the [OwnedRopeSlice<M>].
+    ///
+    /// Runs in O(1) time.
+    #[inline]
+    fn into_iter(self) -> IntoIter<M, C> {
+        let index_range = (self.start_info.len as usize, self.end_info.len as usize);
+        IntoIter::new_with_range(self.node, index_range)
+    }
+}
+
+/// Will share data where possible.
+///
+/// Runs in O(1) time if the [RopeSlice<M>] spans more than one chunk. Otherwise,
+/// the elements of the [RopeSlice<M>] are copied.
+impl<'a, M, C> From<RopeSlice<'a, M, C>> for OwnedRopeSlice<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    fn from(s: RopeSlice<'a, M, C>) -> Self {
+        match s {
+            RopeSlice(RSEnum::Full {
+                node,
+                start_info,
+                end_info,
+            }) => OwnedRopeSlice::new_with_info(Arc::clone(node), start_info, end_info),
+            RopeSlice(RSEnum::Light { slice }) => {
+                let rope = Rope::from_slice_with_config(slice);
+                let end_info = rope.root.slice_info();
+                OwnedRopeSlice::new_with_info(rope.root, SliceInfo::new(), end_info)
+            }
+        }
+    }
+}
+
+/// Runs in O(1) time.
+impl<M, C> From<Rope<M, C>> for OwnedRopeSlice<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    #[inline]
+    fn from(r: Rope<M, C>) -> Self {
+        let end_info = r.root.slice_info();
+        OwnedRopeSlice::new_with_info(r.root, SliceInfo::new(), end_info)
+    }
+}
+
+/// Will share data where possible.
+///
+/// Runs in O(log N) time.
+impl<M, C> From<OwnedRopeSlice<M, C>> for Rope<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    #[inline]
+    fn from(s: OwnedRopeSlice<M, C>) -> Self {
+        Rope::from(s.as_rope_slice())
+    }
+}
+
+impl<M, C> std::fmt::Debug for OwnedRopeSlice<M, C>
+where
+    M: Measurable + Debug,
+    C: RopeConfig,
+{
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&self.as_rope_slice(), f)
+    }
+}
+
+impl<M, C> std::fmt::Display for OwnedRopeSlice<M, C>
+where
+    M: Measurable + Display,
+    C: RopeConfig,
+{
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.as_rope_slice(), f)
+    }
+}
+
+impl<M, C> std::hash::Hash for OwnedRopeSlice<M, C>
+where
+    M: Measurable + std::hash::Hash,
+    C: RopeConfig,
+{
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_rope_slice().hash(state)
+    }
+}
+
+impl<M, C> std::cmp::Eq for OwnedRopeSlice<M, C>
+where
+    M: Measurable + Eq,
+    C: RopeConfig,
+{
+}
+
+impl<M, C> std::cmp::PartialEq<OwnedRopeSlice<M, C>> for OwnedRopeSlice<M, C>
+where
+    M: Measurable + PartialEq,
+    C: RopeConfig,
+{
+    #[inline]
+    fn eq(&self, other: &OwnedRopeSlice<M, C>) -> bool {
+        self.as_rope_slice() == other.as_rope_slice()
+    }
+}
+
+impl<'a, M, C> std::cmp::PartialEq<RopeSlice<'a, M, C>> for OwnedRopeSlice<M, C>
+where
+    M: Measurable + PartialEq,
+    C: RopeConfig,
+{
+    #[inline]
+    fn eq(&self, other: &RopeSlice<'a, M, C>) -> bool {
+        self.as_rope_slice() == *other
+    }
+}
+
+impl<'a, M, C> std::cmp::PartialEq<&'a [M]> for OwnedRopeSlice<M, C>
+where
+    M: Measurable + PartialEq,
+    C: RopeConfig,
+{
+    #[inline]
+    fn eq(&self, other: &&'a [M]) -> bool {
+        self.as_rope_slice() == *other
+    }
+}
+
 //==============================================================
 // Conversion impls
 
@@ -965,16 +1266,17 @@ where
 {
     #[inline]
     fn from(slice: &'a [M]) -> Self {
-        RopeSlice(RSEnum::Light { slice })
+        RopeSlice::from_slice_with_config(slice)
     }
 }
 
-impl<'a, M> From<RopeSlice<'a, M>> for Vec<M>
+impl<'a, M, C> From<RopeSlice<'a, M, C>> for Vec<M>
 where
     M: Measurable,
+    C: RopeConfig,
 {
     #[inline]
-    fn from(s: RopeSlice<'a, M>) -> Self {
+    fn from(s: RopeSlice<'a, M, C>) -> Self {
         let mut vec = Vec::with_capacity(s.len());
         vec.extend(
             s.chunks()
@@ -990,12 +1292,13 @@ where
 /// owned [Vec<M>] if the contents is not contiguous in memory.
 ///
 /// Runs in best case O(1), worst case O(N).
-impl<'a, M> From<RopeSlice<'a, M>> for std::borrow::Cow<'a, [M]>
+impl<'a, M, C> From<RopeSlice<'a, M, C>> for std::borrow::Cow<'a, [M]>
 where
     M: Measurable,
+    C: RopeConfig,
 {
     #[inline]
-    fn from(s: RopeSlice<'a, M>) -> Self {
+    fn from(s: RopeSlice<'a, M, C>) -> Self {
         if let Some(slice) = s.as_slice() {
             std::borrow::Cow::Borrowed(slice)
         } else {
@@ -1007,80 +1310,59 @@ where
 //==============================================================
 // Other impls
 
-impl<'a, M> std::fmt::Debug for RopeSlice<'a, M>
+/// Prints the elements of the [RopeSlice<M>] as a list, no matter how they
+/// are split into chunks, so equal [RopeSlice<M>]s always print the same way.
+///
+/// See [debug_chunks()][RopeSlice::debug_chunks] and
+/// [debug_widths()][RopeSlice::debug_widths] for other ways to print them.
+impl<'a, M, C> std::fmt::Debug for RopeSlice<'a, M, C>
 where
     M: Measurable + Debug,
+    C: RopeConfig,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.debug_list().entries(self.chunks()).finish()
+        f.debug_list().entries(self.chunks().flatten()).finish()
     }
 }
 
-impl<'a, M> std::fmt::Display for RopeSlice<'a, M>
+/// Prints the elements of the [RopeSlice<M>] one after the other, with
+/// nothing in between them.
+impl<'a, M, C> std::fmt::Display for RopeSlice<'a, M, C>
 where
-    M: Measurable + Debug,
+    M: Measurable + Display,
+    C: RopeConfig,
 {
-    #[inline]
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.debug_list().entries(self.chunks()).finish()
+        self.chunks()
+            .flatten()
+            .try_for_each(|measurable| Display::fmt(measurable, f))
     }
 }
 
-impl<'a, M> std::cmp::Eq for RopeSlice<'a, M> where M: Measurable + Eq {}
+impl<'a, M, C> std::cmp::Eq for RopeSlice<'a, M, C>
+where
+    M: Measurable + Eq,
+    C: RopeConfig,
+{
+}
 
-impl<'a, 'b, M> std::cmp::PartialEq<RopeSlice<'b, M>> for RopeSlice<'a, M>
+/// Skips over the subtrees that both [RopeSlice<M>]s share, so comparing
+/// two versions of the same [Rope<M>] only looks at the parts that differ.
+impl<'a, 'b, M, C> std::cmp::PartialEq<RopeSlice<'b, M, C>> for RopeSlice<'a, M, C>
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
-    fn eq(&self, other: &RopeSlice<'b, M>) -> bool {
-        if self.len() != other.len() {
-            return false;
-        }
-
-        let mut chunk_itr_1 = self.chunks();
-        let mut chunk_itr_2 = other.chunks();
-        let mut chunk1 = chunk_itr_1.next().unwrap_or(&[]);
-        let mut chunk2 = chunk_itr_2.next().unwrap_or(&[]);
-
-        loop {
-            if chunk1.len() > chunk2.len() {
-                if &chunk1[..chunk2.len()] != chunk2 {
-                    return false;
-                } else {
-                    chunk1 = &chunk1[chunk2.len()..];
-                    chunk2 = &[];
-                }
-            } else if &chunk2[..chunk1.len()] != chunk1 {
-                return false;
-            } else {
-                chunk2 = &chunk2[chunk1.len()..];
-                chunk1 = &[];
-            }
-
-            if chunk1.is_empty() {
-                if let Some(chunk) = chunk_itr_1.next() {
-                    chunk1 = chunk;
-                } else {
-                    break;
-                }
-            }
-
-            if chunk2.is_empty() {
-                if let Some(chunk) = chunk_itr_2.next() {
-                    chunk2 = chunk;
-                } else {
-                    break;
-                }
-            }
-        }
-
-        return true;
+    #[inline]
+    fn eq(&self, other: &RopeSlice<'b, M, C>) -> bool {
+        self.len() == other.len() && self.common_prefix_len(other) == self.len()
     }
 }
 
-impl<'a, 'b, M> std::cmp::PartialEq<&'b [M]> for RopeSlice<'a, M>
+impl<'a, 'b, M, C> std::cmp::PartialEq<&'b [M]> for RopeSlice<'a, M, C>
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
     #[inline]
     fn eq(&self, other: &&'b [M]) -> bool {
@@ -1107,19 +1389,21 @@ where
     }
 }
 
-impl<'a, 'b, M> std::cmp::PartialEq<RopeSlice<'a, M>> for &'b [M]
+impl<'a, 'b, M, C> std::cmp::PartialEq<RopeSlice<'a, M, C>> for &'b [M]
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
     #[inline]
-    fn eq(&self, other: &RopeSlice<'a, M>) -> bool {
+    fn eq(&self, other: &RopeSlice<'a, M, C>) -> bool {
         other == self
     }
 }
 
-impl<'a, M> std::cmp::PartialEq<[M]> for RopeSlice<'a, M>
+impl<'a, M, C> std::cmp::PartialEq<[M]> for RopeSlice<'a, M, C>
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
     #[inline]
     fn eq(&self, other: &[M]) -> bool {
@@ -1127,19 +1411,21 @@ where
     }
 }
 
-impl<'a, M> std::cmp::PartialEq<RopeSlice<'a, M>> for [M]
+impl<'a, M, C> std::cmp::PartialEq<RopeSlice<'a, M, C>> for [M]
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
     #[inline]
-    fn eq(&self, other: &RopeSlice<'a, M>) -> bool {
+    fn eq(&self, other: &RopeSlice<'a, M, C>) -> bool {
         std::cmp::PartialEq::<&[M]>::eq(other, &self)
     }
 }
 
-impl<'a, M> std::cmp::PartialEq<Vec<M>> for RopeSlice<'a, M>
+impl<'a, M, C> std::cmp::PartialEq<Vec<M>> for RopeSlice<'a, M, C>
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
     #[inline]
     fn eq(&self, other: &Vec<M>) -> bool {
@@ -1147,19 +1433,21 @@ where
     }
 }
 
-impl<'a, M> std::cmp::PartialEq<RopeSlice<'a, M>> for Vec<M>
+impl<'a, M, C> std::cmp::PartialEq<RopeSlice<'a, M, C>> for Vec<M>
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
     #[inline]
-    fn eq(&self, other: &RopeSlice<'a, M>) -> bool {
+    fn eq(&self, other: &RopeSlice<'a, M, C>) -> bool {
         self.as_slice() == other
     }
 }
 
-impl<'a, 'b, M> std::cmp::PartialEq<std::borrow::Cow<'b, [M]>> for RopeSlice<'a, M>
+impl<'a, 'b, M, C> std::cmp::PartialEq<std::borrow::Cow<'b, [M]>> for RopeSlice<'a, M, C>
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
     #[inline]
     fn eq(&self, other: &std::borrow::Cow<'b, [M]>) -> bool {
@@ -1167,97 +1455,79 @@ where
     }
 }
 
-impl<'a, 'b, M> std::cmp::PartialEq<RopeSlice<'a, M>> for std::borrow::Cow<'b, [M]>
+impl<'a, 'b, M, C> std::cmp::PartialEq<RopeSlice<'a, M, C>> for std::borrow::Cow<'b, [M]>
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
     #[inline]
-    fn eq(&self, other: &RopeSlice<'a, M>) -> bool {
+    fn eq(&self, other: &RopeSlice<'a, M, C>) -> bool {
         **self == *other
     }
 }
 
-impl<'a, M> std::cmp::PartialEq<Rope<M>> for RopeSlice<'a, M>
+impl<'a, M, C> std::cmp::PartialEq<Rope<M, C>> for RopeSlice<'a, M, C>
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
     #[inline]
-    fn eq(&self, other: &Rope<M>) -> bool {
+    fn eq(&self, other: &Rope<M, C>) -> bool {
         *self == other.width_slice(..)
     }
 }
 
-impl<'a, M> std::cmp::PartialEq<RopeSlice<'a, M>> for Rope<M>
+impl<'a, M, C> std::cmp::PartialEq<RopeSlice<'a, M, C>> for Rope<M, C>
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
     #[inline]
-    fn eq(&self, other: &RopeSlice<'a, M>) -> bool {
+    fn eq(&self, other: &RopeSlice<'a, M, C>) -> bool {
         self.width_slice(..) == *other
     }
 }
 
-impl<'a, M> std::cmp::Ord for RopeSlice<'a, M>
+/// Skips over the subtrees that both [RopeSlice<M>]s share, so comparing
+/// two versions of the same [Rope<M>] only looks at the parts that differ.
+impl<'a, M, C> std::cmp::Ord for RopeSlice<'a, M, C>
 where
     M: Measurable + Ord,
+    C: RopeConfig,
 {
-    #[allow(clippy::op_ref)] // Erroneously thinks with can directly use a slice.
-    fn cmp(&self, other: &RopeSlice<'a, M>) -> std::cmp::Ordering {
-        let mut chunk_itr_1 = self.chunks();
-        let mut chunk_itr_2 = other.chunks();
-        let mut chunk1 = chunk_itr_1.next().unwrap_or(&[]);
-        let mut chunk2 = chunk_itr_2.next().unwrap_or(&[]);
-
-        loop {
-            if chunk1.len() >= chunk2.len() {
-                let compared = chunk1[..chunk2.len()].cmp(chunk2);
-                if compared != std::cmp::Ordering::Equal {
-                    return compared;
-                }
-
-                chunk1 = &chunk1[chunk2.len()..];
-                chunk2 = &[];
-            } else {
-                let compared = chunk1.cmp(&chunk2[..chunk1.len()]);
-                if compared != std::cmp::Ordering::Equal {
-                    return compared;
-                }
-
-                chunk1 = &[];
-                chunk2 = &chunk2[chunk1.len()..];
-            }
-
-            if chunk1.is_empty() {
-                if let Some(chunk) = chunk_itr_1.next() {
-                    chunk1 = chunk;
-                } else {
-                    break;
-                }
-            }
-
-            if chunk2.is_empty() {
-                if let Some(chunk) = chunk_itr_2.next() {
-                    chunk2 = chunk;
-                } else {
-                    break;
-                }
-            }
-        }
-
-        self.len().cmp(&other.len())
+    #[inline]
+    fn cmp(&self, other: &RopeSlice<'a, M, C>) -> std::cmp::Ordering {
+        self.shared_cmp(other)
     }
 }
 
-impl<'a, 'b, M> std::cmp::PartialOrd<RopeSlice<'b, M>> for RopeSlice<'a, M>
+impl<'a, 'b, M, C> std::cmp::PartialOrd<RopeSlice<'b, M, C>> for RopeSlice<'a, M, C>
 where
     M: Measurable + PartialOrd + Ord,
+    C: RopeConfig,
 {
     #[inline]
-    fn partial_cmp(&self, other: &RopeSlice<'b, M>) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &RopeSlice<'b, M, C>) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
+/// Hashes the elements of the [RopeSlice<M>] one by one, so equal
+/// [RopeSlice<M>]s and [Rope<M>]s hash the same, no matter how they are split
+/// into chunks.
+impl<'a, M, C> std::hash::Hash for RopeSlice<'a, M, C>
+where
+    M: Measurable + std::hash::Hash,
+    C: RopeConfig,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for measurable in self.chunks().flatten() {
+            measurable.hash(state);
+        }
+    }
+}
+
 //===========================================================
 
 #[cfg(test)]
@@ -1323,6 +1593,17 @@ mod tests {
         assert_eq!(slice.width(), 24);
     }
 
+    #[test]
+    fn width_04() {
+        // A full index range over a branch must keep the width of the branch, not its length.
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let slice = rope.index_slice(..);
+        assert_eq!(slice.len(), 70);
+        assert_eq!(slice.width(), 135);
+        assert_eq!(slice.index_to_width(70), 135);
+        assert_eq!(slice.width_slice(31..97), rope.width_slice(31..97));
+    }
+
     #[test]
     fn index_to_width_01() {
         let rope = Rope::from_slice(lorem_ipsum().as_slice());
@@ -1724,4 +2005,48 @@ mod tests {
 
         assert_eq!(slice, cow);
     }
+
+    #[test]
+    fn owned_slice_01() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let owned = rope.owned_width_slice(13..89);
+        let vec: Vec<Lipsum> = rope.width_slice(13..89).into();
+
+        rope.remove(..);
+
+        assert_eq!(owned.len(), vec.len());
+        assert_eq!(owned, vec.as_slice());
+        assert_eq!(owned.as_rope_slice(), vec.as_slice());
+    }
+
+    #[test]
+    fn owned_slice_02() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let owned = rope.owned_index_slice(5..21);
+
+        assert_eq!(owned, &lorem_ipsum()[5..21]);
+        assert_eq!(owned.width(), rope.index_slice(5..21).width());
+    }
+
+    #[test]
+    fn owned_slice_03() {
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let owned = rope.owned_index_slice(..);
+
+        assert_eq!(owned.width(), rope.width());
+        assert_eq!(Rope::from(owned), rope);
+    }
+
+    #[test]
+    fn owned_slice_04() {
+        fn assert_static_send_sync<T: 'static + Send + Sync>(_: &T) {}
+
+        let rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let owned = rope.owned_width_slice(7..30);
+        assert_static_send_sync(&owned);
+
+        let handle = std::thread::spawn(move || owned.into_iter().map(|(_, e)| e).collect());
+        let vec: Vec<Lipsum> = handle.join().unwrap();
+        assert_eq!(rope.width_slice(7..30), vec);
+    }
 }
@@ -1,4 +1,5 @@
 use crate::rope::Measurable;
+use crate::tree::Count;
 
 pub fn width_of<M>(slice: &[M]) -> usize
 where
@@ -62,3 +63,41 @@ where
 
     index
 }
+
+/// Same as [index_to_width()], but using the ending width sums of every
+/// element of a slice, instead of the slice itself.
+pub fn ends_index_to_width(ends: &[Count], index: usize) -> usize {
+    match index {
+        0 => 0,
+        index => ends[index - 1] as usize,
+    }
+}
+
+/// Same as [start_width_to_index()], but using the ending width sums of every
+/// element of a slice, instead of the slice itself.
+pub fn ends_start_width_to_index(ends: &[Count], width: usize) -> usize {
+    let width = width as Count;
+    let index = ends.partition_point(|&end| end < width);
+
+    // An element ending exactly at `width` only counts if it has 0 width,
+    // otherwise, the next element is the one that starts there.
+    let start = ends_index_to_width(ends, index) as Count;
+    if index < ends.len() && ends[index] == width && start < width {
+        index + 1
+    } else {
+        index
+    }
+}
+
+/// Same as [end_width_to_index()], but using the ending width sums of every
+/// element of a slice, instead of the slice itself.
+pub fn ends_end_width_to_index(ends: &[Count], width: usize) -> usize {
+    let index = ends.partition_point(|&end| end <= width as Count);
+
+    // The element that goes past `width` only counts if it starts there.
+    if index < ends.len() && ends_index_to_width(ends, index) < width {
+        index + 1
+    } else {
+        index
+    }
+}
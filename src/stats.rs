@@ -0,0 +1,213 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::config::RopeConfig;
+use crate::rope::{Measurable, Rope};
+use crate::tree::{max_len, Node};
+
+/// Statistics about the tree of a [Rope<M>] and the memory it takes up,
+/// returned by [stats()][Rope::stats].
+///
+/// Nodes that show up more than once in the tree, such as the ones in a
+/// [Rope<M>] made by [repeat()][Rope::repeat], are only counted once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RopeStats {
+    /// The depth of the tree, where a tree with a single leaf has a depth
+    /// of 0.
+    pub depth: usize,
+    /// The amount of branch nodes in the tree.
+    pub branches: usize,
+    /// The amount of leaf nodes in the tree.
+    pub leaves: usize,
+    /// The amount of element slots in the leaves that are in use.
+    pub occupied: usize,
+    /// The amount of element slots in the leaves, whether they are in use or
+    /// not.
+    pub allocated: usize,
+    /// The total amount of bytes allocated for the tree.
+    pub heap_bytes: usize,
+}
+
+/// A report on how fragmented the tree of a [Rope<M>] is, returned by
+/// [fragmentation()][Rope::fragmentation].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fragmentation {
+    /// The amount of leaves in the tree.
+    pub leaves: usize,
+    /// The amount of leaves that the tree would have if they were all full.
+    pub min_leaves: usize,
+    /// How full the leaves are on average, from 0.0 to 1.0.
+    pub fill_ratio: f64,
+    /// The depth of the tree, where a tree with a single leaf has a depth
+    /// of 0.
+    pub depth: usize,
+}
+
+impl<M, C> Rope<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    /// Gathers statistics about the tree of the [Rope<M>], and the memory
+    /// that it takes up.
+    ///
+    /// Unlike [capacity()][Rope::capacity], this counts the actual space
+    /// allocated for each leaf, and the memory taken up by branches.
+    ///
+    /// Runs in O(N) time, but only visits the nodes of the tree, not its
+    /// elements.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let rope = Rope::from_slice(&[Lorem, Ipsum, Dolor(3)].repeat(10_000));
+    /// let stats = rope.stats();
+    ///
+    /// assert_eq!(stats.occupied, rope.len());
+    /// assert!(stats.allocated >= stats.occupied);
+    /// assert!(stats.heap_bytes >= stats.allocated * std::mem::size_of_val(&Lorem));
+    /// ```
+    pub fn stats(&self) -> RopeStats {
+        let mut stats = RopeStats {
+            depth: self.root.depth(),
+            branches: 0,
+            leaves: 0,
+            occupied: 0,
+            allocated: 0,
+            heap_bytes: 0,
+        };
+
+        let mut visited = HashSet::new();
+        let mut stack = vec![&self.root];
+        while let Some(node) = stack.pop() {
+            if !visited.insert(Arc::as_ptr(node)) {
+                continue;
+            }
+
+            stats.heap_bytes += node.heap_bytes();
+            match **node {
+                Node::Leaf(ref slice) => {
+                    stats.leaves += 1;
+                    stats.occupied += slice.len();
+                    stats.allocated += slice.capacity();
+                }
+                Node::Branch(ref children) => {
+                    stats.branches += 1;
+                    stack.extend(children.nodes());
+                }
+            }
+        }
+
+        stats
+    }
+
+    /// The amount of bytes that would be freed if the [Rope<M>] were
+    /// dropped.
+    ///
+    /// This only counts nodes that aren't shared with any other [Rope<M>],
+    /// such as clones, slices, or iterators. Nodes that show up more than
+    /// once in the tree, such as the ones in a [Rope<M>] made by
+    /// [repeat()][Rope::repeat], are still counted once if nothing else holds
+    /// on to them. Comparing this to
+    /// [stats().heap_bytes][RopeStats::heap_bytes] tells how much memory is
+    /// saved by sharing nodes.
+    ///
+    /// Runs in O(N) time, but only visits the nodes of the tree that aren't
+    /// shared, and their children.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let mut rope = Rope::from_slice(&[Lorem, Ipsum, Dolor(3)].repeat(10_000));
+    /// assert_eq!(rope.unique_bytes(), rope.stats().heap_bytes);
+    ///
+    /// let _clone = rope.clone();
+    /// assert_eq!(rope.unique_bytes(), 0);
+    ///
+    /// // Only the nodes on the path to the edit stop being shared.
+    /// rope.insert(100, Sit);
+    /// assert!(rope.unique_bytes() < rope.stats().heap_bytes / 10);
+    /// ```
+    pub fn unique_bytes(&self) -> usize {
+        // A node is freed once every reference to it comes from nodes that
+        // are freed, so the references from those are counted as they are
+        // found.
+        let mut references = HashMap::new();
+        let mut bytes = 0;
+        let mut stack = vec![&self.root];
+        while let Some(node) = stack.pop() {
+            let count = references.entry(Arc::as_ptr(node)).or_insert(0);
+            *count += 1;
+            if *count < Arc::strong_count(node) {
+                continue;
+            }
+
+            bytes += node.heap_bytes();
+            if let Node::Branch(ref children) = **node {
+                stack.extend(children.nodes());
+            }
+        }
+
+        bytes
+    }
+
+    /// Reports how fragmented the tree of the [Rope<M>] is.
+    ///
+    /// This can be used to decide when to call
+    /// [compact_range()][Rope::compact_range].
+    ///
+    /// Runs in O(N) time, but only visits the nodes of the tree, not its
+    /// elements.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let rope = Rope::from_slice(&[Lorem, Ipsum, Dolor(3)].repeat(10_000));
+    /// let fragmentation = rope.fragmentation();
+    ///
+    /// assert!(fragmentation.fill_ratio > 0.9);
+    /// assert!(fragmentation.leaves <= fragmentation.min_leaves + 1);
+    /// ```
+    pub fn fragmentation(&self) -> Fragmentation {
+        let leaves = self.root.leaf_count();
+        let capacity = leaves * max_len::<M, C>();
+
+        Fragmentation {
+            leaves,
+            min_leaves: self.len().div_ceil(max_len::<M, C>()).max(1),
+            fill_ratio: self.len() as f64 / capacity as f64,
+            depth: self.root.depth(),
+        }
+    }
+
+    //-----------------------------------------------------------------------
+    // Internal utilities
+
+    /// The amount of bytes allocated for the nodes of the tree that aren't
+    /// in `visited` yet, adding them to it.
+    ///
+    /// This counts the memory taken up by several [Rope<M>]s together, with
+    /// the nodes that they share only counted once.
+    pub(crate) fn heap_bytes_unvisited(&self, visited: &mut HashSet<*const Node<M, C>>) -> usize {
+        let mut bytes = 0;
+        let mut stack = vec![&self.root];
+        while let Some(node) = stack.pop() {
+            if !visited.insert(Arc::as_ptr(node)) {
+                continue;
+            }
+
+            bytes += node.heap_bytes();
+            if let Node::Branch(ref children) = **node {
+                stack.extend(children.nodes());
+            }
+        }
+
+        bytes
+    }
+}
@@ -0,0 +1,116 @@
+use std::sync::OnceLock;
+
+/// Storage for the accumulated values over the contents of a node, chosen by
+/// [CacheMode::Storage][crate::CacheMode].
+///
+/// This is public only so that it can be named by [CacheMode][crate::CacheMode],
+/// but lives in a private module, so it can't be used outside of the crate.
+pub trait CacheStorage<T>: Default + Clone + Send + Sync {
+    /// Returns the cached values, computing them with `init` if needed.
+    fn get_or_init(&self, init: impl FnOnce() -> Box<[T]>) -> &[T];
+
+    /// Bytes taken by the cached values, if there are any.
+    fn heap_bytes(&self) -> usize;
+
+    /// Throws away the cached values, if there are any.
+    fn clear(&mut self);
+}
+
+/// A lazily computed list of accumulated values over the contents of a node,
+/// which makes it possible to binary search through them.
+///
+/// It is only ever filled in by queries, and must be cleared whenever the
+/// contents of its node change.
+pub struct AccumCache<T>(OnceLock<Box<[T]>>);
+
+impl<T> AccumCache<T> {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self(OnceLock::new())
+    }
+}
+
+impl<T> CacheStorage<T> for AccumCache<T>
+where
+    T: Send + Sync,
+{
+    #[inline(always)]
+    fn get_or_init(&self, init: impl FnOnce() -> Box<[T]>) -> &[T] {
+        self.0.get_or_init(init)
+    }
+
+    #[inline(always)]
+    fn heap_bytes(&self) -> usize {
+        self.0
+            .get()
+            .map_or(0, |values| std::mem::size_of_val(&**values))
+    }
+
+    #[inline(always)]
+    fn clear(&mut self) {
+        self.0.take();
+    }
+}
+
+impl<T> Default for AccumCache<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Nodes are only cloned right before being modified, so there's no point in
+/// carrying the values over.
+impl<T> Clone for AccumCache<T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+/// The storage used when caching is disabled, which takes no space.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoAccumCache;
+
+impl<T> CacheStorage<T> for NoAccumCache {
+    /// Never called, since nodes check [CacheMode::ENABLED][crate::CacheMode]
+    /// before searching through the cache.
+    #[inline(always)]
+    fn get_or_init(&self, _init: impl FnOnce() -> Box<[T]>) -> &[T] {
+        unreachable!("searched through a disabled cache")
+    }
+
+    #[inline(always)]
+    fn heap_bytes(&self) -> usize {
+        0
+    }
+
+    #[inline(always)]
+    fn clear(&mut self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_or_init_01() {
+        let mut cache = AccumCache::new();
+        assert_eq!(cache.get_or_init(|| Box::new([1, 3, 6])), &[1, 3, 6]);
+        // Already computed, so `init` isn't called again.
+        assert_eq!(cache.get_or_init(|| unreachable!()), &[1, 3, 6]);
+
+        cache.clear();
+        assert_eq!(cache.get_or_init(|| Box::new([2])), &[2]);
+    }
+
+    #[test]
+    fn clone_01() {
+        let cache = AccumCache::new();
+        cache.get_or_init(|| Box::new([1, 2]));
+
+        let clone = cache.clone();
+        assert_eq!(clone.get_or_init(|| Box::new([3])), &[3]);
+        assert_eq!(cache.get_or_init(|| unreachable!()), &[1, 2]);
+    }
+}
@@ -1,30 +1,75 @@
 use std::fmt::{self};
 use std::iter::{Iterator, Zip};
+use std::marker::PhantomData;
 use std::slice;
 use std::sync::Arc;
 
+use crate::config::{DefaultConfig, RopeConfig};
 use crate::rope::Measurable;
-use crate::tree::{Node, SliceInfo, MAX_LEN};
+use crate::tree::{
+    caches_widths, max_children, max_len, CacheStorage, Node, SliceInfo, WidthCache,
+};
 
 use super::MAX_CHILDREN;
 
+// Branches with more children than this are searched with a binary search,
+// when the `RopeConfig` enables caching. Smaller in tests, since test trees
+// never get very wide.
+#[cfg(not(test))]
+const LINEAR_SEARCH_MAX: usize = 8;
+#[cfg(test)]
+const LINEAR_SEARCH_MAX: usize = 2;
+
 /// A fixed-capacity vec of child Arc-pointers and child metadata.
 ///
 /// The unsafe guts of this are implemented in NodeChildrenInternal
 /// lower down in this file.
+///
+/// The second field holds the left-side-accumulated [SliceInfo] at the end
+/// of every child, when the [RopeConfig] asks for it.
 #[derive(Clone)]
 #[repr(C)]
-pub(crate) struct BranchChildren<M>(inner::NodeChildrenInternal<M>)
+pub(crate) struct BranchChildren<M, C>(inner::NodeChildrenInternal<M, C>, WidthCache<C, SliceInfo>)
 where
-    M: Measurable;
+    M: Measurable,
+    C: RopeConfig;
+
+/// Checks the bounds of [RopeConfig::MAX_CHILDREN] when `CHECK` is
+/// evaluated, which happens at compile time.
+struct ValidConfig<C>(PhantomData<C>);
 
-impl<M> BranchChildren<M>
+impl<C: RopeConfig> ValidConfig<C> {
+    const CHECK: () = assert!(
+        C::MAX_CHILDREN >= 4 && C::MAX_CHILDREN <= MAX_CHILDREN,
+        "RopeConfig::MAX_CHILDREN must be between 4 and DefaultConfig::MAX_CHILDREN"
+    );
+}
+
+impl<M> BranchChildren<M, DefaultConfig>
 where
     M: Measurable,
 {
     /// Creates a new empty array.
+    ///
+    /// Used for tests and debugging purposes.
+    #[allow(dead_code)]
     pub fn new() -> Self {
-        BranchChildren(inner::NodeChildrenInternal::new())
+        BranchChildren::new_with_config()
+    }
+}
+
+impl<M, C> BranchChildren<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    /// Creates a new empty array, for any [RopeConfig].
+    pub fn new_with_config() -> Self {
+        // Every branch node is created through here, so this is enough to
+        // reject bad configurations at compile time.
+        #[allow(clippy::let_unit_value)]
+        let () = ValidConfig::<C>::CHECK;
+        BranchChildren(inner::NodeChildrenInternal::new(), Default::default())
     }
 
     /// Current length of the array.
@@ -34,16 +79,17 @@ where
 
     /// Returns whether the array is full or not.
     pub fn is_full(&self) -> bool {
-        self.len() == MAX_CHILDREN
+        self.len() == max_children::<C>()
     }
 
     /// Access to the nodes array.
-    pub fn nodes(&self) -> &[Arc<Node<M>>] {
+    pub fn nodes(&self) -> &[Arc<Node<M, C>>] {
         self.0.nodes()
     }
 
     /// Mutable access to the nodes array.
-    pub fn nodes_mut(&mut self) -> &mut [Arc<Node<M>>] {
+    pub fn nodes_mut(&mut self) -> &mut [Arc<Node<M, C>>] {
+        self.1.clear();
         self.0.nodes_mut()
     }
 
@@ -54,24 +100,27 @@ where
 
     /// Mutable access to the info array.
     pub fn info_mut(&mut self) -> &mut [(SliceInfo, bool)] {
+        self.1.clear();
         self.0.info_mut()
     }
 
     /// Mutable access to both the info and nodes arrays simultaneously.
-    pub fn data_mut(&mut self) -> (&mut [(SliceInfo, bool)], &mut [Arc<Node<M>>]) {
+    pub fn data_mut(&mut self) -> (&mut [(SliceInfo, bool)], &mut [Arc<Node<M, C>>]) {
+        self.1.clear();
         self.0.data_mut()
     }
 
     /// Updates the [SliceInfo] of the child at `index`.
     pub fn update_child_info(&mut self, index: usize) {
-        let (info, nodes) = self.0.data_mut();
+        let (info, nodes) = self.data_mut();
         info[index] = (nodes[index].slice_info(), nodes[index].zero_width_end())
     }
 
     /// Pushes an item into the end of the array.
     ///
     /// Increases length by one. Panics if already full.
-    pub fn push(&mut self, item: (SliceInfo, Arc<Node<M>>)) {
+    pub fn push(&mut self, item: (SliceInfo, Arc<Node<M, C>>)) {
+        self.1.clear();
         self.0.push(item)
     }
 
@@ -79,7 +128,7 @@ where
     /// returning the right half.
     ///
     /// This works even when the array is full.
-    pub fn push_split(&mut self, new_child: (SliceInfo, Arc<Node<M>>)) -> Self {
+    pub fn push_split(&mut self, new_child: (SliceInfo, Arc<Node<M, C>>)) -> Self {
         let r_count = (self.len() + 1) / 2;
         let l_count = (self.len() + 1) - r_count;
 
@@ -105,7 +154,7 @@ where
             match *node1 {
                 Node::Leaf(ref mut slice1) => {
                     if let Node::Leaf(ref mut slice2) = *node2 {
-                        if (slice1.len() + slice2.len()) <= MAX_CHILDREN {
+                        if (slice1.len() + slice2.len()) <= max_len::<M, C>() {
                             slice1.push_slice(slice2);
                             true
                         } else {
@@ -120,7 +169,7 @@ where
 
                 Node::Branch(ref mut children1) => {
                     if let Node::Branch(ref mut children2) = *node2 {
-                        if (children1.len() + children2.len()) <= MAX_CHILDREN {
+                        if (children1.len() + children2.len()) <= max_children::<C>() {
                             for _ in 0..children2.len() {
                                 children1.push(children2.remove(0));
                             }
@@ -169,7 +218,7 @@ where
         let mut i = 1;
         while i < self.len() {
             if (self.nodes()[i - 1].leaf_slice().len() + self.nodes()[i].leaf_slice().len())
-                <= MAX_LEN
+                <= max_len::<M, C>()
             {
                 // Scope to contain borrows
                 {
@@ -179,13 +228,13 @@ where
                     slice_l.push_slice(slice_r);
                 }
                 self.remove(i);
-            } else if self.nodes()[i - 1].leaf_slice().len() < MAX_LEN {
+            } else if self.nodes()[i - 1].leaf_slice().len() < max_len::<M, C>() {
                 // Scope to contain borrows
                 {
                     let ((_, node_l), (_, node_r)) = self.get_two_mut(i - 1, i);
                     let slice_l = Arc::make_mut(node_l).leaf_slice_mut();
                     let slice_r = Arc::make_mut(node_r).leaf_slice_mut();
-                    let split_index_r = MAX_LEN - slice_l.len();
+                    let split_index_r = max_len::<M, C>() - slice_l.len();
                     slice_l.push_slice(&slice_r[..split_index_r]);
                     slice_r.truncate_front(split_index_r);
                 }
@@ -203,7 +252,8 @@ where
     /// Pops an item off the end of the array and returns it.
     ///
     /// Decreases length by one. Panics if already empty.
-    pub fn pop(&mut self) -> (SliceInfo, Arc<Node<M>>) {
+    pub fn pop(&mut self) -> (SliceInfo, Arc<Node<M, C>>) {
+        self.1.clear();
         self.0.pop()
     }
 
@@ -211,7 +261,8 @@ where
     ///
     /// Increases length by one. Panics if already full. Preserves ordering
     /// of the other items.
-    pub fn insert(&mut self, index: usize, item: (SliceInfo, Arc<Node<M>>)) {
+    pub fn insert(&mut self, index: usize, item: (SliceInfo, Arc<Node<M, C>>)) {
+        self.1.clear();
         self.0.insert(index, item)
     }
 
@@ -219,7 +270,7 @@ where
     /// the right half.
     ///
     /// This works even when the array is full.
-    pub fn insert_split(&mut self, index: usize, item: (SliceInfo, Arc<Node<M>>)) -> Self {
+    pub fn insert_split(&mut self, index: usize, item: (SliceInfo, Arc<Node<M, C>>)) -> Self {
         assert!(self.len() > 0);
         assert!(index <= self.len());
         let extra = if index < self.len() {
@@ -236,7 +287,8 @@ where
     /// Removes the item at the given index from the the array.
     ///
     /// Decreases length by one. Preserves ordering of the other items.
-    pub fn remove(&mut self, index: usize) -> (SliceInfo, Arc<Node<M>>) {
+    pub fn remove(&mut self, index: usize) -> (SliceInfo, Arc<Node<M, C>>) {
+        self.1.clear();
         self.0.remove(index)
     }
 
@@ -246,7 +298,7 @@ where
     pub fn split_off(&mut self, index: usize) -> Self {
         assert!(index <= self.len());
 
-        let mut other = BranchChildren::new();
+        let mut other = BranchChildren::new_with_config();
         let count = self.len() - index;
         for _ in 0..count {
             other.push(self.remove(index));
@@ -264,8 +316,8 @@ where
         index1: usize,
         index2: usize,
     ) -> (
-        (&mut SliceInfo, &mut Arc<Node<M>>),
-        (&mut SliceInfo, &mut Arc<Node<M>>),
+        (&mut SliceInfo, &mut Arc<Node<M, C>>),
+        (&mut SliceInfo, &mut Arc<Node<M, C>>),
     ) {
         assert!(index1 < index2);
         assert!(index2 < self.len());
@@ -285,7 +337,7 @@ where
     }
 
     /// Creates an iterator over the array's items.
-    pub fn iter(&self) -> Zip<slice::Iter<(SliceInfo, bool)>, slice::Iter<Arc<Node<M>>>> {
+    pub fn iter(&self) -> Zip<slice::Iter<(SliceInfo, bool)>, slice::Iter<Arc<Node<M, C>>>> {
         Iterator::zip(self.info().iter(), self.nodes().iter())
     }
 
@@ -303,21 +355,66 @@ where
         acc
     }
 
+    /// The left-side-accumulated [SliceInfo] at the end of every child,
+    /// computed on the first call after the children change.
+    fn accum_info(&self) -> &[SliceInfo] {
+        self.1.get_or_init(|| {
+            let mut accum = SliceInfo::new();
+            self.info()
+                .iter()
+                .map(|(info, _)| {
+                    accum += *info;
+                    accum
+                })
+                .collect()
+        })
+    }
+
+    /// Returns the child index and left-side-accumulated [SliceInfo] of the
+    /// first child whose left-side-accumulated end doesn't match `before`,
+    /// through a binary search.
+    ///
+    /// `before` must match every child before the ones that don't match it.
+    /// If the [RopeConfig] doesn't enable caching, or there are too few
+    /// children for a binary search to be worth it, returns the first child.
+    #[inline(always)]
+    fn skip_by<B>(&self, before: B) -> (usize, SliceInfo)
+    where
+        B: Fn(SliceInfo) -> bool,
+    {
+        if caches_widths::<C>() && self.len() > LINEAR_SEARCH_MAX {
+            let accum_info = self.accum_info();
+            let index = accum_info[..(self.len() - 1)].partition_point(|end| before(*end));
+            let accum = match index {
+                0 => SliceInfo::new(),
+                index => accum_info[index - 1],
+            };
+            (index, accum)
+        } else {
+            (0, SliceInfo::new())
+        }
+    }
+
     /// Returns the child index and left-side-accumulated [SliceInfo] of the
     /// first child that matches the given predicate.
     ///
+    /// Children that match `before` are skipped through a binary search,
+    /// when possible (see [skip_by()][Self::skip_by]), so `pred` must never
+    /// match them.
+    ///
     /// If no child matches the predicate, the last child is returned.
     #[inline(always)]
-    pub fn search_by<F>(&self, pred: F) -> (usize, SliceInfo)
+    pub fn search_by<B, F>(&self, before: B, pred: F) -> (usize, SliceInfo)
     where
-        // (left-accumulated start info, left-accumulated end info)
+        // (left-accumulated end info)
+        B: Fn(SliceInfo) -> bool,
+        // (left-accumulated end info, whether the child ends in 0 width elements)
         F: Fn(SliceInfo, bool) -> bool,
     {
         debug_assert!(self.len() > 0);
 
-        let mut accum = SliceInfo::new();
-        let mut index = 0;
-        for (info, zero_width_end) in self.info()[0..(self.len() - 1)].iter() {
+        let (mut index, mut accum) = self.skip_by(before);
+        for (info, zero_width_end) in self.info()[index..(self.len() - 1)].iter() {
             let next_accum = accum + *info;
             if pred(next_accum, *zero_width_end) {
                 break;
@@ -334,7 +431,10 @@ where
     ///
     /// One-past-the end is valid, and will return the last child.
     pub fn search_index(&self, index: usize) -> (usize, SliceInfo) {
-        let (index, accum) = self.search_by(|end, _| index < end.len as usize);
+        let (index, accum) = self.search_by(
+            |end| end.len as usize <= index,
+            |end, _| index < end.len as usize,
+        );
 
         debug_assert!(
             index <= (accum.len + self.info()[index].0.len) as usize,
@@ -351,9 +451,12 @@ where
     pub fn search_start_width(&self, width: usize) -> (usize, SliceInfo) {
         // The search uses the `<=` comparison because any slice may end with 0 width
         // elements, and the use of the `<` comparison would leave those behind.
-        let (index, accum) = self.search_by(|end, zero_width_end| {
-            width < end.width as usize || (width == end.width as usize && zero_width_end)
-        });
+        let (index, accum) = self.search_by(
+            |end| (end.width as usize) < width,
+            |end, zero_width_end| {
+                width < end.width as usize || (width == end.width as usize && zero_width_end)
+            },
+        );
 
         debug_assert!(
             width <= (accum.width + self.info()[index].0.width) as usize,
@@ -370,7 +473,10 @@ where
     pub fn search_end_width(&self, width: usize) -> (usize, SliceInfo) {
         // The search uses the `<=` comparison because any slice may end with 0 width
         // elements, and the use of the `<` comparison would leave those behind.
-        let (index, accum) = self.search_by(|end, _| width < end.width as usize);
+        let (index, accum) = self.search_by(
+            |end| end.width as usize <= width,
+            |end, _| width < end.width as usize,
+        );
 
         debug_assert!(
             width <= (accum.width + self.info()[index].0.width) as usize,
@@ -380,34 +486,62 @@ where
         (index, accum)
     }
 
-    /// Same as [search_start_width()][Self::search_start_width] above,
-    /// except that it only calulates the left-side-accumulated _width_,
-    /// rather than the full [SliceInfo].
+    /// Returns the child indices at the start and end of the given index
+    /// range, and returns their left-side-accumulated lengths as well.
     ///
-    /// Return is (child_index, left_acc_width)
+    /// Return is:
+    /// (
+    ///     (left_node_index, left_acc_left_side_len),
+    ///     (right_node_index, right_acc_left_side_len),
+    /// )
     ///
-    /// One-past-the end is valid, and will return the last child.
+    /// One-past-the end is valid, and corresponds to the last child.
     #[inline(always)]
-    pub fn search_width_only(&self, width: usize) -> (usize, usize) {
+    pub fn search_index_range(
+        &self,
+        start_index: usize,
+        end_index: usize,
+    ) -> ((usize, usize), (usize, usize)) {
+        debug_assert!(start_index <= end_index);
         debug_assert!(self.len() > 0);
 
-        let mut accum_width = 0;
-        let mut index = 0;
-        for (info, zero_width_end) in self.info()[0..(self.len() - 1)].iter() {
-            let next_accum = accum_width + info.width as usize;
-            if (width <= next_accum && *zero_width_end) || width < next_accum {
+        let (mut index, accum) = self.skip_by(|end| end.len as usize <= start_index);
+        let mut accum_len = accum.len as usize;
+
+        // Find left child and info
+        for (info, _) in self.info()[index..(self.len() - 1)].iter() {
+            let next_accum = accum_len + info.len as usize;
+            if start_index < next_accum {
                 break;
             }
-            accum_width = next_accum;
+            accum_len = next_accum;
             index += 1;
         }
+        let l_child_i = index;
+        let l_acc_info = accum_len;
 
-        debug_assert!(
-            width <= (accum_width + self.info()[index].0.width as usize) as usize,
+        // Find right child and info
+        let (r_index, r_accum) = self.skip_by(|end| (end.len as usize) < end_index);
+        if r_index > index {
+            index = r_index;
+            accum_len = r_accum.len as usize;
+        }
+        for (info, _) in self.info()[index..(self.len() - 1)].iter() {
+            let next_accum = accum_len + info.len as usize;
+            if end_index <= next_accum {
+                break;
+            }
+            accum_len = next_accum;
+            index += 1;
+        }
+
+        #[cfg(any(test, debug_assertions))]
+        assert!(
+            end_index <= accum_len + self.info()[index].0.len as usize,
             "Index out of bounds."
         );
 
-        (index, accum_width)
+        ((l_child_i, l_acc_info), (index, accum_len))
     }
 
     /// Returns the child indices at the start and end of the given width
@@ -420,6 +554,7 @@ where
     /// )
     ///
     /// One-past-the end is valid, and corresponds to the last child.
+    #[allow(dead_code)]
     #[inline(always)]
     pub fn search_width_range(
         &self,
@@ -473,6 +608,12 @@ where
         true
     }
 
+    /// Bytes taken by the heap buffers of the children, not counting the
+    /// child nodes themselves.
+    pub fn heap_bytes(&self) -> usize {
+        self.1.heap_bytes()
+    }
+
     pub fn zero_width_end(&self) -> bool {
         self.nodes()
             .iter()
@@ -482,9 +623,10 @@ where
     }
 }
 
-impl<M> fmt::Debug for BranchChildren<M>
+impl<M, C> fmt::Debug for BranchChildren<M, C>
 where
     M: Measurable + fmt::Debug,
+    C: RopeConfig,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("NodeChildren")
@@ -508,9 +650,11 @@ where
 /// accessing elements that are semantically out of bounds. This happened once,
 /// and it was a pain to track down--as memory safety bugs often are.
 mod inner {
+    use crate::config::RopeConfig;
     use crate::rope::Measurable;
 
     use super::{Node, SliceInfo, MAX_CHILDREN};
+    use std::marker::PhantomData;
     use std::mem;
     use std::mem::MaybeUninit;
     use std::ptr;
@@ -518,22 +662,26 @@ mod inner {
 
     /// This is essentially a fixed-capacity, stack-allocated [Vec<(M, SliceInfo)>].
     #[repr(C)]
-    pub(crate) struct NodeChildrenInternal<M>
+    pub(crate) struct NodeChildrenInternal<M, C>
     where
         M: Measurable,
+        C: RopeConfig,
     {
         /// An array of the child nodes.
         /// INVARIANT: The nodes from 0..len must be initialized
-        nodes: [MaybeUninit<Arc<Node<M>>>; MAX_CHILDREN],
+        nodes: [MaybeUninit<Arc<Node<M, C>>>; MAX_CHILDREN],
         /// An array of the child node [SliceInfo]s
         /// INVARIANT: The nodes from 0..len must be initialized
         info: [MaybeUninit<(SliceInfo, bool)>; MAX_CHILDREN],
         len: u8,
+        /// The [RopeConfig] is only ever used through associated items.
+        _config: PhantomData<fn() -> C>,
     }
 
-    impl<M> NodeChildrenInternal<M>
+    impl<M, C> NodeChildrenInternal<M, C>
     where
         M: Measurable,
+        C: RopeConfig,
     {
         /// Creates a new empty array.
         #[inline(always)]
@@ -544,6 +692,7 @@ mod inner {
                 nodes: unsafe { MaybeUninit::uninit().assume_init() },
                 info: unsafe { MaybeUninit::uninit().assume_init() },
                 len: 0,
+                _config: PhantomData,
             }
         }
 
@@ -555,7 +704,7 @@ mod inner {
 
         /// Access to the nodes array.
         #[inline(always)]
-        pub fn nodes(&self) -> &[Arc<Node<M>>] {
+        pub fn nodes(&self) -> &[Arc<Node<M, C>>] {
             // SAFETY: MaybeUninit<T> is layout compatible with T, and
             // the nodes from 0..len are guaranteed to be initialized
             unsafe { mem::transmute(&self.nodes[..(self.len())]) }
@@ -563,7 +712,7 @@ mod inner {
 
         /// Mutable access to the nodes array.
         #[inline(always)]
-        pub fn nodes_mut(&mut self) -> &mut [Arc<Node<M>>] {
+        pub fn nodes_mut(&mut self) -> &mut [Arc<Node<M, C>>] {
             // SAFETY: MaybeUninit<T> is layout compatible with T, and
             // the nodes from 0..len are guaranteed to be initialized
             unsafe { mem::transmute(&mut self.nodes[..(self.len as usize)]) }
@@ -587,7 +736,7 @@ mod inner {
 
         /// Mutable access to both the info and nodes arrays simultaneously.
         #[inline(always)]
-        pub fn data_mut(&mut self) -> (&mut [(SliceInfo, bool)], &mut [Arc<Node<M>>]) {
+        pub fn data_mut(&mut self) -> (&mut [(SliceInfo, bool)], &mut [Arc<Node<M, C>>]) {
             // SAFETY: MaybeUninit<T> is layout compatible with T, and
             // the info from 0..len are guaranteed to be initialized
             (
@@ -600,7 +749,7 @@ mod inner {
         ///
         /// Increases length by one. Panics if already full.
         #[inline(always)]
-        pub fn push(&mut self, item: (SliceInfo, Arc<Node<M>>)) {
+        pub fn push(&mut self, item: (SliceInfo, Arc<Node<M, C>>)) {
             assert!(self.len() < MAX_CHILDREN);
             self.info[self.len()] = MaybeUninit::new((item.0, item.1.zero_width_end()));
             self.nodes[self.len as usize] = MaybeUninit::new(item.1);
@@ -612,7 +761,7 @@ mod inner {
         ///
         /// Decreases length by one. Panics if already empty.
         #[inline(always)]
-        pub fn pop(&mut self) -> (SliceInfo, Arc<Node<M>>) {
+        pub fn pop(&mut self) -> (SliceInfo, Arc<Node<M, C>>) {
             assert!(self.len() > 0);
             self.len -= 1;
             // SAFETY: before this, len was long enough to guarantee that both must be init
@@ -627,7 +776,7 @@ mod inner {
         /// Increases length by one. Panics if already full. Preserves ordering
         /// of the other items.
         #[inline(always)]
-        pub fn insert(&mut self, index: usize, item: (SliceInfo, Arc<Node<M>>)) {
+        pub fn insert(&mut self, index: usize, item: (SliceInfo, Arc<Node<M, C>>)) {
             assert!(index <= self.len());
             assert!(self.len() < MAX_CHILDREN);
 
@@ -654,7 +803,7 @@ mod inner {
         ///
         /// Decreases length by one. Preserves ordering of the other items.
         #[inline(always)]
-        pub fn remove(&mut self, index: usize) -> (SliceInfo, Arc<Node<M>>) {
+        pub fn remove(&mut self, index: usize) -> (SliceInfo, Arc<Node<M, C>>) {
             assert!(self.len() > 0);
             assert!(index < self.len());
 
@@ -683,9 +832,10 @@ mod inner {
         }
     }
 
-    impl<M> Drop for NodeChildrenInternal<M>
+    impl<M, C> Drop for NodeChildrenInternal<M, C>
     where
         M: Measurable,
+        C: RopeConfig,
     {
         fn drop(&mut self) {
             // The `.nodes` array contains `MaybeUninit` wrappers, which need
@@ -697,9 +847,10 @@ mod inner {
         }
     }
 
-    impl<M> Clone for NodeChildrenInternal<M>
+    impl<M, C> Clone for NodeChildrenInternal<M, C>
     where
         M: Measurable,
+        C: RopeConfig,
     {
         fn clone(&self) -> Self {
             // Create an empty NodeChildrenInternal first, then fill it
@@ -754,8 +905,9 @@ mod inner {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{CachedConfig, DefaultConfig};
     use crate::tree::{LeafSlice, Node, SliceInfo};
-    use crate::Lipsum::*;
+    use crate::Lipsum::{self, *};
     use std::sync::Arc;
 
     #[test]
@@ -924,4 +1076,140 @@ mod tests {
 
         children.search_width_range(17, 18);
     }
+
+    #[test]
+    fn search_index_range_03() {
+        let mut children = BranchChildren::new();
+        children.push((
+            SliceInfo::new(),
+            Arc::new(Node::Leaf(LeafSlice::from_slice(&[Lorem, Ipsum, Dolor(4)]))),
+        ));
+        children.push((
+            SliceInfo::new(),
+            Arc::new(Node::Leaf(LeafSlice::from_slice(&[Sit, Amet]))),
+        ));
+        children.push((
+            SliceInfo::new(),
+            Arc::new(Node::Leaf(LeafSlice::from_slice(&[
+                Consectur("text here"),
+                Adipiscing(true),
+            ]))),
+        ));
+
+        children.update_child_info(0);
+        children.update_child_info(1);
+        children.update_child_info(2);
+
+        let at_0_0 = children.search_index_range(0, 0);
+        let at_3_3 = children.search_index_range(3, 3);
+        let at_5_5 = children.search_index_range(5, 5);
+        let at_7_7 = children.search_index_range(7, 7);
+
+        assert_eq!(at_0_0, ((0, 0), (0, 0)));
+        assert_eq!(at_3_3, ((1, 3), (1, 3)));
+        assert_eq!(at_5_5, ((2, 5), (2, 5)));
+        assert_eq!(at_7_7, ((2, 5), (2, 5)));
+
+        let at_0_3 = children.search_index_range(0, 3);
+        let at_3_7 = children.search_index_range(3, 7);
+        let at_2_4 = children.search_index_range(2, 4);
+
+        assert_eq!(at_0_3, ((0, 0), (0, 0)));
+        assert_eq!(at_3_7, ((1, 3), (2, 5)));
+        assert_eq!(at_2_4, ((0, 0), (1, 3)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn search_index_range_04() {
+        let mut children = BranchChildren::new();
+        children.push((
+            SliceInfo::new(),
+            Arc::new(Node::Leaf(LeafSlice::from_slice(&[Lorem, Ipsum, Dolor(4)]))),
+        ));
+        children.push((
+            SliceInfo::new(),
+            Arc::new(Node::Leaf(LeafSlice::from_slice(&[Sit, Amet]))),
+        ));
+        children.push((
+            SliceInfo::new(),
+            Arc::new(Node::Leaf(LeafSlice::from_slice(&[
+                Consectur("text here"),
+                Adipiscing(true),
+            ]))),
+        ));
+
+        children.update_child_info(0);
+        children.update_child_info(1);
+        children.update_child_info(2);
+
+        children.search_index_range(8, 9);
+    }
+
+    fn wide_children<C: RopeConfig>() -> BranchChildren<Lipsum, C> {
+        let mut children = BranchChildren::new_with_config();
+        let slices: [&[Lipsum]; 5] = [
+            &[Lorem, Ipsum, Dolor(4)],
+            &[Sit, Amet],
+            &[Consectur("text here"), Sit],
+            &[Amet, Adipiscing(true)],
+            &[Dolor(0), Lorem],
+        ];
+        for slice in slices {
+            let node = Arc::new(Node::Leaf(LeafSlice::from_slice(slice)));
+            children.push((node.slice_info(), node));
+        }
+        for i in 0..children.len() {
+            children.update_child_info(i);
+        }
+        children
+    }
+
+    #[test]
+    fn cached_search_01() {
+        let cached = wide_children::<CachedConfig>();
+        let linear = wide_children::<DefaultConfig>();
+        let info = cached.combined_info();
+
+        for width in 0..=info.width as usize {
+            assert_eq!(
+                cached.search_start_width(width),
+                linear.search_start_width(width)
+            );
+            assert_eq!(
+                cached.search_end_width(width),
+                linear.search_end_width(width)
+            );
+        }
+        for start in 0..=info.len as usize {
+            assert_eq!(cached.search_index(start), linear.search_index(start));
+            for end in start..=info.len as usize {
+                assert_eq!(
+                    cached.search_index_range(start, end),
+                    linear.search_index_range(start, end)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn cached_search_02() {
+        let mut children = wide_children::<CachedConfig>();
+        assert_eq!(children.search_index(9).0, 4);
+        assert_eq!(children.search_index(9).1.len, 9);
+
+        // Changes to the children throw away the old accumulated info.
+        children.remove(1);
+        assert_eq!(children.search_index(9).0, 3);
+        assert_eq!(children.search_index(9).1.len, 7);
+    }
+
+    #[test]
+    fn uncached_size_01() {
+        // Without caching, branches take no space for the cache.
+        assert_eq!(
+            std::mem::size_of::<BranchChildren<Lipsum, DefaultConfig>>(),
+            std::mem::size_of::<inner::NodeChildrenInternal<Lipsum, DefaultConfig>>()
+        );
+    }
 }
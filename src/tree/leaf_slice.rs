@@ -2,28 +2,40 @@ use std::borrow::Borrow;
 use std::fmt::{Debug, Display};
 use std::ops::Deref;
 
+use crate::config::RopeConfig;
 use crate::rope::Measurable;
+use crate::slice_utils::{
+    end_width_to_index, ends_end_width_to_index, ends_index_to_width, ends_start_width_to_index,
+    index_to_width, start_width_to_index,
+};
+use crate::tree::{caches_widths, CacheStorage, Count, WidthCache};
 
-use self::inner::LeafSmallVec;
+pub(crate) use self::inner::LeafSmallVec;
 
 /// A custom small string.  The unsafe guts of this are in `NodeSmallString`
 /// further down in this file.
-#[derive(Clone, Default)]
+///
+/// The second field holds the ending width sums of every element, when the
+/// [RopeConfig] asks for them.
+#[derive(Clone)]
 #[repr(C)]
-pub(crate) struct LeafSlice<M>(inner::LeafSmallVec<M>)
+pub(crate) struct LeafSlice<M, C>(inner::LeafSmallVec<M>, WidthCache<C, Count>)
 where
-    M: Measurable;
+    M: Measurable,
+    C: RopeConfig;
 
-impl<M> LeafSlice<M>
+impl<M, C> LeafSlice<M, C>
 where
     M: Measurable,
+    C: RopeConfig,
 {
     /// Creates a new [Leaf] from a slice.
     pub fn from_slice(value: &[M]) -> Self {
-        Self(LeafSmallVec::from_slice(value))
+        Self(LeafSmallVec::from_slice(value), Default::default())
     }
 
     pub fn insert_slice(&mut self, index: usize, slice: &[M]) {
+        self.1.clear();
         self.0.insert_slice(index, slice);
     }
 
@@ -37,6 +49,7 @@ where
         let tot_len = self.len() + slice.len();
         let mid_idx = tot_len / 2;
 
+        self.1.clear();
         self.0.insert_slice(split_idx, slice);
         let right = LeafSlice::from_slice(&self[mid_idx..]);
         self.truncate(mid_idx);
@@ -48,6 +61,7 @@ where
     /// Appends a `&str` to end the of the `NodeText`.
     pub fn push_slice(&mut self, slice: &[M]) {
         let len = self.len();
+        self.1.clear();
         self.0.insert_slice(len, slice);
     }
 
@@ -64,6 +78,7 @@ where
 
     /// Drops the text after byte index `byte_idx`.
     pub fn truncate(&mut self, byte_idx: usize) {
+        self.1.clear();
         self.0.truncate(byte_idx);
         self.0.inline_if_possible();
     }
@@ -71,12 +86,14 @@ where
     /// Drops the text before byte index `byte_idx`, shifting the
     /// rest of the text to fill in the space.
     pub fn truncate_front(&mut self, byte_idx: usize) {
+        self.1.clear();
         self.0.remove_range(0, byte_idx);
         self.0.inline_if_possible();
     }
 
     /// Removes the text in the byte index interval `[byte_start, byte_end)`.
     pub fn remove_range(&mut self, byte_start: usize, byte_end: usize) {
+        self.1.clear();
         self.0.remove_range(byte_start, byte_end);
         self.0.inline_if_possible();
     }
@@ -86,7 +103,8 @@ where
     /// The left part remains in the original, and the right part is
     /// returned in a new `NodeText`.
     pub fn split_off(&mut self, byte_idx: usize) -> Self {
-        let other = LeafSlice(self.0.split_off(byte_idx));
+        self.1.clear();
+        let other = LeafSlice(self.0.split_off(byte_idx), Default::default());
         self.0.inline_if_possible();
         other
     }
@@ -98,11 +116,81 @@ where
             .map(|measurable| measurable.width() == 0)
             .unwrap_or(false)
     }
+
+    /// How many elements fit in the leaf without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Bytes taken by the heap buffers of the leaf.
+    pub fn heap_bytes(&self) -> usize {
+        self.0.heap_capacity() * std::mem::size_of::<M>() + self.1.heap_bytes()
+    }
+
+    /// The ending width sums of every element, computed on the first call
+    /// after the leaf changes.
+    fn width_ends(&self) -> &[Count] {
+        self.1.get_or_init(|| {
+            let mut accum = 0;
+            self.0
+                .as_slice()
+                .iter()
+                .map(|measurable| {
+                    accum += measurable.width() as Count;
+                    accum
+                })
+                .collect()
+        })
+    }
+
+    /// Gets the width sum up to a given `index`, using the cached widths if
+    /// `C` enables them.
+    #[inline]
+    pub fn index_to_width(&self, index: usize) -> usize {
+        if caches_widths::<C>() {
+            ends_index_to_width(self.width_ends(), index)
+        } else {
+            index_to_width(self, index)
+        }
+    }
+
+    /// Finds the index of the element whose starting width sum matches
+    /// `width`, using the cached widths if `C` enables them.
+    #[inline]
+    pub fn start_width_to_index(&self, width: usize) -> usize {
+        if caches_widths::<C>() {
+            ends_start_width_to_index(self.width_ends(), width)
+        } else {
+            start_width_to_index(self, width)
+        }
+    }
+
+    /// Finds the index of the element whose ending width sum matches
+    /// `width`, using the cached widths if `C` enables them.
+    #[inline]
+    pub fn end_width_to_index(&self, width: usize) -> usize {
+        if caches_widths::<C>() {
+            ends_end_width_to_index(self.width_ends(), width)
+        } else {
+            end_width_to_index(self, width)
+        }
+    }
+}
+
+impl<M, C> Default for LeafSlice<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    fn default() -> Self {
+        Self(LeafSmallVec::default(), Default::default())
+    }
 }
 
-impl<M> std::cmp::PartialEq for LeafSlice<M>
+impl<M, C> std::cmp::PartialEq for LeafSlice<M, C>
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
     fn eq(&self, other: &Self) -> bool {
         let (s1, s2): (&[M], &[M]) = (self, other);
@@ -110,45 +198,50 @@ where
     }
 }
 
-impl<'a, M> PartialEq<LeafSlice<M>> for &'a [M]
+impl<'a, M, C> PartialEq<LeafSlice<M, C>> for &'a [M]
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
-    fn eq(&self, other: &LeafSlice<M>) -> bool {
+    fn eq(&self, other: &LeafSlice<M, C>) -> bool {
         *self == (other as &[M])
     }
 }
 
-impl<'a, M> PartialEq<&'a [M]> for LeafSlice<M>
+impl<'a, M, C> PartialEq<&'a [M]> for LeafSlice<M, C>
 where
     M: Measurable + PartialEq,
+    C: RopeConfig,
 {
     fn eq(&self, other: &&'a [M]) -> bool {
         (self as &[M]) == *other
     }
 }
 
-impl<M> std::fmt::Display for LeafSlice<M>
+impl<M, C> std::fmt::Display for LeafSlice<M, C>
 where
     M: Measurable + Display + Debug,
+    C: RopeConfig,
 {
     fn fmt(&self, fm: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
         LeafSlice::deref(self).fmt(fm)
     }
 }
 
-impl<M> std::fmt::Debug for LeafSlice<M>
+impl<M, C> std::fmt::Debug for LeafSlice<M, C>
 where
     M: Measurable + Debug,
+    C: RopeConfig,
 {
     fn fmt(&self, fm: &mut std::fmt::Formatter) -> std::fmt::Result {
         LeafSlice::deref(self).fmt(fm)
     }
 }
 
-impl<M> Deref for LeafSlice<M>
+impl<M, C> Deref for LeafSlice<M, C>
 where
     M: Measurable,
+    C: RopeConfig,
 {
     type Target = [M];
 
@@ -157,18 +250,20 @@ where
     }
 }
 
-impl<M> AsRef<[M]> for LeafSlice<M>
+impl<M, C> AsRef<[M]> for LeafSlice<M, C>
 where
     M: Measurable,
+    C: RopeConfig,
 {
     fn as_ref(&self) -> &[M] {
         self.0.as_slice()
     }
 }
 
-impl<M> Borrow<[M]> for LeafSlice<M>
+impl<M, C> Borrow<[M]> for LeafSlice<M, C>
 where
     M: Measurable,
+    C: RopeConfig,
 {
     fn borrow(&self) -> &[M] {
         self.0.as_slice()
@@ -182,40 +277,74 @@ where
 /// Try to keep this as small as possible, and implement functionality on
 /// NodeText via the safe APIs whenever possible.
 mod inner {
-    use crate::tree::MAX_LEN;
-    use smallvec::{Array, SmallVec};
+    use std::mem::MaybeUninit;
+    use std::ptr;
+
+    use crate::tree::{inline_len, MAX_LEAF_BYTES};
 
     use super::Measurable;
 
     /// The backing internal buffer type for `NodeText`.
+    ///
+    /// It always takes `MAX_LEAF_BYTES` of space, no matter the size of `M`,
+    /// so that nodes stay close to their target size for any element type.
+    /// The zero length array makes sure that it is aligned for `M`.
+    ///
+    /// `N` is only ever changed to measure the layout of leaves, when
+    /// `MAX_LEAF_BYTES` is computed.
     #[derive(Copy, Clone)]
-    struct BackingArray<M>([M; MAX_LEN])
-    where
-        M: Measurable;
-
-    /// We need a very specific size of array, which is not necessarily
-    /// supported directly by the impls in the smallvec crate.  We therefore
-    /// have to implement this unsafe trait for our specific array size.
-    /// TODO: once integer const generics land, and smallvec updates its APIs
-    /// to use them, switch over and get rid of this unsafe impl.
-    unsafe impl<M> Array for BackingArray<M>
+    #[repr(C)]
+    struct BackingArray<M, const N: usize = MAX_LEAF_BYTES> {
+        _align: [M; 0],
+        bytes: [MaybeUninit<u8>; N],
+    }
+
+    impl<M> BackingArray<M>
     where
         M: Measurable,
     {
-        type Item = M;
-        fn size() -> usize {
-            MAX_LEN
+        #[inline(always)]
+        fn uninit() -> Self {
+            BackingArray {
+                _align: [],
+                bytes: [MaybeUninit::uninit(); MAX_LEAF_BYTES],
+            }
+        }
+
+        #[inline(always)]
+        fn as_ptr(&self) -> *const M {
+            self.bytes.as_ptr() as *const M
         }
+
+        #[inline(always)]
+        fn as_mut_ptr(&mut self) -> *mut M {
+            self.bytes.as_mut_ptr() as *mut M
+        }
+    }
+
+    /// Where the elements of a `LeafSmallVec` are stored.
+    ///
+    /// Elements that don't fit in the inline array are moved onto the heap.
+    /// Since [Measurable] requires [Copy], elements never need to be dropped,
+    /// and can be freely moved around with plain memory copies.
+    // The size difference is the whole point of the inline storage.
+    #[allow(clippy::large_enum_variant)]
+    #[derive(Clone)]
+    enum Buffer<M, const N: usize = MAX_LEAF_BYTES> {
+        /// Invariants: `len <= inline_len::<M>()`, and the first `len`
+        /// elements of `array` are initialized.
+        Inline {
+            len: usize,
+            array: BackingArray<M, N>,
+        },
+        Heap(Vec<M>),
     }
 
     /// Internal small string for `NodeText`.
-    #[derive(Clone, Default)]
+    #[derive(Clone)]
     #[repr(C)]
-    pub struct LeafSmallVec<M>
-    where
-        M: Measurable,
-    {
-        buffer: SmallVec<BackingArray<M>>,
+    pub struct LeafSmallVec<M, const N: usize = MAX_LEAF_BYTES> {
+        buffer: Buffer<M, N>,
     }
 
     impl<M> LeafSmallVec<M>
@@ -224,9 +353,16 @@ mod inner {
     {
         #[inline(always)]
         pub fn with_capacity(capacity: usize) -> Self {
-            LeafSmallVec {
-                buffer: SmallVec::with_capacity(capacity),
-            }
+            let buffer = if capacity <= inline_len::<M>() {
+                Buffer::Inline {
+                    len: 0,
+                    array: BackingArray::uninit(),
+                }
+            } else {
+                Buffer::Heap(Vec::with_capacity(capacity))
+            };
+
+            LeafSmallVec { buffer }
         }
 
         #[inline(always)]
@@ -238,11 +374,38 @@ mod inner {
 
         #[inline(always)]
         pub fn len(&self) -> usize {
-            self.buffer.len()
+            match self.buffer {
+                Buffer::Inline { len, .. } => len,
+                Buffer::Heap(ref vec) => vec.len(),
+            }
+        }
+
+        /// How many elements fit without reallocating.
+        #[inline(always)]
+        pub fn capacity(&self) -> usize {
+            match self.buffer {
+                Buffer::Inline { .. } => inline_len::<M>(),
+                Buffer::Heap(ref vec) => vec.capacity(),
+            }
+        }
+
+        /// How many elements fit in the heap buffer, if there is one.
+        #[inline(always)]
+        pub fn heap_capacity(&self) -> usize {
+            match self.buffer {
+                Buffer::Inline { .. } => 0,
+                Buffer::Heap(ref vec) => vec.capacity(),
+            }
         }
 
         pub fn as_slice(&self) -> &[M] {
-            &self.buffer
+            match self.buffer {
+                // SAFETY: The first `len` elements are initialized.
+                Buffer::Inline { len, ref array } => unsafe {
+                    std::slice::from_raw_parts(array.as_ptr(), len)
+                },
+                Buffer::Heap(ref vec) => vec,
+            }
         }
 
         /// Inserts a [`&[Measurable]`][Measurable] at [`byte_idx`][usize].
@@ -250,9 +413,40 @@ mod inner {
         /// Panics on out-of-bounds or of [`byte_idx`][usize] isn't a char boundary.
         #[inline(always)]
         pub fn insert_slice(&mut self, byte_idx: usize, slice: &[M]) {
-            // Copy bytes from `slice` into the appropriate space in the
-            // buffer.
-            self.buffer.insert_from_slice(byte_idx, slice);
+            let len = self.len();
+            assert!(byte_idx <= len, "Insertion index out of bounds.");
+
+            match self.buffer {
+                Buffer::Inline {
+                    ref mut len,
+                    ref mut array,
+                } if *len + slice.len() <= inline_len::<M>() => {
+                    // SAFETY: Both the shifted elements and the inserted ones
+                    // stay within the inline capacity, and the destination
+                    // ends up fully initialized.
+                    unsafe {
+                        let ptr = array.as_mut_ptr();
+                        ptr::copy(
+                            ptr.add(byte_idx),
+                            ptr.add(byte_idx + slice.len()),
+                            *len - byte_idx,
+                        );
+                        ptr::copy_nonoverlapping(slice.as_ptr(), ptr.add(byte_idx), slice.len());
+                    }
+                    *len += slice.len();
+                }
+                Buffer::Inline { .. } => {
+                    // Spill onto the heap.
+                    let mut vec = Vec::with_capacity(len + slice.len());
+                    vec.extend_from_slice(&self.as_slice()[..byte_idx]);
+                    vec.extend_from_slice(slice);
+                    vec.extend_from_slice(&self.as_slice()[byte_idx..]);
+                    self.buffer = Buffer::Heap(vec);
+                }
+                Buffer::Heap(ref mut vec) => {
+                    vec.splice(byte_idx..byte_idx, slice.iter().copied());
+                }
+            }
         }
 
         /// Removes text in range `[start_byte_idx, end_byte_idx)`
@@ -261,14 +455,29 @@ mod inner {
         #[inline(always)]
         pub fn remove_range(&mut self, start_byte_idx: usize, end_byte_idx: usize) {
             assert!(start_byte_idx <= end_byte_idx);
-            // Already checked by copy_within/is_char_boundary.
-            debug_assert!(end_byte_idx <= self.len());
-            let len = self.len();
-            let amt = end_byte_idx - start_byte_idx;
-
-            self.buffer.copy_within(end_byte_idx..len, start_byte_idx);
-
-            self.buffer.truncate(len - amt);
+            assert!(end_byte_idx <= self.len(), "Removal range out of bounds.");
+
+            match self.buffer {
+                Buffer::Inline {
+                    ref mut len,
+                    ref mut array,
+                } => {
+                    // SAFETY: Only initialized elements are moved, and they
+                    // are moved within the inline capacity.
+                    unsafe {
+                        let ptr = array.as_mut_ptr();
+                        ptr::copy(
+                            ptr.add(end_byte_idx),
+                            ptr.add(start_byte_idx),
+                            *len - end_byte_idx,
+                        );
+                    }
+                    *len -= end_byte_idx - start_byte_idx;
+                }
+                Buffer::Heap(ref mut vec) => {
+                    vec.drain(start_byte_idx..end_byte_idx);
+                }
+            }
         }
 
         /// Removes text after `byte_idx`.
@@ -276,7 +485,10 @@ mod inner {
         pub fn truncate(&mut self, byte_idx: usize) {
             // Already checked by is_char_boundary.
             debug_assert!(byte_idx <= self.len());
-            self.buffer.truncate(byte_idx);
+            match self.buffer {
+                Buffer::Inline { ref mut len, .. } => *len = byte_idx.min(*len),
+                Buffer::Heap(ref mut vec) => vec.truncate(byte_idx),
+            }
         }
 
         /// Splits at `byte_idx`, returning the right part and leaving the
@@ -287,10 +499,8 @@ mod inner {
         pub fn split_off(&mut self, byte_idx: usize) -> Self {
             // Already checked by is_char_boundary.
             debug_assert!(byte_idx <= self.len());
-            let len = self.len();
-            let mut other = LeafSmallVec::with_capacity(len - byte_idx);
-            other.buffer.extend_from_slice(&self.buffer[byte_idx..]);
-            self.buffer.truncate(byte_idx);
+            let other = LeafSmallVec::from_slice(&self.as_slice()[byte_idx..]);
+            self.truncate(byte_idx);
             other
         }
 
@@ -298,18 +508,29 @@ mod inner {
         /// fit inline.
         #[inline(always)]
         pub fn inline_if_possible(&mut self) {
-            if self.buffer.spilled() && (self.buffer.len() <= self.buffer.inline_size()) {
-                self.buffer.shrink_to_fit();
+            if let Buffer::Heap(ref vec) = self.buffer {
+                if vec.len() <= inline_len::<M>() {
+                    *self = LeafSmallVec::from_slice(vec);
+                }
             }
         }
     }
 
+    impl<M> Default for LeafSmallVec<M>
+    where
+        M: Measurable,
+    {
+        fn default() -> Self {
+            LeafSmallVec::with_capacity(0)
+        }
+    }
+
     //-----------------------------------------------------------------------
 
     #[cfg(test)]
     mod tests {
         use super::*;
-        use crate::Lipsum::*;
+        use crate::Lipsum::{self, *};
 
         #[test]
         fn vec_basics() {
@@ -375,6 +596,37 @@ mod inner {
             assert_eq!(vec_2.as_slice(), &[Sit, Amet]);
         }
 
+        #[test]
+        fn spill_01() {
+            let slice = [Lorem, Ipsum, Dolor(3), Sit, Amet, Consectur("hi")];
+            assert!(slice.len() > inline_len::<Lipsum>());
+
+            let mut vec = LeafSmallVec::from_slice(&slice[..1]);
+            assert!(matches!(vec.buffer, Buffer::Inline { .. }));
+
+            vec.insert_slice(1, &slice[1..]);
+            assert!(matches!(vec.buffer, Buffer::Heap(_)));
+            assert_eq!(vec.as_slice(), &slice);
+
+            vec.remove_range(1, 6);
+            vec.inline_if_possible();
+            assert!(matches!(vec.buffer, Buffer::Inline { .. }));
+            assert_eq!(vec.as_slice(), &[Lorem]);
+        }
+
+        #[test]
+        fn inline_01() {
+            let mut vec = LeafSmallVec::from_slice(&[Lorem]);
+            vec.insert_slice(0, &[Sit]);
+            vec.insert_slice(1, &[]);
+            assert!(matches!(vec.buffer, Buffer::Inline { .. }));
+            assert_eq!(vec.as_slice(), &[Sit, Lorem]);
+
+            let vec_2 = vec.split_off(1);
+            assert_eq!(vec.as_slice(), &[Sit]);
+            assert_eq!(vec_2.as_slice(), &[Lorem]);
+        }
+
         #[test]
         #[should_panic]
         fn split_off_02() {
@@ -383,3 +635,71 @@ mod inner {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CachedConfig, DefaultConfig};
+    use crate::slice_utils::width_of;
+    use crate::Lipsum::{self, *};
+
+    #[test]
+    fn cached_widths_01() {
+        let slices: [&[Lipsum]; 5] = [
+            &[Lorem, Ipsum, Dolor(4), Sit, Amet, Consectur("hi")],
+            &[Sit, Amet, Lorem, Sit, Sit, Dolor(0), Ipsum, Amet],
+            &[Dolor(3)],
+            &[Sit],
+            &[],
+        ];
+
+        for slice in slices {
+            let cached = LeafSlice::<_, CachedConfig>::from_slice(slice);
+            let uncached = LeafSlice::<_, DefaultConfig>::from_slice(slice);
+            for index in 0..=slice.len() {
+                assert_eq!(cached.index_to_width(index), uncached.index_to_width(index));
+            }
+            for width in 0..=width_of(slice) {
+                assert_eq!(
+                    cached.start_width_to_index(width),
+                    uncached.start_width_to_index(width)
+                );
+                assert_eq!(
+                    cached.end_width_to_index(width),
+                    uncached.end_width_to_index(width)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn cached_widths_02() {
+        let mut slice = LeafSlice::<_, CachedConfig>::from_slice(&[Lorem, Ipsum, Dolor(4)]);
+        assert_eq!(slice.start_width_to_index(3), 2);
+
+        // Changes to the leaf throw away the old widths.
+        slice.insert_slice(0, &[Dolor(10), Sit]);
+        assert_eq!(slice.start_width_to_index(3), 0);
+        assert_eq!(slice.end_width_to_index(10), 2);
+
+        slice.remove_range(0, 2);
+        assert_eq!(slice.index_to_width(3), 7);
+
+        let right = slice.split_off(1);
+        assert_eq!(slice.index_to_width(1), 1);
+        assert_eq!(right.index_to_width(2), 6);
+    }
+
+    #[test]
+    fn uncached_size_01() {
+        // Without caching, leaves take no space for the cache.
+        assert_eq!(
+            std::mem::size_of::<LeafSlice<Lipsum, DefaultConfig>>(),
+            std::mem::size_of::<LeafSmallVec<Lipsum>>()
+        );
+        assert!(
+            std::mem::size_of::<LeafSlice<Lipsum, CachedConfig>>()
+                > std::mem::size_of::<LeafSmallVec<Lipsum>>()
+        );
+    }
+}
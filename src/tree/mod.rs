@@ -1,21 +1,37 @@
+mod accum_cache;
 mod node;
 mod branch_children;
 mod leaf_slice;
 mod slice_info;
 
-pub(crate) use self::node::Node;
+pub(crate) use self::accum_cache::{AccumCache, CacheStorage, NoAccumCache};
 pub(crate) use self::branch_children::BranchChildren;
 pub(crate) use self::leaf_slice::LeafSlice;
+pub(crate) use self::node::Node;
 pub(crate) use self::slice_info::SliceInfo;
 
+use crate::config::{CacheMode, RopeConfig};
+
 // Type used for storing tree metadata, such as indices and widths.
 pub(crate) type Count = u64;
 
+// What the nodes of a tree with a given config store for their caches.
+pub(crate) type WidthCache<C, T> = <<C as RopeConfig>::Cache as CacheMode>::Storage<T>;
+
+// Whether the nodes of a tree with a given config cache their widths.
+#[inline(always)]
+pub(crate) fn caches_widths<C: RopeConfig>() -> bool {
+    C::Cache::ENABLED
+}
+
 // Real constants used in release builds.
-#[cfg(not(any(test, feature = "small_chunks")))]
+#[cfg(not(test))]
 mod constants {
-    use super::SliceInfo;
-    use smallvec::SmallVec;
+    use super::leaf_slice::LeafSmallVec;
+    use super::{Node, SliceInfo};
+    use crate::config::DefaultConfig;
+    use crate::rope::Measurable;
+    use crate::Lipsum;
     use std::{
         mem::{align_of, size_of},
         sync::Arc,
@@ -39,9 +55,17 @@ mod constants {
     // Space that the strong and weak Arc counters take up in `ArcInner`.
     const ARC_COUNTERS_SIZE: usize = size_of::<std::sync::atomic::AtomicUsize>() * 2;
 
+    // A leaf with this many inline bytes is sized by them, rather than by its
+    // heap `Vec`, so it can be used to measure the layout of every leaf.
+    type ProbeLeaf = LeafSmallVec<u8, 64>;
+
+    // What a branch stores for each child, besides the child itself: its info,
+    // and whether it ends in 0 width elements.
+    type ChildInfo = (SliceInfo, bool);
+
     // Misc useful info that we need below.
-    const NODE_CHILDREN_ALIGN: usize = cmax(align_of::<Arc<u8>>(), align_of::<SliceInfo>());
-    const NODE_TEXT_ALIGN: usize = align_of::<SmallVec<[u8; 16]>>();
+    const NODE_CHILDREN_ALIGN: usize = cmax(align_of::<Arc<u8>>(), align_of::<ChildInfo>());
+    const NODE_TEXT_ALIGN: usize = align_of::<ProbeLeaf>();
     const START_OFFSET: usize = {
         const NODE_INNER_ALIGN: usize = cmax(NODE_CHILDREN_ALIGN, NODE_TEXT_ALIGN);
         // The +NODE_INNER_ALIGN is because of Node's enum discriminant.
@@ -51,7 +75,7 @@ mod constants {
     // Node maximums.
     pub(crate) const MAX_CHILDREN: usize = {
         let node_list_align = align_of::<Arc<u8>>();
-        let info_list_align = align_of::<SliceInfo>();
+        let info_list_align = align_of::<ChildInfo>();
         let field_gap = if node_list_align >= info_list_align {
             0
         } else {
@@ -64,35 +88,91 @@ mod constants {
         // The -NODE_CHILDREN_ALIGN is for the `len` field in `NodeChildrenInternal`.
         let target_size = TARGET_TOTAL_SIZE - START_OFFSET - NODE_CHILDREN_ALIGN - field_gap;
 
-        target_size / (size_of::<Arc<u8>>() + size_of::<SliceInfo>())
+        target_size / (size_of::<Arc<u8>>() + size_of::<ChildInfo>())
     };
-    pub(crate) const MAX_LEN: usize = {
-        let smallvec_overhead = size_of::<SmallVec<[u8; 16]>>() - 16;
-        TARGET_TOTAL_SIZE - START_OFFSET - smallvec_overhead
+    // The space, in bytes, that a leaf has for storing its elements inline.
+    pub(crate) const MAX_LEAF_BYTES: usize = {
+        // The length and variant of the leaf's buffer.
+        let leaf_overhead = size_of::<ProbeLeaf>() - 64;
+        TARGET_TOTAL_SIZE - START_OFFSET - leaf_overhead
     };
 
-    // Node minimums.
-    // Note: MIN_LEN is intentionally a little smaller than half
-    // MAX_LEN, to give a little wiggle room when on the edge of
-    // merging/splitting.
-    pub(crate) const MIN_CHILDREN: usize = MAX_CHILDREN / 2;
-    pub(crate) const MIN_LEN: usize = (MAX_LEN / 2) - (MAX_LEN / 32);
+    // Checks that nodes end up within the target size, no matter the size of
+    // the elements, since leaves always store the same amount of bytes.
+    const _: () = {
+        #[derive(Clone, Copy)]
+        struct Big([u64; 256]);
+
+        impl Measurable for Big {
+            fn width(&self) -> usize {
+                self.0.len()
+            }
+        }
+
+        assert!(ARC_COUNTERS_SIZE + size_of::<Node<Lipsum, DefaultConfig>>() <= TARGET_TOTAL_SIZE);
+        assert!(ARC_COUNTERS_SIZE + size_of::<Node<Big, DefaultConfig>>() <= TARGET_TOTAL_SIZE);
+    };
 }
 
 // Smaller constants used in debug builds. These are different from release
 // in order to trigger deeper trees without having to use huge slice data in
 // the tests.
-#[cfg(any(test, feature = "small_chunks"))]
-mod test_constants {
-    pub(crate) const MAX_CHILDREN: usize = 5;
-    pub(crate) const MIN_CHILDREN: usize = MAX_CHILDREN / 2;
+#[cfg(test)]
+pub(crate) const MAX_CHILDREN: usize = 5;
 
-    pub(crate) const MAX_LEN: usize = 9;
-    pub(crate) const MIN_LEN: usize = (MAX_LEN / 2) - (MAX_LEN / 32);
-}
+// Small enough that most test leaves get spilled onto the heap.
+#[cfg(test)]
+pub(crate) const MAX_LEAF_BYTES: usize = 64;
 
+// These are used by the `DefaultConfig`. `MAX_CHILDREN` is also the capacity
+// of every branch node, and `MAX_LEAF_BYTES` the inline capacity of every leaf
+// node, no matter the `RopeConfig`.
 #[cfg(not(test))]
-pub(crate) use self::constants::{MAX_CHILDREN, MAX_LEN, MIN_CHILDREN, MIN_LEN};
+pub(crate) use self::constants::{MAX_CHILDREN, MAX_LEAF_BYTES};
 
-#[cfg(test)]
-pub(crate) use self::test_constants::{MAX_CHILDREN, MAX_LEN, MIN_CHILDREN, MIN_LEN};
+// Leaves need room for at least a few elements, or else splitting and
+// merging them stops making sense.
+const MIN_MAX_LEN: usize = 8;
+
+// Branch maximum, in children, for a given config.
+#[inline(always)]
+pub(crate) fn max_children<C: RopeConfig>() -> usize {
+    C::MAX_CHILDREN
+}
+
+// Branch minimum, in children, for a given config.
+#[inline(always)]
+pub(crate) fn min_children<C: RopeConfig>() -> usize {
+    C::MAX_CHILDREN / 2
+}
+
+// Leaf maximum, in elements, for a given element type and config.
+#[inline(always)]
+pub(crate) fn max_len<M, C: RopeConfig>() -> usize {
+    C::max_len::<M>().max(MIN_MAX_LEN)
+}
+
+// Leaf minimum, in elements, for a given element type and config.
+// Note: this is intentionally a little smaller than half of `max_len()`, to
+// give a little wiggle room when on the edge of merging/splitting.
+#[inline(always)]
+pub(crate) fn min_len<M, C: RopeConfig>() -> usize {
+    (max_len::<M, C>() / 2) - (max_len::<M, C>() / 32)
+}
+
+// Below this many elements, a branch full of leaves is considered to be close
+// to maximum leaf fragmentation, and gets its leaves compacted.
+// The constant here was arrived at experimentally, and is otherwise fairly
+// arbitrary.
+#[inline(always)]
+pub(crate) fn frag_min_len<M, C: RopeConfig>() -> usize {
+    (max_len::<M, C>() * min_children::<C>()) + (max_len::<M, C>() / 32)
+}
+
+// How many elements fit in the inline storage of a leaf.
+pub(crate) const fn inline_len<M>() -> usize {
+    match std::mem::size_of::<M>() {
+        0 => usize::MAX,
+        size => MAX_LEAF_BYTES / size,
+    }
+}
@@ -1,24 +1,27 @@
 use std::sync::Arc;
 
+use crate::config::RopeConfig;
 use crate::rope::Measurable;
-use crate::slice_utils::{start_width_to_index, index_to_width, end_width_to_index};
+use crate::slice_utils::end_width_to_index;
 use crate::tree::{
-    BranchChildren, Count, LeafSlice, SliceInfo, MAX_LEN, MAX_CHILDREN, MIN_LEN, MIN_CHILDREN,
+    frag_min_len, max_children, min_children, min_len, BranchChildren, Count, LeafSlice, SliceInfo,
 };
 
 #[derive(Debug, Clone)]
 #[repr(u8, C)]
-pub(crate) enum Node<M>
+pub(crate) enum Node<M, C>
 where
     M: Measurable,
+    C: RopeConfig,
 {
-    Leaf(LeafSlice<M>),
-    Branch(BranchChildren<M>),
+    Leaf(LeafSlice<M, C>),
+    Branch(BranchChildren<M, C>),
 }
 
-impl<M> Node<M>
+impl<M, C> Node<M, C>
 where
     M: Measurable,
+    C: RopeConfig,
 {
     /// Creates an empty [Node<M>].
     #[inline(always)]
@@ -41,7 +44,7 @@ where
     /// Fetches a chunk mutably, and allows it to be edited via a closure.
     ///
     /// There are three parameters:
-    /// - width: the chunk that contains this width is fetched,
+    /// - index: the chunk that contains this index is fetched,
     /// - node_info: this is the [SliceInfo] of the node it's being called on.
     ///              This makes it a little awkward to call, but is needed since
     ///              it's actually the parent node that contains the [SliceInfo],
@@ -50,9 +53,9 @@ where
     ///
     /// The closure is effectively the termination case for the recursion,
     /// and takes essentially same parameters and returns the same things as
-    /// the method itself. In particular, the closure receives the width offset
-    /// of the width within the given chunk and the [SliceInfo] of the chunk.
-    /// The main difference is that it receives a [LeafSlice<M>] instead of a node.
+    /// the method itself. In particular, the closure receives the offset of
+    /// the index within the given chunk and the [SliceInfo] of the chunk.
+    /// The main difference is that it receives a [LeafSlice<M, C>] instead of a node.
     ///
     /// The closure is expected to return the updated [SliceInfo] of the [Node<M>],
     /// and if the node had to be split, then it also returns the right-hand
@@ -63,49 +66,78 @@ where
     /// It is up to the caller to check for that new [Node<M>], and handle it by
     /// creating a new root with both the original [Node<M>] and the new node as
     /// children.
-    pub fn edit_chunk_at_width<F>(
+    ///
+    /// One-past-the-end is valid, and fetches the last chunk.
+    pub fn edit_chunk_at_index<F>(
         &mut self,
-        width: usize,
+        index: usize,
         node_info: SliceInfo,
+        edit: F,
+    ) -> (SliceInfo, Option<(SliceInfo, Arc<Node<M, C>>)>)
+    where
+        F: FnMut(
+            usize,
+            SliceInfo,
+            &mut LeafSlice<M, C>,
+        ) -> (SliceInfo, Option<(SliceInfo, Arc<Node<M, C>>)>),
+    {
+        self.edit_chunk_by(
+            index,
+            node_info,
+            &|children, index| {
+                let (child_i, acc_info) = children.search_index(index);
+                (child_i, acc_info.len as usize)
+            },
+            edit,
+        )
+    }
+
+    /// Shared implementation of the `edit_chunk_at_*` methods.
+    ///
+    /// `search` returns the index of the child containing `pos`, and the
+    /// position at the start of said child.
+    fn edit_chunk_by<S, F>(
+        &mut self,
+        pos: usize,
+        node_info: SliceInfo,
+        search: &S,
         mut edit: F,
-    ) -> (SliceInfo, Option<(SliceInfo, Arc<Node<M>>)>)
+    ) -> (SliceInfo, Option<(SliceInfo, Arc<Node<M, C>>)>)
     where
+        S: Fn(&BranchChildren<M, C>, usize) -> (usize, usize),
         F: FnMut(
             usize,
             SliceInfo,
-            &mut LeafSlice<M>,
-        ) -> (SliceInfo, Option<(SliceInfo, Arc<Node<M>>)>),
+            &mut LeafSlice<M, C>,
+        ) -> (SliceInfo, Option<(SliceInfo, Arc<Node<M, C>>)>),
     {
         match *self {
-            Node::Leaf(ref mut slice) => edit(width, node_info, slice),
+            Node::Leaf(ref mut slice) => edit(pos, node_info, slice),
             Node::Branch(ref mut children) => {
                 // Compact leaf children if we're very close to maximum leaf
                 // fragmentation. This basically guards against excessive memory
                 // ballooning when repeatedly appending to the end of a rope.
-                // The constant here was arrived at experimentally, and is otherwise
-                // fairly arbitrary.
-                const FRAG_MIN_BYTES: usize = (MAX_LEN * MIN_CHILDREN) + (MAX_LEN / 32);
                 if children.is_full()
                     && children.nodes()[0].is_leaf()
-                    && (children.combined_info().len as usize) < FRAG_MIN_BYTES
+                    && (children.combined_info().len as usize) < frag_min_len::<M, C>()
                 {
                     children.compact_leaves();
                 }
 
                 // Find the child we care about.
-                let (child_i, acc_width) = children.search_width_only(width);
+                let (child_i, acc_pos) = search(children, pos);
                 let (info, _) = children.info()[child_i];
 
                 // Recurse into the child.
                 let (l_info, residual) = Arc::make_mut(&mut children.nodes_mut()[child_i])
-                    .edit_chunk_at_width(width - acc_width, info, edit);
+                    .edit_chunk_by(pos - acc_pos, info, search, edit);
 
                 let zero_width_end = children.nodes()[child_i].zero_width_end();
                 children.info_mut()[child_i] = (l_info, zero_width_end);
 
                 // Handle the residual node if there is one and return.
                 if let Some((r_info, r_node)) = residual {
-                    if children.len() < MAX_CHILDREN {
+                    if children.len() < max_children::<C>() {
                         children.insert(child_i + 1, (r_info, r_node));
                         (node_info - info + l_info + r_info, None)
                     } else {
@@ -127,29 +159,20 @@ where
     ///
     /// Returns (in this order):
     /// - The updated [SliceInfo] for the node.
-    /// - Whether [fix_tree_seam()][Node::fix_tree_seam] needs to be run after this.
+    /// - Whether [fix_tree_seam_at_index()][Node::fix_tree_seam_at_index] needs
+    ///   to be run after this.
     ///
     /// WARNING: does not correctly handle all slice being removed. That
     /// should be special-cased in calling code.
     pub fn remove_range(
         &mut self,
-        start_width: usize,
-        end_width: usize,
+        start_index: usize,
+        end_index: usize,
         node_info: SliceInfo,
     ) -> (SliceInfo, bool) {
         match *self {
             // If it's a leaf
             Node::Leaf(ref mut slice) => {
-                let start_index = start_width_to_index(slice, start_width);
-
-                // In this circumstance, nothing needs to be done, since we're removing
-                // in the middle of an element.
-                if start_width == end_width && slice[start_index].width() > 0 {
-                    return (SliceInfo::from_slice(slice), false);
-                }
-
-                let end_index = end_width_to_index(slice, end_width);
-
                 // Remove slice and calculate new info & seam info.
                 if start_index > 0 || end_index < slice.len() {
                     let seg_len = end_index - start_index; // Length of removal segement
@@ -183,17 +206,17 @@ where
                 // - Whether there's a possible CRLF seam that needs fixing.
                 // - Whether the tree may need invariant fixing.
                 // - Updated [SliceInfo] of the node.
-                let handle_child = |children: &mut BranchChildren<M>,
+                let handle_child = |children: &mut BranchChildren<M, C>,
                                     child_i: usize,
-                                    c_width_acc: usize|
+                                    c_index_acc: usize|
                  -> (bool, SliceInfo) {
                     // Recurse into child
                     let (tmp_info, _) = children.info()[child_i];
-                    let tmp_width = children.info()[child_i].0.width as usize;
+                    let tmp_len = tmp_info.len as usize;
                     let (new_info, needs_fix) = Arc::make_mut(&mut children.nodes_mut()[child_i])
                         .remove_range(
-                            start_width - c_width_acc.min(start_width),
-                            (end_width - c_width_acc).min(tmp_width),
+                            start_index - c_index_acc.min(start_index),
+                            (end_index - c_index_acc).min(tmp_len),
                             tmp_info,
                         );
 
@@ -209,7 +232,7 @@ where
                 };
 
                 // Shared code for merging children
-                let merge_child = |children: &mut BranchChildren<M>, child_i: usize| {
+                let merge_child = |children: &mut BranchChildren<M, C>, child_i: usize| {
                     if child_i < children.len()
                         && children.len() > 1
                         && children.nodes()[child_i].is_undersized()
@@ -222,14 +245,14 @@ where
                     }
                 };
 
-                // Get child info for the two widths
-                let ((l_child_i, l_width_acc), (r_child_i, r_width_acc)) =
-                    children.search_width_range(start_width, end_width);
+                // Get child info for the two indices
+                let ((l_child_i, l_index_acc), (r_child_i, r_index_acc)) =
+                    children.search_index_range(start_index, end_index);
 
                 // Both indices point into the same child
                 if l_child_i == r_child_i {
                     let (info, _) = children.info()[l_child_i];
-                    let (mut needs_fix, new_info) = handle_child(children, l_child_i, l_width_acc);
+                    let (mut needs_fix, new_info) = handle_child(children, l_child_i, l_index_acc);
 
                     if children.len() > 0 {
                         merge_child(children, l_child_i);
@@ -251,7 +274,7 @@ where
                     let r_child_exists: bool;
                     let start_i = l_child_i + 1;
                     let end_i =
-                        if r_width_acc + children.info()[r_child_i].0.width as usize == end_width {
+                        if r_index_acc + children.info()[r_child_i].0.len as usize == end_index {
                             r_child_exists = false;
                             r_child_i + 1
                         } else {
@@ -266,12 +289,12 @@ where
 
                     // Handle right child
                     if r_child_exists {
-                        let (fix, _) = handle_child(children, l_child_i + 1, r_width_acc);
+                        let (fix, _) = handle_child(children, l_child_i + 1, r_index_acc);
                         needs_fix |= fix;
                     }
 
                     // Handle left child
-                    let (fix, _) = handle_child(children, l_child_i, l_width_acc);
+                    let (fix, _) = handle_child(children, l_child_i, l_index_acc);
                     needs_fix |= fix;
 
                     if children.len() > 0 {
@@ -295,11 +318,15 @@ where
         }
     }
 
-    pub fn append_at_depth(&mut self, mut other: Arc<Node<M>>, depth: usize) -> Option<Arc<Self>> {
+    pub fn append_at_depth(
+        &mut self,
+        mut other: Arc<Node<M, C>>,
+        depth: usize,
+    ) -> Option<Arc<Self>> {
         if depth == 0 {
             if let Node::Branch(ref mut children_l) = *self {
                 if let Node::Branch(ref mut children_r) = *Arc::make_mut(&mut other) {
-                    if (children_l.len() + children_r.len()) <= MAX_CHILDREN {
+                    if (children_l.len() + children_r.len()) <= max_children::<C>() {
                         for _ in 0..children_r.len() {
                             children_l.push(children_r.remove(0));
                         }
@@ -322,7 +349,7 @@ where
                 Arc::make_mut(&mut children.nodes_mut()[last_i]).append_at_depth(other, depth - 1);
             children.update_child_info(last_i);
             if let Some(extra_node) = residual {
-                if children.len() < MAX_CHILDREN {
+                if children.len() < max_children::<C>() {
                     children.push((extra_node.slice_info(), extra_node));
                     return None;
                 } else {
@@ -337,7 +364,7 @@ where
         }
     }
 
-    pub fn prepend_at_depth(&mut self, other: Arc<Node<M>>, depth: usize) -> Option<Arc<Self>> {
+    pub fn prepend_at_depth(&mut self, other: Arc<Node<M, C>>, depth: usize) -> Option<Arc<Self>> {
         if depth == 0 {
             match *self {
                 Node::Leaf(_) => {
@@ -350,7 +377,7 @@ where
                 Node::Branch(ref mut children_r) => {
                     let mut other = other;
                     if let Node::Branch(ref mut children_l) = *Arc::make_mut(&mut other) {
-                        if (children_l.len() + children_r.len()) <= MAX_CHILDREN {
+                        if (children_l.len() + children_r.len()) <= max_children::<C>() {
                             for _ in 0..children_l.len() {
                                 children_r.insert(0, children_l.pop());
                             }
@@ -370,7 +397,7 @@ where
                 Arc::make_mut(&mut children.nodes_mut()[0]).prepend_at_depth(other, depth - 1);
             children.update_child_info(0);
             if let Some(extra_node) = residual {
-                if children.len() < MAX_CHILDREN {
+                if children.len() < max_children::<C>() {
                     children.insert(0, (extra_node.slice_info(), extra_node));
                     return None;
                 } else {
@@ -422,29 +449,26 @@ where
         }
     }
 
-    /// Splits the [Node<M>] index `width`, returning the right side of the split.
-    pub fn start_split(&mut self, width: usize) -> Self {
-        debug_assert!(width != 0);
-        debug_assert!(width != (self.slice_info().width as usize));
+    /// Splits the [Node<M>] at `index`, returning the right side of the split.
+    ///
+    /// Unlike [end_split()][Node::end_split], this splits at an exact element,
+    /// which makes a difference when there are 0 width elements at the split.
+    pub fn split_at_index(&mut self, index: usize) -> Self {
+        debug_assert!(index != 0);
+        debug_assert!(index != (self.slice_info().len as usize));
         match *self {
-            Node::Leaf(ref mut slice) => {
-                let index = start_width_to_index(slice, width);
-                Node::Leaf(slice.split_off(index))
-            }
+            Node::Leaf(ref mut slice) => Node::Leaf(slice.split_off(index)),
             Node::Branch(ref mut children) => {
-                let (child_i, acc_info) = children.search_start_width(width);
-                let (child_info, _) = children.info()[child_i];
+                let (child_i, acc_info) = children.search_index(index);
 
-                if width == acc_info.width as usize {
+                if index == acc_info.len as usize {
                     Node::Branch(children.split_off(child_i))
-                } else if width == (acc_info.width as usize + child_info.width as usize) {
-                    Node::Branch(children.split_off(child_i + 1))
                 } else {
                     let mut r_children = children.split_off(child_i + 1);
 
                     // Recurse
                     let r_node = Arc::make_mut(&mut children.nodes_mut()[child_i])
-                        .end_split(width - acc_info.width as usize);
+                        .split_at_index(index - acc_info.len as usize);
 
                     r_children.insert(0, (r_node.slice_info(), Arc::new(r_node)));
 
@@ -457,9 +481,69 @@ where
         }
     }
 
+    /// Packs the leaves that overlap the index range `[start, end)` into as
+    /// few leaves as possible, merging and redistributing the branches above
+    /// them as needed.
+    ///
+    /// Leaves that share a parent with the leaves in the range are packed as
+    /// well. The children of this [Node<M>] are left with valid sizes, but it
+    /// may itself end up undersized, for its parent to fix.
+    pub fn compact_range(&mut self, start: usize, end: usize) {
+        let Node::Branch(ref mut children) = *self else {
+            return;
+        };
+
+        if children.nodes()[0].is_leaf() {
+            children.compact_leaves();
+            return;
+        }
+
+        // Compact the children in the range.
+        let ((first, mut child_start), (last, _)) = children.search_index_range(start, end);
+        for child_i in first..=last {
+            let child_end = child_start + children.info()[child_i].0.len as usize;
+            Arc::make_mut(&mut children.nodes_mut()[child_i]).compact_range(
+                start.saturating_sub(child_start),
+                end.min(child_end).saturating_sub(child_start),
+            );
+            children.update_child_info(child_i);
+            child_start = child_end;
+        }
+
+        // Then merge them with each other, and with their direct neighbors.
+        let mut child_i = first.saturating_sub(1);
+        let mut last = (last + 1).min(children.len() - 1);
+        while child_i < last {
+            let child_count = children.nodes()[child_i].child_count()
+                + children.nodes()[child_i + 1].child_count();
+
+            if child_count <= max_children::<C>() {
+                children.merge_distribute(child_i, child_i + 1);
+                last -= 1;
+
+                // The leaves at the seam between both children can now be
+                // compacted together.
+                let len = children.info()[child_i].0.len as usize;
+                Arc::make_mut(&mut children.nodes_mut()[child_i]).compact_range(0, len);
+                children.update_child_info(child_i);
+            } else {
+                if children.nodes()[child_i].is_undersized()
+                    || children.nodes()[child_i + 1].is_undersized()
+                {
+                    children.merge_distribute(child_i, child_i + 1);
+                }
+                child_i += 1;
+            }
+        }
+
+        if last > 0 && children.nodes()[last].is_undersized() {
+            children.merge_distribute(last - 1, last);
+        }
+    }
+
     /// Returns the chunk that contains the given index, and the [SliceInfo]
     /// corresponding to the start of the chunk.
-    pub fn get_chunk_at_index(&self, mut index: usize) -> (&[M], SliceInfo) {
+    pub fn get_chunk_at_index(&self, mut index: usize) -> (&LeafSlice<M, C>, SliceInfo) {
         let mut node = self;
         let mut info = SliceInfo::new();
 
@@ -478,9 +562,36 @@ where
         }
     }
 
+    /// Returns the leaf [Node<M>] that contains the given index, and the
+    /// [SliceInfo] corresponding to the start of that leaf.
+    ///
+    /// Same as [get_chunk_at_index()][Node::get_chunk_at_index], except that
+    /// it hands out a shared pointer to the leaf, instead of borrowing it.
+    pub fn get_leaf_at_index(
+        node: &Arc<Node<M, C>>,
+        mut index: usize,
+    ) -> (Arc<Node<M, C>>, SliceInfo) {
+        let mut node = node;
+        let mut info = SliceInfo::new();
+
+        loop {
+            match **node {
+                Node::Leaf(_) => {
+                    return (Arc::clone(node), info);
+                }
+                Node::Branch(ref children) => {
+                    let (child_i, acc_info) = children.search_index(index);
+                    info += acc_info;
+                    node = &children.nodes()[child_i];
+                    index -= acc_info.len as usize;
+                }
+            }
+        }
+    }
+
     /// Returns the chunk that contains the given width, and the [SliceInfo]
     /// corresponding to the start of the chunk.
-    pub fn get_first_chunk_at_width(&self, mut width: usize) -> (&[M], SliceInfo) {
+    pub fn get_first_chunk_at_width(&self, mut width: usize) -> (&LeafSlice<M, C>, SliceInfo) {
         let mut node = self;
         let mut info = SliceInfo::new();
 
@@ -502,7 +613,7 @@ where
 
     /// Returns the chunk that contains the given width, and the [SliceInfo]
     /// corresponding to the start of the chunk.
-    pub fn get_last_chunk_at_width(&self, mut width: usize) -> (&[M], SliceInfo) {
+    pub fn get_last_chunk_at_width(&self, mut width: usize) -> (&LeafSlice<M, C>, SliceInfo) {
         let mut node = self;
         let mut info = SliceInfo::new();
 
@@ -526,7 +637,7 @@ where
     #[inline(always)]
     pub fn start_width_to_slice_info(&self, width: usize) -> SliceInfo {
         let (chunk, info) = self.get_first_chunk_at_width(width);
-        let bi = start_width_to_index(chunk, width - info.width as usize);
+        let bi = chunk.start_width_to_index(width - info.width as usize);
         SliceInfo {
             len: info.len + bi as Count,
             width: width as Count,
@@ -537,7 +648,7 @@ where
     #[inline(always)]
     pub fn end_width_to_slice_info(&self, width: usize) -> SliceInfo {
         let (chunk, info) = self.get_last_chunk_at_width(width);
-        let bi = end_width_to_index(chunk, width - info.width as usize);
+        let bi = chunk.end_width_to_index(width - info.width as usize);
         SliceInfo {
             len: info.len + bi as Count,
             width: width as Count,
@@ -548,7 +659,7 @@ where
     #[inline(always)]
     pub fn index_to_slice_info(&self, index: usize) -> SliceInfo {
         let (chunk, info) = self.get_chunk_at_index(index);
-        let width = index_to_width(chunk, index - info.len as usize);
+        let width = chunk.index_to_width(index - info.len as usize);
         SliceInfo {
             len: index as Count,
             width: info.width + width as Count,
@@ -572,14 +683,14 @@ where
         }
     }
 
-    pub fn children(&self) -> &BranchChildren<M> {
+    pub fn children(&self) -> &BranchChildren<M, C> {
         match *self {
             Node::Branch(ref children) => children,
             _ => panic!(),
         }
     }
 
-    pub fn children_mut(&mut self) -> &mut BranchChildren<M> {
+    pub fn children_mut(&mut self) -> &mut BranchChildren<M, C> {
         match *self {
             Node::Branch(ref mut children) => children,
             _ => panic!(),
@@ -593,7 +704,7 @@ where
         }
     }
 
-    pub fn leaf_slice_mut(&mut self) -> &mut LeafSlice<M> {
+    pub fn leaf_slice_mut(&mut self) -> &mut LeafSlice<M, C> {
         match *self {
             Node::Leaf(ref mut slice) => slice,
             _ => panic!(),
@@ -609,8 +720,8 @@ where
 
     pub fn is_undersized(&self) -> bool {
         match *self {
-            Node::Leaf(ref slice) => slice.len() < MIN_LEN,
-            Node::Branch(ref children) => children.len() < MIN_CHILDREN,
+            Node::Leaf(ref slice) => slice.len() < min_len::<M, C>(),
+            Node::Branch(ref children) => children.len() < min_children::<C>(),
         }
     }
 
@@ -633,6 +744,28 @@ where
         }
     }
 
+    /// Bytes taken by the allocation of this [Node<M>] inside of its [Arc],
+    /// and by the heap buffers that it owns, not counting its children.
+    pub fn heap_bytes(&self) -> usize {
+        // The strong and weak counts come before the node itself.
+        let arc_bytes = 2 * std::mem::size_of::<usize>() + std::mem::size_of::<Self>();
+        arc_bytes
+            + match *self {
+                Node::Leaf(ref slice) => slice.heap_bytes(),
+                Node::Branch(ref children) => children.heap_bytes(),
+            }
+    }
+
+    /// The amount of leaves in the tree.
+    pub fn leaf_count(&self) -> usize {
+        match *self {
+            Node::Leaf(_) => 1,
+            Node::Branch(ref children) => {
+                children.nodes().iter().map(|node| node.leaf_count()).sum()
+            }
+        }
+    }
+
     /// Debugging tool to make sure that all of the meta-data of the
     /// tree is consistent with the actual data.
     pub fn assert_integrity(&self) {
@@ -678,7 +811,7 @@ where
                 if is_root {
                     assert!(children.len() > 1);
                 } else {
-                    assert!(children.len() >= MIN_CHILDREN);
+                    assert!(children.len() >= min_children::<C>());
                 }
 
                 for node in children.nodes() {
@@ -698,8 +831,8 @@ where
             loop {
                 let do_merge = (children.len() > 1)
                     && match *children.nodes()[0] {
-                        Node::Leaf(ref slice) => slice.len() < MIN_LEN,
-                        Node::Branch(ref children2) => children2.len() < MIN_CHILDREN,
+                        Node::Leaf(ref slice) => slice.len() < min_len::<M, C>(),
+                        Node::Branch(ref children2) => children2.len() < min_children::<C>(),
                     };
 
                 if do_merge {
@@ -727,8 +860,8 @@ where
                 let last_i = children.len() - 1;
                 let do_merge = (children.len() > 1)
                     && match *children.nodes()[last_i] {
-                        Node::Leaf(ref slice) => slice.len() < MIN_LEN,
-                        Node::Branch(ref children2) => children2.len() < MIN_CHILDREN,
+                        Node::Leaf(ref slice) => slice.len() < min_len::<M, C>(),
+                        Node::Branch(ref children2) => children2.len() < min_children::<C>(),
                     };
 
                 if do_merge {
@@ -759,8 +892,8 @@ where
                 if children.len() > 1 {
                     let (child_i, start_info) = children.search_start_width(widt);
                     let mut do_merge = match *children.nodes()[child_i] {
-                        Node::Leaf(ref slice) => slice.len() < MIN_LEN,
-                        Node::Branch(ref children2) => children2.len() < MIN_CHILDREN,
+                        Node::Leaf(ref slice) => slice.len() < min_len::<M, C>(),
+                        Node::Branch(ref children2) => children2.len() < min_children::<C>(),
                     };
 
                     if child_i == 0 {
@@ -771,8 +904,10 @@ where
                         do_merge |= {
                             start_info.width as usize == widt
                                 && match *children.nodes()[child_i - 1] {
-                                    Node::Leaf(ref slice) => slice.len() < MIN_LEN,
-                                    Node::Branch(ref children2) => children2.len() < MIN_CHILDREN,
+                                    Node::Leaf(ref slice) => slice.len() < min_len::<M, C>(),
+                                    Node::Branch(ref children2) => {
+                                        children2.len() < min_children::<C>()
+                                    }
                                 }
                         };
                         if do_merge {
@@ -807,6 +942,61 @@ where
         }
     }
 
+    /// Same as [fix_tree_seam()][Node::fix_tree_seam], except that it takes
+    /// the index of the seam, rather than its width.
+    ///
+    /// This is able to find seams in between 0 width elements, which can't be
+    /// told apart by their widths alone.
+    ///
+    /// Returns whether it did anything or not that would affect the
+    /// parent. True: did stuff, false: didn't do stuff
+    pub fn fix_tree_seam_at_index(&mut self, index: usize) -> bool {
+        if let Node::Branch(ref mut children) = *self {
+            let mut did_stuff = false;
+            loop {
+                // Do merging
+                if children.len() > 1 {
+                    let (child_i, start_info) = children.search_index(index);
+                    let mut do_merge = children.nodes()[child_i].is_undersized();
+
+                    if child_i == 0 {
+                        if do_merge {
+                            did_stuff |= children.merge_distribute(0, 1);
+                        }
+                    } else {
+                        do_merge |= start_info.len as usize == index
+                            && children.nodes()[child_i - 1].is_undersized();
+                        if do_merge {
+                            did_stuff |= children.merge_distribute(child_i - 1, child_i);
+                        }
+                    }
+                }
+
+                // Do recursion
+                let (child_i, start_info) = children.search_index(index);
+
+                if start_info.len as usize == index && child_i != 0 {
+                    let tmp = children.info()[child_i - 1].0.len as usize;
+                    let effect_1 = Arc::make_mut(&mut children.nodes_mut()[child_i - 1])
+                        .fix_tree_seam_at_index(tmp);
+                    let effect_2 =
+                        Arc::make_mut(&mut children.nodes_mut()[child_i]).fix_tree_seam_at_index(0);
+                    if (!effect_1) && (!effect_2) {
+                        break;
+                    }
+                } else if !Arc::make_mut(&mut children.nodes_mut()[child_i])
+                    .fix_tree_seam_at_index(index - start_info.len as usize)
+                {
+                    break;
+                }
+            }
+            debug_assert!(children.is_info_accurate());
+            did_stuff
+        } else {
+            false
+        }
+    }
+
     pub fn zero_width_end(&self) -> bool {
         match self {
             Node::Leaf(ref leaf) => leaf.zero_width_end(),
@@ -0,0 +1,339 @@
+use std::fmt;
+
+use crate::config::RopeConfig;
+use crate::rope::{Measurable, Rope};
+use crate::tree::{min_children, Node};
+
+/// An invariant of the tree behind a [Rope<M>] that doesn't hold, returned
+/// by [validate()][Rope::validate].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The path from the root to the offending node, as the index of the
+    /// child taken in each branch. An empty path means the root itself.
+    pub path: Vec<usize>,
+    /// Which invariant failed.
+    pub kind: ValidationErrorKind,
+}
+
+/// The kinds of invariants checked by [validate()][Rope::validate].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationErrorKind {
+    /// The length and width that the parent has cached for a node don't
+    /// match its contents.
+    StaleSliceInfo {
+        /// The cached `(len, width)`.
+        cached: (usize, usize),
+        /// The actual `(len, width)`.
+        actual: (usize, usize),
+    },
+
+    /// The `zero_width_end` flag that the parent has cached for a node
+    /// doesn't match its contents.
+    WrongZeroWidthEnd {
+        /// The cached flag.
+        cached: bool,
+    },
+
+    /// The leaves under a node are not at the same depth as the ones under
+    /// its first sibling.
+    UnevenDepth {
+        /// The depth of the first sibling.
+        expected: usize,
+        /// The depth of the node.
+        found: usize,
+    },
+
+    /// A branch has fewer children than allowed. The root may have as few
+    /// as 2, and every other branch must be at least half full.
+    UndersizedNode {
+        /// The amount of children in the branch.
+        children: usize,
+        /// The minimum amount of children for the branch.
+        min: usize,
+    },
+
+    /// A leaf, other than the root, has no elements.
+    EmptyLeaf,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ValidationErrorKind::StaleSliceInfo { cached, actual } => write!(
+                f,
+                "Stale slice info: cached len {}, width {}, but found len {}, width {}",
+                cached.0, cached.1, actual.0, actual.1
+            )?,
+            ValidationErrorKind::WrongZeroWidthEnd { cached } => {
+                write!(f, "Wrong zero width end flag: cached {}", cached)?
+            }
+            ValidationErrorKind::UnevenDepth { expected, found } => {
+                write!(f, "Uneven depth: expected {}, found {}", expected, found)?
+            }
+            ValidationErrorKind::UndersizedNode { children, min } => write!(
+                f,
+                "Undersized node: {} children, at least {} required",
+                children, min
+            )?,
+            ValidationErrorKind::EmptyLeaf => write!(f, "Empty leaf")?,
+        }
+        write!(f, ", at node path {:?}", self.path)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl<M, C> Rope<M, C>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    /// Checks that the tree behind the [Rope<M>] is well formed.
+    ///
+    /// Every [Rope<M>] built and edited through this crate is always valid,
+    /// so this is only useful for ropes that may have been built incorrectly,
+    /// for example, through
+    /// [RopeBuilder::_append_chunk()][crate::RopeBuilder::_append_chunk].
+    ///
+    /// Checks, for every node, that:
+    ///
+    /// - The length, width and `zero_width_end` flag cached by its parent
+    ///   match its contents.
+    /// - All of its leaves are at the same depth.
+    /// - It has enough children, if it is a branch.
+    /// - It is not empty, if it is a leaf other than the root.
+    ///
+    /// Runs in O(N) time, but only visits the nodes of the tree, not its
+    /// elements.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first failed invariant found, in depth first order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use any_rope::Rope;
+    /// # use any_rope::Lipsum::*;
+    /// let rope = Rope::from_slice(&[Lorem, Ipsum, Dolor(3), Sit].repeat(1000));
+    ///
+    /// assert!(rope.validate().is_ok());
+    /// ```
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut path = Vec::new();
+        validate_node(&self.root, &mut path, true).map(|_| ())
+    }
+}
+
+/// Validates `node` and its descendants, returning the depth of its leaves.
+fn validate_node<M, C>(
+    node: &Node<M, C>,
+    path: &mut Vec<usize>,
+    is_root: bool,
+) -> Result<usize, ValidationError>
+where
+    M: Measurable,
+    C: RopeConfig,
+{
+    let error = |path: &[usize], kind| ValidationError {
+        path: path.to_vec(),
+        kind,
+    };
+
+    let children = match *node {
+        Node::Leaf(ref slice) => {
+            if !is_root && slice.is_empty() {
+                return Err(error(path, ValidationErrorKind::EmptyLeaf));
+            }
+            return Ok(0);
+        }
+        Node::Branch(ref children) => children,
+    };
+
+    let min = if is_root { 2 } else { min_children::<C>() };
+    if children.len() < min {
+        let kind = ValidationErrorKind::UndersizedNode {
+            children: children.len(),
+            min,
+        };
+        return Err(error(path, kind));
+    }
+
+    let mut depth = None;
+    for (i, ((info, zero_width_end), child)) in children.iter().enumerate() {
+        path.push(i);
+
+        let actual = child.slice_info();
+        if *info != actual {
+            let kind = ValidationErrorKind::StaleSliceInfo {
+                cached: (info.len as usize, info.width as usize),
+                actual: (actual.len as usize, actual.width as usize),
+            };
+            return Err(error(path, kind));
+        }
+        if *zero_width_end != child.zero_width_end() {
+            let kind = ValidationErrorKind::WrongZeroWidthEnd {
+                cached: *zero_width_end,
+            };
+            return Err(error(path, kind));
+        }
+
+        let child_depth = validate_node(child, path, false)?;
+        match depth {
+            Some(expected) if expected != child_depth => {
+                let kind = ValidationErrorKind::UnevenDepth {
+                    expected,
+                    found: child_depth,
+                };
+                return Err(error(path, kind));
+            }
+            _ => depth = Some(child_depth),
+        }
+
+        path.pop();
+    }
+
+    Ok(depth.unwrap() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::tree::{BranchChildren, LeafSlice, SliceInfo};
+    use crate::DefaultConfig;
+    use crate::Lipsum::{self, *};
+
+    fn lorem_ipsum() -> Vec<Lipsum> {
+        [Lorem, Ipsum, Dolor(3), Sit, Consectur("amet")].repeat(20)
+    }
+
+    /// Applies `f` to the children of the first branch of leaves in the
+    /// tree, keeping the cached information above it up to date, and
+    /// pushes the path to that branch onto `path`.
+    fn edit_first_branch<F>(
+        node: &mut Arc<Node<Lipsum, DefaultConfig>>,
+        path: &mut Vec<usize>,
+        f: F,
+    ) where
+        F: FnOnce(&mut BranchChildren<Lipsum, DefaultConfig>),
+    {
+        let children = Arc::make_mut(node).children_mut();
+        if children.nodes()[0].is_leaf() {
+            f(children);
+        } else {
+            path.push(0);
+            edit_first_branch(&mut children.nodes_mut()[0], path, f);
+            children.update_child_info(0);
+        }
+    }
+
+    #[test]
+    fn validate_01() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        assert_eq!(rope.validate(), Ok(()));
+
+        let mut width = 0;
+        while width < rope.width() {
+            rope.remove(width..(width + 3).min(rope.width()));
+            assert_eq!(rope.validate(), Ok(()));
+            width += 7;
+        }
+
+        let empty: Rope<Lipsum> = Rope::new();
+        assert_eq!(empty.validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_02() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let mut path = Vec::new();
+        edit_first_branch(&mut rope.root, &mut path, |children| {
+            children.info_mut()[1].0.width += 1;
+        });
+        path.push(1);
+
+        let error = rope.validate().unwrap_err();
+        assert_eq!(error.path, path);
+        assert!(matches!(
+            error.kind,
+            ValidationErrorKind::StaleSliceInfo { cached, actual } if cached.1 == actual.1 + 1
+        ));
+    }
+
+    #[test]
+    fn validate_03() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let mut path = Vec::new();
+        edit_first_branch(&mut rope.root, &mut path, |children| {
+            let (info, nodes) = children.data_mut();
+            nodes[0] = Arc::new(Node::Leaf(LeafSlice::from_slice(&[Sit; 4])));
+            info[0] = (SliceInfo::from_slice(&[Sit; 4]), false);
+        });
+        path.push(0);
+
+        let error = rope.validate().unwrap_err();
+        assert_eq!(error.path, path);
+        assert_eq!(
+            error.kind,
+            ValidationErrorKind::WrongZeroWidthEnd { cached: false }
+        );
+    }
+
+    #[test]
+    fn validate_04() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let mut path = Vec::new();
+        edit_first_branch(&mut rope.root, &mut path, |children| {
+            let (info, nodes) = children.data_mut();
+            nodes[1] = Arc::new(Node::new());
+            info[1] = (SliceInfo::new(), false);
+        });
+        path.push(1);
+
+        let error = rope.validate().unwrap_err();
+        assert_eq!(error.path, path);
+        assert_eq!(error.kind, ValidationErrorKind::EmptyLeaf);
+    }
+
+    #[test]
+    fn validate_05() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let mut path = Vec::new();
+        edit_first_branch(&mut rope.root, &mut path, |children| {
+            while children.len() > 1 {
+                children.pop();
+            }
+        });
+
+        let error = rope.validate().unwrap_err();
+        assert_eq!(error.path, path);
+        assert!(matches!(
+            error.kind,
+            ValidationErrorKind::UndersizedNode { children: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn validate_06() {
+        let mut rope = Rope::from_slice(lorem_ipsum().as_slice());
+        let depth = rope.root.depth();
+
+        let children = Arc::make_mut(&mut rope.root).children_mut();
+        children.nodes_mut()[1] = Arc::new(Node::Leaf(LeafSlice::from_slice(&[Lorem, Ipsum])));
+        children.update_child_info(1);
+
+        let error = rope.validate().unwrap_err();
+        assert_eq!(error.path, vec![1]);
+        assert_eq!(
+            error.kind,
+            ValidationErrorKind::UnevenDepth {
+                expected: depth - 1,
+                found: 0
+            }
+        );
+        assert!(error.to_string().starts_with("Uneven depth"));
+    }
+}
@@ -0,0 +1,67 @@
+use any_rope::{
+    Edit,
+    Lipsum::{self, *},
+    Rope,
+};
+use rand::Rng;
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn apply_edits_random() {
+    let mut rng = rand::thread_rng();
+    let vec = [Lorem, Ipsum, Dolor(3), Ipsum].repeat(2000);
+    let rope = Rope::from_slice(&vec);
+
+    for _ in 0..20 {
+        // Edit boundaries, as sorted indices of the original elements.
+        let mut bounds: Vec<usize> = (0..200)
+            .map(|_| rng.gen::<usize>() % (vec.len() + 1))
+            .collect();
+        bounds.sort_unstable();
+
+        let mut edits = Vec::new();
+        let mut expected = vec.clone();
+        for pair in bounds.chunks(2).rev() {
+            let (start, end) = (pair[0], pair[1]);
+            let slice = vec![Sit, Dolor(start % 5), Amet];
+            let range = rope.index_to_width(start)..rope.index_to_width(end);
+
+            let edit = match rng.gen::<usize>() % 3 {
+                0 => {
+                    expected.splice(start..start, slice.iter().copied());
+                    Edit::Insert(range.start, slice)
+                }
+                1 => {
+                    expected.splice(start..end, []);
+                    Edit::Remove(range)
+                }
+                _ => {
+                    expected.splice(start..end, slice.iter().copied());
+                    Edit::Replace(range, slice)
+                }
+            };
+            edits.push(edit);
+        }
+
+        let mut edited = rope.clone();
+        edited.apply_edits(edits);
+
+        assert_eq!(edited, expected.as_slice());
+        edited.assert_integrity();
+        edited.assert_invariants();
+    }
+}
+
+#[test]
+fn apply_edits_map() {
+    let vec: Vec<Lipsum> = [Lorem, Ipsum, Dolor(3)].repeat(100);
+    let mut rope = Rope::from_slice(&vec);
+
+    let map = rope.apply_edits((0..100).map(|i| Edit::Replace(i * 6..i * 6 + 1, vec![Ipsum])));
+
+    assert_eq!(rope, [Ipsum, Ipsum, Dolor(3)].repeat(100).as_slice());
+    for i in 0..100 {
+        assert_eq!(map.map_width(i * 6 + 1), i * 7 + 2);
+        assert_eq!(map.map_index(i * 3 + 1), i * 3 + 1);
+    }
+}
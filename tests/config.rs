@@ -0,0 +1,213 @@
+use any_rope::{
+    CacheWidths,
+    Lipsum::{self, *},
+    Measurable, NoCache, Rope, RopeBuilder, RopeConfig, SmallConfig,
+};
+use rand::Rng;
+
+/// A configuration that is neither the default, nor the smallest possible.
+#[derive(Debug, Clone, Copy)]
+struct MediumConfig;
+
+impl RopeConfig for MediumConfig {
+    const MAX_CHILDREN: usize = 7;
+    const LEAF_BYTES: usize = 256;
+    type Cache = NoCache;
+}
+
+/// A configuration with wide branches and cached widths.
+#[derive(Debug, Clone, Copy)]
+struct CachedConfig;
+
+impl RopeConfig for CachedConfig {
+    const MAX_CHILDREN: usize = 24;
+    const LEAF_BYTES: usize = 256;
+    type Cache = CacheWidths;
+}
+
+/// The same as [CachedConfig], but without cached widths, so that both build
+/// the same trees.
+#[derive(Debug, Clone, Copy)]
+struct UncachedConfig;
+
+impl RopeConfig for UncachedConfig {
+    const MAX_CHILDREN: usize = 24;
+    const LEAF_BYTES: usize = 256;
+    type Cache = NoCache;
+}
+
+fn random_edits<C: RopeConfig>(mut rope: Rope<Lipsum, C>, mut vec: Vec<Lipsum>) {
+    let mut rng = rand::thread_rng();
+    let elements = [Lorem, Ipsum, Dolor(3), Sit, Amet, Adipiscing(true)];
+
+    for _ in 0..1000 {
+        let index = rng.gen::<usize>() % (vec.len() + 1);
+        let element = elements[rng.gen::<usize>() % elements.len()];
+        let width = rope.index_to_width(index);
+
+        // Inserting at a width goes after any 0 width elements there.
+        let index = rope.end_width_to_index(width);
+        rope.insert(width, element);
+        vec.insert(index, element);
+    }
+
+    assert_eq!(rope, vec.as_slice());
+    rope.assert_integrity();
+    rope.assert_invariants();
+
+    for _ in 0..200 {
+        let start = rng.gen::<usize>() % (rope.width() + 1);
+        let end = (start + rng.gen::<usize>() % 20).min(rope.width());
+        let cut = rope.cut(start..end);
+        let width = rng.gen::<usize>() % (rope.width() + 1);
+        rope.insert_rope(width, cut);
+    }
+
+    assert_eq!(rope.len(), vec.len());
+    rope.assert_integrity();
+    rope.assert_invariants();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn small_config() {
+    let vec = [Lorem, Ipsum, Dolor(5), Sit, Amet].repeat(200);
+    let rope = Rope::<_, SmallConfig>::from_slice_with_config(&vec);
+
+    assert_eq!(rope, vec.as_slice());
+    rope.assert_integrity();
+    rope.assert_invariants();
+
+    random_edits(rope, vec);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn custom_config() {
+    let vec = [Lorem, Ipsum, Dolor(5), Sit, Amet].repeat(200);
+
+    let mut builder = RopeBuilder::<_, MediumConfig>::new_with_config();
+    for chunk in vec.chunks(7) {
+        builder.append_slice(chunk);
+    }
+    let rope = builder.finish();
+
+    assert_eq!(rope, vec.as_slice());
+    rope.assert_integrity();
+    rope.assert_invariants();
+
+    random_edits(rope, vec);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn config_from_iter() {
+    let rope = Rope::<Lipsum, SmallConfig>::from_iter_with_config([Lorem, Ipsum, Sit].repeat(100));
+    assert_eq!(
+        Rope::<Lipsum, SmallConfig>::from_chunks_with_config([[Lorem, Ipsum, Sit]; 100]),
+        rope
+    );
+    let slice = rope.width_slice(10..200);
+    let owned: Rope<Lipsum, SmallConfig> = slice.into();
+
+    assert_eq!(owned, slice);
+    owned.assert_integrity();
+    owned.assert_invariants();
+}
+
+/// The index of the element that starts at `width`, found by going through
+/// the elements one by one.
+fn start_width_to_index(vec: &[Lipsum], width: usize) -> usize {
+    let mut end = 0;
+    vec.iter()
+        .position(|element| {
+            end += element.width();
+            end > width || (element.width() == 0 && end >= width)
+        })
+        .unwrap_or(vec.len())
+}
+
+/// The index of the element that ends at `width`, past any elements with a
+/// width of 0 there, found by going through the elements one by one.
+fn end_width_to_index(vec: &[Lipsum], width: usize) -> usize {
+    let mut start = 0;
+    vec.iter()
+        .position(|element| {
+            let found = start > width || (element.width() != 0 && start == width);
+            start += element.width();
+            found
+        })
+        .unwrap_or(vec.len())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn cached_widths() {
+    let mut rng = rand::thread_rng();
+    let mut vec = [Lorem, Ipsum, Dolor(5), Sit, Amet].repeat(2000);
+    let mut rope = Rope::<_, UncachedConfig>::from_slice_with_config(&vec);
+    let mut cached = Rope::<_, CachedConfig>::from_slice_with_config(&vec);
+    let mut medium = Rope::<_, MediumConfig>::from_slice_with_config(&vec);
+
+    for _ in 0..100 {
+        let start = rng.gen::<usize>() % (rope.width() + 1);
+        let end = (start + rng.gen::<usize>() % 20).min(rope.width());
+        rope.remove(start..end);
+        cached.remove(start..end);
+        medium.remove(start..end);
+
+        let width = rng.gen::<usize>() % (rope.width() + 1);
+        rope.insert_slice(width, &[Dolor(2), Sit, Lorem]);
+        cached.insert_slice(width, &[Dolor(2), Sit, Lorem]);
+        medium.insert_slice(width, &[Dolor(2), Sit, Lorem]);
+
+        vec = Vec::from(&rope);
+        assert_eq!(cached, vec.as_slice());
+        assert_eq!(medium, vec.as_slice());
+
+        // Each rope is checked against the elements themselves, so that no
+        // tree shape or cache can hide a wrong answer from another one.
+        let slice = medium.width_slice(..);
+        let mut reader = medium.reader();
+        for _ in 0..20 {
+            let width = rng.gen::<usize>() % (rope.width() + 1);
+            let index = start_width_to_index(&vec, width);
+            assert_eq!(rope.start_width_to_index(width), index);
+            assert_eq!(cached.start_width_to_index(width), index);
+            assert_eq!(medium.start_width_to_index(width), index);
+            assert_eq!(slice.start_width_to_index(width), index);
+            assert_eq!(reader.start_width_to_index(width), index);
+
+            let index = end_width_to_index(&vec, width);
+            assert_eq!(rope.end_width_to_index(width), index);
+            assert_eq!(cached.end_width_to_index(width), index);
+            assert_eq!(medium.end_width_to_index(width), index);
+            assert_eq!(slice.end_width_to_index(width), index);
+            assert_eq!(reader.end_width_to_index(width), index);
+            assert_eq!(rope.get_from_width(width), cached.get_from_width(width));
+
+            // Slices can start and end among elements with a width of 0.
+            let end = rng.gen::<usize>() % (vec.len() + 1);
+            let part = medium.index_slice(end / 2..end);
+            let part_vec = Vec::from(part);
+            let part_width = rng.gen::<usize>() % (part.width() + 1);
+            assert_eq!(
+                part.start_width_to_index(part_width),
+                start_width_to_index(&part_vec, part_width)
+            );
+            assert_eq!(
+                part.end_width_to_index(part_width),
+                end_width_to_index(&part_vec, part_width)
+            );
+
+            let index = rng.gen::<usize>() % (rope.len() + 1);
+            let width: usize = vec[..index].iter().map(Measurable::width).sum();
+            assert_eq!(rope.index_to_width(index), width);
+            assert_eq!(cached.index_to_width(index), width);
+            assert_eq!(medium.index_to_width(index), width);
+        }
+    }
+
+    cached.assert_integrity();
+    cached.assert_invariants();
+}
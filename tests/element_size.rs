@@ -0,0 +1,72 @@
+use any_rope::{Measurable, Rope};
+use rand::Rng;
+
+/// An element much bigger than the space a leaf has for inline storage.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Big([u64; 256]);
+
+impl Measurable for Big {
+    fn width(&self) -> usize {
+        (self.0[0] % 3) as usize
+    }
+}
+
+/// A zero sized element.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Unit;
+
+impl Measurable for Unit {
+    fn width(&self) -> usize {
+        1
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn big_elements() {
+    let mut rng = rand::thread_rng();
+    let mut rope = Rope::new();
+    let mut vec = Vec::new();
+
+    for i in 0..500 {
+        let big = Big([i; 256]);
+        let index = rng.gen::<usize>() % (vec.len() + 1);
+        let width = rope.index_to_width(index);
+
+        // Inserting at a width goes after any 0 width elements there.
+        let index = rope.end_width_to_index(width);
+        rope.insert(width, big);
+        vec.insert(index, big);
+    }
+
+    assert_eq!(rope, vec);
+    rope.assert_integrity();
+    rope.assert_invariants();
+
+    for _ in 0..100 {
+        let start = rng.gen::<usize>() % (rope.width() + 1);
+        let end = (start + rng.gen::<usize>() % 8).min(rope.width());
+        let cut = rope.cut(start..end);
+        rope.insert_rope(start.min(rope.width()), cut);
+    }
+
+    assert_eq!(rope.len(), vec.len());
+    rope.assert_integrity();
+    rope.assert_invariants();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn zero_sized_elements() {
+    let mut rope = Rope::from_slice(&[Unit; 10000]);
+    assert_eq!(rope.len(), 10000);
+    assert_eq!(rope.width(), 10000);
+
+    rope.remove(20..9000);
+    rope.insert_slice(10, &[Unit; 5000]);
+    assert_eq!(rope.len(), 6020);
+    assert!(rope.iter().all(|(_, unit)| unit == Unit));
+
+    rope.assert_integrity();
+    rope.assert_invariants();
+}
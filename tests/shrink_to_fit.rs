@@ -71,3 +71,44 @@ fn shrink_to_fit() {
     rope2.assert_integrity();
     rope2.assert_invariants();
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn compact_range() {
+    let mut rng = rand::thread_rng();
+    let mut rope = Rope::new();
+
+    // Do a bunch of random incoherent inserts
+    for _ in 0..1000 {
+        let width = rope.width().max(1);
+        rope.insert_slice(
+            rng.gen::<usize>() % width,
+            random_slice(&mut rng).as_slice(),
+        );
+    }
+
+    let rope2 = rope.clone();
+
+    // Compact it in random pieces, and then as a whole.
+    for _ in 0..20 {
+        let start = rng.gen::<usize>() % (rope.width() + 1);
+        let end = (start + rng.gen::<usize>() % 500).min(rope.width());
+        rope.compact_range(start..end);
+
+        assert_eq!(rope, rope2);
+        rope.assert_integrity();
+        rope.assert_invariants();
+    }
+    rope.compact_range(..);
+
+    assert_eq!(rope, rope2);
+    assert!(rope.capacity() < rope2.capacity());
+    assert!(rope.fragmentation().leaves < rope2.fragmentation().leaves);
+
+    // Make sure the rope is sound
+    rope.assert_integrity();
+    rope.assert_invariants();
+
+    rope2.assert_integrity();
+    rope2.assert_invariants();
+}